@@ -0,0 +1,54 @@
+// Offers a reload when the app's code fails to load. See src/reload_prompt.rs.
+(function () {
+  function hydrated() {
+    return document.documentElement.hasAttribute("data-hydrated");
+  }
+
+  // The message browsers give when an `import()` of a code chunk fails.
+  function isChunkLoadError(error) {
+    var message = String(error && error.message ? error.message : error);
+    return /dynamically imported module|Importing a module script failed/.test(message);
+  }
+
+  function offerReload() {
+    if (document.getElementById("reload-prompt") || !document.body) {
+      return;
+    }
+    var prompt = document.createElement("div");
+    prompt.id = "reload-prompt";
+    prompt.setAttribute("role", "alert");
+    prompt.style.cssText =
+      "position: fixed; left: 0; right: 0; bottom: 0; z-index: 2147483646; padding: 1rem; " +
+      "background: #1e3a8a; color: white; font-family: sans-serif; text-align: center";
+    prompt.textContent = "A new version of this site is available. ";
+    var button = document.createElement("button");
+    button.textContent = "Reload";
+    button.style.cssText =
+      "margin-left: 1rem; padding: 0.25rem 0.75rem; border-radius: 0.25rem; background: white; color: #1e3a8a";
+    button.onclick = function () {
+      location.reload();
+    };
+    prompt.appendChild(button);
+    document.body.appendChild(prompt);
+  }
+
+  // A <script> that couldn't be loaded, e.g. /pkg/tailwind.js. Load errors don't bubble, but they do
+  // pass by `window` in the capture phase (the `true`).
+  addEventListener(
+    "error",
+    function (event) {
+      if (event.target && event.target.tagName === "SCRIPT" && !hydrated()) {
+        offerReload();
+      }
+    },
+    true
+  );
+
+  // A promise nobody handled failed: before hydration that is loading the WASM (`init(...)`), after
+  // it only a failed chunk `import()` counts.
+  addEventListener("unhandledrejection", function (event) {
+    if (!hydrated() || isChunkLoadError(event.reason)) {
+      offerReload();
+    }
+  });
+})();
@@ -0,0 +1,84 @@
+use crate::context::expect_context;
+use leptos::*;
+use std::time::Duration;
+
+// Screen reader announcements. Changes that are obvious when you look at the page ("5 results
+// loaded", "item deleted") are silent for someone using a screen reader, unless the text ends up in an
+// `aria-live` region. `App` renders a `<LiveRegion/>` and any component can push a message into it
+// with `use_announcer(cx).announce("...")`.
+
+/// How long a message stays in the region. Clearing it afterwards keeps stale messages from being
+/// read again when the user browses the page.
+const CLEAR_AFTER: Duration = Duration::from_secs(5);
+
+#[derive(Copy, Clone)]
+pub struct Announcer {
+    polite: RwSignal<String>,
+    assertive: RwSignal<String>,
+    // Bumped on every announcement so that only the newest one gets cleared by its timer.
+    generation: StoredValue<usize>,
+}
+
+impl Announcer {
+    /// Read out when the screen reader is done with what it is currently saying. Use this for most things.
+    pub fn announce(&self, message: impl Into<String>) {
+        self.push(self.polite, message.into());
+    }
+
+    /// Interrupts whatever the screen reader is saying. Only for urgent things like errors.
+    pub fn announce_assertive(&self, message: impl Into<String>) {
+        self.push(self.assertive, message.into());
+    }
+
+    fn push(&self, region: RwSignal<String>, message: String) {
+        self.generation.update_value(|generation| *generation += 1);
+        let generation = self.generation.get_value();
+
+        // Screen readers only react to changes, so announcing the same text twice in a row would be
+        // silent. Emptying the region first and filling it on the next frame makes it a change every time.
+        region.set(String::new());
+        request_animation_frame(move || region.set(message));
+
+        let generation_now = self.generation;
+        set_timeout(
+            move || {
+                if generation_now.try_get_value() == Some(generation) {
+                    region.set(String::new());
+                }
+            },
+            CLEAR_AFTER,
+        );
+    }
+}
+
+/// Provides the announcer to every component below `cx`. Render a `<LiveRegion/>` in the same scope.
+pub fn provide_announcer(cx: Scope) -> Announcer {
+    let announcer = Announcer {
+        polite: create_rw_signal(cx, String::new()),
+        assertive: create_rw_signal(cx, String::new()),
+        generation: store_value(cx, 0),
+    };
+    provide_context(cx, announcer);
+    announcer
+}
+
+/// Pulls the announcer out of context.
+pub fn use_announcer(cx: Scope) -> Announcer {
+    expect_context::<Announcer>(cx, "App")
+}
+
+/// The (visually hidden) live regions the announcements are written to. The regions have to be in the
+/// page before a message is put into them, which is why they are always rendered, even when empty.
+#[component]
+pub fn LiveRegion(cx: Scope) -> impl IntoView {
+    let announcer = use_announcer(cx);
+
+    view! { cx,
+        <div class="sr-only" role="status" aria-live="polite" aria-atomic="true">
+            {move || announcer.polite.get()}
+        </div>
+        <div class="sr-only" role="alert" aria-live="assertive" aria-atomic="true">
+            {move || announcer.assertive.get()}
+        </div>
+    }
+}
@@ -0,0 +1,314 @@
+use cfg_if::cfg_if;
+
+// Plain Axum handlers for the `/api/...` routes that aren't server functions. They only exist on the
+// server, main.rs adds them to the router.
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use crate::characters::{initial_characters, Character};
+    use crate::versioned::{self, Saved, Versioned};
+    use axum::body::{Body, Bytes};
+    use axum::extract::{FromRef, Path, Query, RawQuery, State};
+    use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use axum::response::{IntoResponse, Response};
+    use axum::Json;
+    use futures::stream::{self, Stream, StreamExt};
+    use leptos::{provide_context, LeptosOptions};
+    use log::{info, warn};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex, PoisonError};
+    use std::time::{Duration, Instant};
+    use tokio::sync::watch;
+
+    /// Everything the handlers share. Axum hands each handler the part it asks for, e.g.
+    /// `State<LeptosOptions>` or `State<AppState>`, see the `FromRef` impl below.
+    #[derive(Clone)]
+    pub struct AppState {
+        pub leptos_options: LeptosOptions,
+        /// When the server started, for the uptime in `/api/health`.
+        pub started: Instant,
+        /// For requests to other servers. A single client reuses its connections, so we share one.
+        pub http: reqwest::Client,
+        pub amiibo_cache: AmiiboCache,
+        /// The rows of the data table example.
+        pub characters: CharacterStore,
+        /// Turns `true` once the server is asked to stop, see `shutdown_signal` in main.rs.
+        pub shutting_down: watch::Receiver<bool>,
+    }
+
+    impl FromRef<AppState> for LeptosOptions {
+        fn from_ref(state: &AppState) -> Self {
+            state.leptos_options.clone()
+        }
+    }
+
+    #[derive(Serialize)]
+    pub struct Health {
+        status: &'static str,
+        version: &'static str,
+        uptime_seconds: u64,
+    }
+
+    /// `GET /api/health`: answers `200 OK` as long as the server is up. Meant for load balancers and uptime checks.
+    pub async fn health(State(state): State<AppState>) -> Json<Health> {
+        Json(Health {
+            status: "ok",
+            version: env!("CARGO_PKG_VERSION"),
+            uptime_seconds: state.started.elapsed().as_secs(),
+        })
+    }
+
+    #[derive(Serialize)]
+    pub struct Version {
+        name: &'static str,
+        version: &'static str,
+        /// "debug" or "release".
+        profile: &'static str,
+    }
+
+    /// `GET /api/version`: what is running, filled in by cargo at compile time.
+    pub async fn version() -> Json<Version> {
+        Json(Version {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+        })
+    }
+
+    /// Usernames that are taken, as far as `/api/username` is concerned. A real app would look in its database.
+    const TAKEN_USERNAMES: [&str; 5] = ["admin", "root", "leptos", "ferris", "tailwind"];
+
+    #[derive(Deserialize)]
+    pub struct UsernameQuery {
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    pub struct UsernameAvailability {
+        available: bool,
+    }
+
+    /// `GET /api/username?name=ferris`: whether the name is still free, for the Form validation example.
+    /// It takes its time on purpose, like a real database would on a bad day, so the form's "Checking…" is
+    /// there long enough to be seen.
+    pub async fn username(Query(query): Query<UsernameQuery>) -> Json<UsernameAvailability> {
+        tokio::time::sleep(Duration::from_millis(700)).await;
+        let name = query.name.to_lowercase();
+        Json(UsernameAvailability {
+            available: !TAKEN_USERNAMES.contains(&name.as_str()),
+        })
+    }
+
+    /// How long an answer of the Amiibo API is reused before we ask again.
+    const AMIIBO_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+    /// Recent answers of the Amiibo API, by name. Cloning it gives another handle to the same cache.
+    ///
+    /// Requests are handled on several threads at once, hence the `Mutex`. It is only locked for a
+    /// quick look-up or insert and never while waiting for the Amiibo API, so requests don't wait on
+    /// each other. Two requests for the same name that both miss the cache will both ask the Amiibo
+    /// API, the second answer simply replaces the first.
+    #[derive(Clone, Default)]
+    pub struct AmiiboCache(Arc<Mutex<HashMap<String, CachedAnswer>>>);
+
+    struct CachedAnswer {
+        fetched: Instant,
+        content_type: HeaderValue,
+        body: Bytes,
+    }
+
+    impl AmiiboCache {
+        fn get(&self, name: &str) -> Option<(HeaderValue, Bytes)> {
+            self.entries()
+                .get(name)
+                .filter(|answer| answer.fetched.elapsed() < AMIIBO_CACHE_TTL)
+                .map(|answer| (answer.content_type.clone(), answer.body.clone()))
+        }
+
+        fn insert(&self, name: String, content_type: HeaderValue, body: Bytes) {
+            let fetched = Instant::now();
+            self.entries().insert(name, CachedAnswer { fetched, content_type, body });
+        }
+
+        /// Expired answers are never served, but they would stay in memory forever if nobody asks for
+        /// that name again. This clears them out every `AMIIBO_CACHE_TTL`.
+        pub fn purge_periodically(&self) {
+            let cache = self.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(AMIIBO_CACHE_TTL);
+                loop {
+                    interval.tick().await;
+                    cache.entries().retain(|_, answer| answer.fetched.elapsed() < AMIIBO_CACHE_TTL);
+                }
+            });
+        }
+
+        fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedAnswer>> {
+            // A panic while the lock was held "poisons" it. The map is still fine for a cache, keep using it.
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    /// The rows of the data table example, see characters.rs. They live in memory, so a restart brings
+    /// back the initial ones. Cloning it gives another handle to the same rows.
+    #[derive(Clone)]
+    pub struct CharacterStore(Arc<Mutex<Vec<Versioned<Character>>>>);
+
+    impl Default for CharacterStore {
+        fn default() -> Self {
+            Self(Arc::new(Mutex::new(initial_characters())))
+        }
+    }
+
+    impl CharacterStore {
+        pub fn list(&self) -> Vec<Versioned<Character>> {
+            self.rows().clone()
+        }
+
+        /// Saves `character` as row `id` if nobody saved it since `version`, see `versioned::save`.
+        pub fn save(&self, id: usize, version: u64, character: Character) -> Saved<Character> {
+            versioned::save(&mut self.rows(), id, version, character)
+        }
+
+        fn rows(&self) -> std::sync::MutexGuard<'_, Vec<Versioned<Character>>> {
+            // A save either happens completely or not at all, a panic can't leave a row half written.
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct AmiiboQuery {
+        name: Option<String>,
+    }
+
+    /// An answer of the Amiibo API, see `ask_amiibo_api`.
+    pub struct AmiiboAnswer {
+        pub status: StatusCode,
+        pub content_type: HeaderValue,
+        pub retry_after: Option<HeaderValue>,
+        pub body: Bytes,
+    }
+
+    impl AmiiboAnswer {
+        /// Our own answer, for when the Amiibo API didn't give us a usable one.
+        fn bad_gateway(message: &'static str) -> Self {
+            AmiiboAnswer {
+                status: StatusCode::BAD_GATEWAY,
+                content_type: HeaderValue::from_static("text/plain; charset=utf-8"),
+                retry_after: None,
+                body: Bytes::from_static(message.as_bytes()),
+            }
+        }
+    }
+
+    impl IntoResponse for AmiiboAnswer {
+        fn into_response(self) -> Response {
+            let mut response = (self.status, [(header::CONTENT_TYPE, self.content_type)], self.body).into_response();
+            if let Some(retry_after) = self.retry_after {
+                response.headers_mut().insert(header::RETRY_AFTER, retry_after);
+            }
+            response
+        }
+    }
+
+    /// `GET /api/amiibo?name=mario`: asks the Amiibo API on behalf of the browser and passes its JSON on
+    /// as it is. The browser only talks to us, so it doesn't matter whether the Amiibo API allows requests
+    /// from other sites (CORS), and there is one place to change if the API ever moves. The fetch example
+    /// gets its list from the `get_amiibo` server function, this is for "Inspect the raw response".
+    pub async fn amiibo(State(state): State<AppState>, Query(query): Query<AmiiboQuery>) -> Response {
+        ask_amiibo_api(&state, query.name).await.into_response()
+    }
+
+    /// Asks the Amiibo API for the amiibo called `name`, or for all of them. Used by `GET /api/amiibo` and by the
+    /// `get_amiibo` server function. Successful answers are cached for a few minutes, see `AmiiboCache`.
+    pub async fn ask_amiibo_api(state: &AppState, name: Option<String>) -> AmiiboAnswer {
+        let cache_key = name.clone().unwrap_or_default();
+        if let Some((content_type, body)) = state.amiibo_cache.get(&cache_key) {
+            return AmiiboAnswer { status: StatusCode::OK, content_type, retry_after: None, body };
+        }
+
+        let mut request = state.http.get("https://www.amiiboapi.com/api/amiibo/");
+        if let Some(name) = &name {
+            request = request.query(&[("name", name)]);
+        }
+        let upstream = match request.send().await {
+            Ok(upstream) => upstream,
+            Err(error) => {
+                warn!("Amiibo API unreachable: {error}");
+                return AmiiboAnswer::bad_gateway("Amiibo API unreachable");
+            }
+        };
+        // A 404 (no amiibo with that name) or 429 (slow down) means the same to the browser as it means to us,
+        // so it is passed on. A broken upstream is a 502 Bad Gateway: the problem is behind us, not in the request.
+        let status = match upstream.status().as_u16() {
+            status if status >= 500 => StatusCode::BAD_GATEWAY,
+            status => StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY),
+        };
+        let retry_after = upstream.headers().get(header::RETRY_AFTER).cloned();
+        let content_type = upstream
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .cloned()
+            .unwrap_or_else(|| header::HeaderValue::from_static("application/json"));
+        match upstream.bytes().await {
+            Ok(body) => {
+                // Errors aren't cached, the next request should try again.
+                if status == StatusCode::OK {
+                    state.amiibo_cache.insert(cache_key, content_type.clone(), body.clone());
+                }
+                AmiiboAnswer { status, content_type, retry_after, body }
+            }
+            Err(error) => {
+                warn!("Reading the Amiibo API response failed: {error}");
+                AmiiboAnswer::bad_gateway("Amiibo API response incomplete")
+            }
+        }
+    }
+
+    /// `POST /api/...`: the server functions, e.g. `get_amiibo` in app.rs. The same as `leptos_axum::handle_server_fns`,
+    /// but with the `AppState` in context, like the pages have it.
+    pub async fn server_fns(
+        State(state): State<AppState>,
+        path: Path<String>,
+        headers: HeaderMap,
+        query: RawQuery,
+        request: Request<Body>,
+    ) -> impl IntoResponse {
+        leptos_axum::handle_server_fns_with_context(path, headers, query, move |cx| provide_context(cx, state.clone()), request)
+            .await
+    }
+
+    /// `GET /api/ticks`: server-sent events with the current time, one every second. Read by the /sse page.
+    pub async fn ticks(State(state): State<AppState>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+        info!("SSE client connected");
+        let interval = tokio::time::interval(Duration::from_secs(1));
+        // `unfold` turns the interval into a stream: every tick produces one event. The guard travels along
+        // in the stream's state, so it is dropped together with the stream.
+        let stream = stream::unfold((interval, DisconnectGuard), |(mut interval, guard)| async move {
+            interval.tick().await;
+            let now = chrono::Utc::now().format("%H:%M:%S UTC").to_string();
+            Some((Ok(Event::default().data(now)), (interval, guard)))
+        });
+        // The stream never ends on its own, and a graceful shutdown waits for every response to finish. So it ends
+        // when the server is asked to stop, otherwise one open /sse page would keep the server from ever stopping.
+        let mut shutting_down = state.shutting_down;
+        let stream = stream.take_until(async move {
+            _ = shutting_down.wait_for(|shutting_down| *shutting_down).await;
+        });
+        // Proxies and load balancers tend to close connections that stay quiet for too long. The keep-alive
+        // sends a comment line (ignored by the browser) whenever nothing else was sent for 15 seconds.
+        Sse::new(stream).keep_alive(KeepAlive::default())
+    }
+
+    /// Axum drops the stream when the client goes away (tab closed, navigated elsewhere), which stops
+    /// the interval. This just makes that visible in the server log.
+    struct DisconnectGuard;
+
+    impl Drop for DisconnectGuard {
+        fn drop(&mut self) {
+            info!("SSE client disconnected");
+        }
+    }
+}}
@@ -1,10 +1,95 @@
-use chrono::{Datelike, NaiveDate};
-use leptos::{error::Result, *};
-use leptos::{html::Li, *};
-use leptos_meta::{provide_meta_context, Link, Stylesheet, Title};
-use leptos_router::{Route, Router, Routes};
-use log::info;
-use log::Level;
+use crate::announce::{provide_announcer, LiveRegion};
+use crate::debounce::provide_timings;
+use crate::elapsed::OpenFor;
+use crate::error_page::ErrorPage;
+use crate::favorites::{provide_favorites, FavoriteToggle};
+use crate::flags::provide_feature_flags;
+use crate::locale::provide_locale;
+use crate::nav::NavBar;
+use crate::not_found::NotFound;
+use crate::pending::{provide_pending_async, title_with_badge};
+use crate::preferences::provide_preferences;
+use crate::recent::{provide_recently_viewed, RecordVisits};
+use crate::reload_prompt::ReloadPromptScript;
+use crate::scroll_restoration::use_scroll_restoration;
+use crate::settings::Settings;
+use crate::tasks::provide_tasks;
+use crate::theme::{provide_theme, ThemeScript, ThemeToggle};
+use crate::toast::{provide_toasts, Toaster};
+use cfg_if::cfg_if;
+use leptos::*;
+use leptos_meta::{provide_meta_context, Body, Link, Stylesheet, Title};
+use leptos_router::{AnimatedRoutes, Router};
+use std::time::Duration;
+
+// What only the examples use, see the `examples-*` features in Cargo.toml.
+#[cfg(any(feature = "examples-basic", feature = "examples-advanced"))]
+use crate::context::expect_context;
+#[cfg(any(feature = "examples-basic", feature = "examples-advanced"))]
+use crate::flags::use_feature_flags;
+#[cfg(any(feature = "examples-basic", feature = "examples-advanced"))]
+use crate::locale::{use_locale, LocalePicker};
+cfg_if! { if #[cfg(feature = "examples-basic")] {
+    use crate::favorites::use_favorites;
+    use crate::locale::format_date_localized;
+    use crate::permalink::use_permalink;
+    use crate::recent::use_recently_viewed;
+    use crate::repeat::use_hold_to_repeat;
+    use crate::scroll_progress::use_scroll_progress;
+    use crate::scroll_spy::use_scroll_spy;
+    use crate::trace::trace_signal;
+    use chrono::{Datelike, NaiveDate};
+    use leptos::html::Li;
+    use leptos_router::{use_query_map, A};
+}}
+cfg_if! { if #[cfg(feature = "examples-advanced")] {
+    use crate::announce::use_announcer;
+    use crate::characters::Character;
+    use crate::circuit_breaker::Breaker;
+    use crate::client_only::{use_client_only, ClientOnly};
+    use crate::context::use_context_or_default;
+    use crate::debounce::{use_debounced, use_throttled, use_timings, DEFAULT_DEBOUNCE, DEFAULT_THROTTLE};
+    use crate::diff::DiffView;
+    use crate::download::{download_bytes, zip_files};
+    use crate::empty_state::{EmptyAction, EmptyState};
+    use crate::event_source::{use_event_source, EventSourceStatus};
+    use crate::fetch::{get_json, skip_invalid, FetchError};
+    use crate::find::{find_matches, replace_all, replace_range, FindOptions};
+    use crate::flags::DEFAULT_FLAGS;
+    use crate::highlight::Highlight;
+    use crate::json_tree::JsonTree;
+    use crate::lazy::{use_lazy, Lazy};
+    use crate::locale::format_time_localized;
+    use crate::modal::Modal;
+    use crate::paginate::{use_paginated, InfiniteScroll, Page};
+    use crate::permalink::{use_permalink_state, QueryState};
+    use crate::reactive_graph::{use_tracker, GraphNode, NodeKind, ReactiveGraph, UpdateSteps};
+    use crate::repeater::Repeater;
+    use crate::rich_text::RichTextEditor;
+    use crate::state_machine::use_state_machine;
+    use crate::tabs::{Tab, TabMount, Tabs};
+    use crate::tasks::{use_tasks, TaskList};
+    use crate::toc::TableOfContents;
+    use crate::pending::use_pending_async;
+    use crate::preferences::use_auto_refresh;
+    use crate::toast::{use_toasts, MAX_VISIBLE};
+    use crate::versioned::{Saved, Versioned};
+    use crate::validate::{
+        email, matches, max_len, min_len, required, use_async_validation, use_validation, validate_all, AsyncRule,
+        FieldErrors, Rule, Validation,
+    };
+    use crate::websocket::{use_websocket, WebSocketStatus};
+    use crate::worker::run_in_worker;
+    use crate::window_size::use_window_size;
+    use chrono::Local;
+    use futures::future::{abortable, AbortHandle};
+    use leptos::error::Result;
+    use unicode_segmentation::UnicodeSegmentation;
+    use leptos::html::Div;
+    use leptos_router::ParamsMap;
+    use log::{info, Level};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+}}
 
 // This is part is used in the example for parent child communication.
 // We create a new type (something like a type alias in TypeScript). It is not completely necessary
@@ -15,70 +100,365 @@ use log::Level;
 // it but it will make the code more verbose.
 // Traits are like interfaces or protocols in other languages. `WriteSignal` is a type in Leptos that is
 // a setter for the signal.
+#[cfg(feature = "examples-basic")]
 #[derive(Copy, Clone)]
 struct ObjectContainContext(WriteSignal<bool>);
 
 #[component]
 pub fn App(cx: Scope) -> impl IntoView {
     provide_meta_context(cx);
+    // Runtime feature flags, see flags.rs and the /flags page.
+    let flags = provide_feature_flags(cx);
+    // Count of running fetches/actions, shown as a badge in the title e.g. "(1) Leptos Examples".
+    let pending = provide_pending_async(cx);
+    // Background tasks with their status, see tasks.rs. After the pending count, which counts them too.
+    provide_tasks(cx);
+    // Toast notifications, any component can show one with `use_toasts(cx).push(...)`.
+    provide_toasts(cx);
+    // Messages for screen readers, any component can send one with `use_announcer(cx).announce(...)`.
+    provide_announcer(cx);
+    // Light or dark, see theme.rs.
+    provide_theme(cx);
+    // How dates and times are written, see locale.rs.
+    provide_locale(cx);
+    // How long debounced values wait and how often throttled ones change, see debounce.rs and the /timings page.
+    provide_timings(cx);
+    // Auto-refresh and reduced motion, see preferences.rs. All of the above can be changed at /settings.
+    provide_preferences(cx);
+    // The example pages starred with the star at the top, see favorites.rs.
+    provide_favorites(cx);
+    // The example pages you looked at last, shown on the home page. See recent.rs.
+    provide_recently_viewed(cx);
+    // Back/forward brings you back to where you were on the page, see scroll_restoration.rs.
+    use_scroll_restoration(cx);
+    // The router flips this while it is navigating. Together with the pending count it drives the
+    // progress bar at the top of the page.
+    let (is_routing, set_is_routing) = create_signal(cx, false);
+    let loading = move || is_routing.get() || pending.count() != 0;
 
     view! { cx,
+        // The formatter reads `pending.count()`, so the title updates whenever the count changes.
+        <Title
+            text="Leptos Examples"
+            formatter=move |text| {
+                if flags.enabled("title_badge") { title_with_badge(pending.count(), text) } else { text }
+            }
+        />
         <Stylesheet id="leptos" href="/pkg/tailwind.css"/>
+        <ThemeScript/>
+        <ReloadPromptScript/>
+        // Printed pages are always light: paper is white, and browsers leave out background colors by default.
+        <Body class="dark:bg-gray-900 dark:text-gray-100 print:bg-white print:text-black"/>
         <Link rel="shortcut icon" type_="image/ico" href="/favicon.ico"/>
-        <Router>
-            <Routes>
-                <Route
-                    path=""
-                    view=move |cx| {
-                        view! { cx, <Home/> }
-                    }
-                />
-                <Route
-                    path="text_input"
-                    view=move |cx| {
-                        view! { cx, <TextInput/> }
-                    }
-                />
-                <Route
-                    path="control_flow"
-                    view=move |cx| {
-                        view! { cx, <ControlFlow/> }
-                    }
+        // Any error that isn't handled further down ends up here and replaces the whole app with a
+        // "Something went wrong" page, instead of leaving a blank or half-rendered page behind.
+        <ErrorBoundary fallback=|cx, errors| view! { cx, <ErrorPage errors=errors/> }>
+            <Show when=move || flags.enabled("progress_bar") fallback=|_| ()>
+                <TopProgressBar active=loading/>
+            </Show>
+            <Router set_is_routing=set_is_routing>
+                <RecordVisits track=is_example_page/>
+                <NavBar pages=example_pages()/>
+                // Buttons and status bits are no use on paper, `print:hidden` leaves them out of printouts.
+                <p class="fixed top-2 right-10 print:hidden">
+                    <FavoriteToggle track=is_example_page/>
+                </p>
+                // `AnimatedRoutes` is `Routes` with page transitions. On navigation it puts the `outro` class on
+                // the wrapping <div> and keeps showing the old page until that animation has ended, then swaps
+                // in the new page with the `intro` class. The animations are defined in tailwind.config.js.
+                // It waits for the `animationend` event, so the animations must really run: with "reduce motion"
+                // turned on in the OS they are cut to 1ms instead of being left out.
+                <AnimatedRoutes
+                    class="motion-reduce:[animation-duration:1ms]"
+                    outro="animate-route-out"
+                    intro="animate-route-in"
+                    outro_back="animate-route-out-back"
+                    intro_back="animate-route-in-back"
+                    children=Box::new(example_routes)
                 />
-                <Route
-                    path="parent_child_communication"
-                    view=move |cx| {
-                        view! { cx, <ParentChildCommunication/> }
-                    }
-                />
-                <Route
-                    path="pass_children"
-                    view=move |cx| {
-                        view! { cx, <PassChildren/> }
-                    }
-                />
-                <Route
-                    path="fetch"
-                    view=move |cx| {
-                        view! { cx, <Fetch/> }
-                    }
-                />
-            </Routes>
-        </Router>
+            </Router>
+            <Toaster/>
+            <LiveRegion/>
+            <p class="fixed top-2 right-2 print:hidden">
+                <ThemeToggle/>
+            </p>
+            <p class="fixed text-xs text-gray-500 bottom-2 left-2 print:hidden">
+                <OpenFor/>
+            </p>
+        </ErrorBoundary>
     }
 }
 
+/// The routes of the example pages that are compiled in, see the `examples-basic` and `examples-advanced`
+/// features in Cargo.toml. A page whose feature is turned off has no route, so the router never refers to a
+/// component that doesn't exist. Any other path gets the "404 — Not Found" page.
+fn example_routes(cx: Scope) -> Fragment {
+    use leptos_router::Route;
+    let mut routes = Vec::new();
+    #[cfg(feature = "examples-basic")]
+    routes.extend([
+        view! { cx,
+            <Route path="" view=|cx| view! { cx, <Home/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="text_input" view=|cx| view! { cx, <TextInput/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="control_flow" view=|cx| view! { cx, <ControlFlow/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="parent_child_communication" view=|cx| view! { cx, <ParentChildCommunication/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="pass_children" view=|cx| view! { cx, <PassChildren/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="all" view=|cx| view! { cx, <AllExamples/> }/>
+        }
+        .into_view(cx),
+    ]);
+    #[cfg(feature = "examples-advanced")]
+    routes.extend([
+        view! { cx,
+            <Route path="fetch" view=|cx| view! { cx, <Fetch/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="modal" view=|cx| view! { cx, <ModalExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="toasts" view=|cx| view! { cx, <ToastExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="flags" view=|cx| view! { cx, <FeatureFlagsExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="paginated" view=|cx| view! { cx, <PaginatedExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="chat" view=|cx| view! { cx, <ChatExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="sse" view=|cx| view! { cx, <ServerSentEventsExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="client_only" view=|cx| view! { cx, <ClientOnlyExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="tabs" view=|cx| view! { cx, <TabsExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="memo" view=|cx| view! { cx, <MemoExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="worker" view=|cx| view! { cx, <WorkerExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="tasks" view=|cx| view! { cx, <TasksExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="effects" view=|cx| view! { cx, <EffectsExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="cleanup" view=|cx| view! { cx, <CleanupExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="context" view=|cx| view! { cx, <ContextExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="table" view=|cx| view! { cx, <TableExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="form" view=|cx| view! { cx, <FormExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="textstats" view=|cx| view! { cx, <TextStatsExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="editor" view=|cx| view! { cx, <EditorExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="richtext" view=|cx| view! { cx, <RichTextExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="reactivity" view=|cx| view! { cx, <ReactiveGraphExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="batch" view=|cx| view! { cx, <BatchExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="lazy" view=|cx| view! { cx, <LazyExample/> }/>
+        }
+        .into_view(cx),
+        view! { cx,
+            <Route path="timings" view=|cx| view! { cx, <TimingsExample/> }/>
+        }
+        .into_view(cx),
+    ]);
+    // The settings aren't an example, they are there whatever the features.
+    routes.push(
+        view! { cx,
+            <Route path="settings" view=|cx| view! { cx, <Settings/> }/>
+        }
+        .into_view(cx),
+    );
+    // Matches every path, so it only gets the ones no route above matched. It must stay out of the server's
+    // routes though, see main.rs.
+    routes.push(
+        view! { cx,
+            <Route path="/*any" view=|cx| view! { cx, <NotFound/> }/>
+        }
+        .into_view(cx),
+    );
+    Fragment::new(routes)
+}
+
+/// The paths and titles of the example pages that are compiled in, like `example_routes`. The home page isn't
+/// in here, it's where you start from.
+#[allow(unused_mut)]
+fn example_pages() -> Vec<(&'static str, &'static str)> {
+    let mut pages = Vec::new();
+    #[cfg(feature = "examples-basic")]
+    pages.extend([
+        ("/text_input", "Text input"),
+        ("/control_flow", "Control flow"),
+        ("/parent_child_communication", "Parent child communication"),
+        ("/pass_children", "Pass children"),
+        ("/all", "All examples"),
+    ]);
+    #[cfg(feature = "examples-advanced")]
+    pages.extend([
+        ("/fetch", "Fetch"),
+        ("/modal", "Modal"),
+        ("/toasts", "Toasts"),
+        ("/flags", "Feature flags"),
+        ("/paginated", "Paginated"),
+        ("/chat", "Chat"),
+        ("/sse", "Server-sent events"),
+        ("/client_only", "Client only"),
+        ("/tabs", "Tabs"),
+        ("/memo", "Memo"),
+        ("/worker", "Web worker"),
+        ("/tasks", "Background tasks"),
+        ("/effects", "Effects"),
+        ("/cleanup", "Cleanup"),
+        ("/context", "Context"),
+        ("/table", "Data table"),
+        ("/form", "Form validation"),
+        ("/textstats", "Text statistics"),
+        ("/editor", "Find and replace"),
+        ("/richtext", "Rich text"),
+        ("/reactivity", "Reactive graph"),
+        ("/batch", "Batching"),
+        ("/lazy", "Lazy initialization"),
+        ("/timings", "Timings"),
+    ]);
+    pages
+}
+
+fn is_example_page(path: &str) -> bool {
+    example_pages().iter().any(|(page, _)| *page == path)
+}
+
+/// The title of the example page at `path`, see `example_pages`.
+#[cfg(feature = "examples-basic")]
+fn example_title(path: &str) -> Option<&'static str> {
+    example_pages()
+        .into_iter()
+        .find_map(|(page, title)| (page == path).then_some(title))
+}
+
 // Topics: signals, derived signals, props (default, signals)
 // The component attribute marks your code to be a component. Leptos does the heavy lifting of making your Rust code work as a component.
+#[cfg(feature = "examples-basic")]
 #[component]
 fn Home(cx: Scope) -> impl IntoView {
     // This is how you create a typical signal in Leptos. It looks like a typical React state hook if you know React.
     // You basically pattern match (or destructure) `value` which refers the value of the actual signal and `set_value` which
     // refers to the setter function.
-    let (value, set_value) = create_signal(cx, 0);
+    // `use_permalink` (see permalink.rs) wraps `create_signal` and also keeps the value in the URL (`/?value=25`),
+    // so a copied link restores the counter.
+    let (value, set_value) = use_permalink(cx, "value", 0);
+    // The counter also survives a refresh: every change is saved to localStorage, and a page opened without a value in
+    // the URL picks the saved one up. A link with a value still shows that value.
+    // The server has no localStorage and renders 0, so the saved value is applied after the first frame, once the page
+    // has hydrated with 0 just like the server rendered it. It is read right away though, before the effect below
+    // saves that 0 over it.
+    if !leptos_dom::is_server()
+        && use_query_map(cx).with_untracked(|query| query.get("value").is_none())
+    {
+        if let Some(saved) = saved_counter() {
+            request_animation_frame(move || _ = set_value.try_set(saved));
+        }
+    }
+    create_effect(cx, move |_| {
+        let value = value.get();
+        // Private browsing can leave us without localStorage, then the counter just isn't saved.
+        if let Some(storage) = window().local_storage().ok().flatten() {
+            _ = storage.set_item(COUNTER_STORAGE_KEY, &value.to_string());
+        }
+    });
     // You can create a derived signal which is a variable that depends on a signal. When the signal variable updates, this
     // also updates. But as we will see later, they do not have the same type so you will need to some type assignments to make it work.
     let pixel_value = move || value.get() * 5;
+    // Holding down `+` or `-` keeps stepping, faster the longer you hold (see repeat.rs).
+    let repeat = use_hold_to_repeat(cx);
+    // How far one click of `+` or `-` moves the counter, picked with the buttons below it.
+    let (step, set_step) = create_signal(cx, 5);
+    // Everything about the counter that depends on both `value` and `step`, derived in one go. `combine!` (see
+    // combine.rs) writes the closure that reads both signals, inside it `value` and `step` are plain numbers.
+    let status = crate::combine!(cx, |value, step| CounterStatus {
+        can_decrement: value - step >= COUNTER_MIN,
+        can_increment: value + step <= COUNTER_MAX,
+        percent: value * 100 / COUNTER_MAX,
+    });
+    // Every change of the counter and its status is logged to the browser console (see trace.rs), watch it while you click.
+    trace_signal(cx, "value", value);
+    trace_signal(cx, "status", status);
+    // The counter stays between `COUNTER_MIN` and `COUNTER_MAX`, a step that doesn't fit anymore does nothing.
+    let decrement = move || {
+        if status.get_untracked().can_decrement {
+            set_value.update(|value| *value -= step.get_untracked());
+        }
+    };
+    let increment = move || {
+        if status.get_untracked().can_increment {
+            set_value.update(|value| *value += step.get_untracked());
+        }
+    };
+    // The ceiling of the second progress bar, picked with the buttons below it.
+    let (ceiling, set_ceiling) = create_signal(cx, 100);
+    // A derived signal for "Reset": nothing to reset when the counter is at 0 already.
+    let at_zero = move || value.get() == 0;
+    let flags = use_feature_flags(cx);
+    let favorites = use_favorites(cx);
+    let recent = use_recently_viewed(cx);
 
     view! { cx,
         <Title text="Leptos Examples"/>
@@ -91,7 +471,7 @@ fn Home(cx: Scope) -> impl IntoView {
                     // Try passing in a different value for `max` to see the change. Clicking on the `+` or `-` buttons changes the state value
                     // for `value`, which in turn changes the value of the progress bar.
                     <div class="mx-auto">
-                        <ProgressBar max=200 progress=value/>
+                        <ProgressBar max={COUNTER_MAX as u16} progress=value/>
                     </div>
                     <div class="mx-auto">
                         <button
@@ -100,7 +480,23 @@ fn Home(cx: Scope) -> impl IntoView {
                             // runs and updates `value` using the `set_value()` setter.
 
                             // The * in *value dereferences value, which means we access the actual value and not the pointer to the value.
-                            on:click=move |_| set_value.update(|value| *value -= 5)
+                            // (`decrement` and `increment` are defined above, they are the same kind of closure.)
+
+                            // With the mouse, stepping starts on `mousedown` and repeats until the button is released or the
+                            // pointer leaves it. `click` still fires after that, so we only use it for the keyboard (Enter/Space),
+                            // which is recognizable by `detail()` being 0 (it counts mouse clicks).
+                            // Dimmed at the end of the range. Not `disabled`: a disabled button gets no `mouseup`, so holding it
+                            // down until the end would never stop repeating.
+                            class:opacity-50=move || !status.get().can_decrement
+                            aria-disabled=move || (!status.get().can_decrement).to_string()
+                            on:mousedown=move |_| repeat.start(decrement)
+                            on:mouseup=move |_| repeat.stop()
+                            on:mouseleave=move |_| repeat.stop()
+                            on:click=move |event| {
+                                if event.detail() == 0 {
+                                    decrement()
+                                }
+                            }
                             class="px-3 py-2 m-1 text-white bg-blue-700 border-b-4 border-l-2 border-blue-800 rounded shadow-lg"
                         >
                             "-"
@@ -109,73 +505,246 @@ fn Home(cx: Scope) -> impl IntoView {
                             {value}
                         </button>
                         <button
-                            on:click=move |_| set_value.update(|value| *value += 5)
+                            class:opacity-50=move || !status.get().can_increment
+                            aria-disabled=move || (!status.get().can_increment).to_string()
+                            on:mousedown=move |_| repeat.start(increment)
+                            on:mouseup=move |_| repeat.stop()
+                            on:mouseleave=move |_| repeat.stop()
+                            on:click=move |event| {
+                                if event.detail() == 0 {
+                                    increment()
+                                }
+                            }
                             class="px-3 py-2 m-1 text-white bg-blue-700 border-b-4 border-l-2 border-blue-800 rounded shadow-lg"
                         >
                             "+"
                         </button>
+                        // A plain `disabled` this time, no holding down here. The attribute is added and removed as
+                        // `at_zero` changes, and `disabled:` classes gray the button out while it is there.
+                        <button
+                            disabled=at_zero
+                            on:click=move |_| set_value.set(0)
+                            class="px-3 py-2 m-1 text-white bg-blue-700 border-b-4 border-l-2 border-blue-800 rounded shadow-lg disabled:bg-gray-400 disabled:border-gray-500 disabled:cursor-not-allowed"
+                        >
+                            "Reset"
+                        </button>
+                    </div>
+                    <div class="mx-auto text-sm">
+                        "Step: "
+                        {[1, 5, 10]
+                            .into_iter()
+                            .map(|size| view! { cx,
+                                <button
+                                    class="px-2 m-1 border rounded"
+                                    class:bg-blue-800=move || step.get() == size
+                                    on:click=move |_| set_step(size)
+                                >
+                                    {size}
+                                </button>
+                            })
+                            .collect_view(cx)}
+                        {move || format!(" ({}%)", status.get().percent)}
+                    </div>
+                    // The same `ProgressBar` with a signal as `max`: the bar changes when you pick another ceiling, without
+                    // the counter changing. A counter above the ceiling fills the bar.
+                    <div class="mx-auto">
+                        <ProgressBar max=ceiling progress=value/>
+                    </div>
+                    <div class="mx-auto text-sm">
+                        "Ceiling: "
+                        {[50, 100, 200]
+                            .into_iter()
+                            .map(|size| view! { cx,
+                                <button
+                                    class="px-2 m-1 border rounded"
+                                    class:bg-blue-800=move || ceiling.get() == size
+                                    on:click=move |_| set_ceiling(size)
+                                >
+                                    {size}
+                                </button>
+                            })
+                            .collect_view(cx)}
                     </div>
                     <div class="mx-auto">
+                        // `width` is a `Signal<i32>` with `#[prop(into)]`, so a `ReadSignal` goes in as it is...
                         <ItsMeMario width= value />
-                        // <ItsMeMario width= pixel_value />
-                        // <ItsMeMario width= Signal::derive(cx, pixel_value) />
+                        // ...and the derived signal goes in through `Signal::derive`. A bare closure wouldn't do.
+                        <ItsMeMario width= Signal::derive(cx, pixel_value) />
+                        // Only rendered when the `mario_party` feature flag is on (turn it on at /flags).
+                        <Show when=move || flags.enabled("mario_party") fallback=|_| ()>
+                            <ItsMeMario width= value />
+                            <ItsMeMario width= value />
+                        </Show>
                     </div>
+                    <PageLinks title="Favorites" paths=Signal::derive(cx, move || favorites.paths())/>
+                    <PageLinks title="Recently viewed" paths=Signal::derive(cx, move || recent.paths())/>
                 </div>
             </div>
         </main>
     }
 }
 
+/// Links to the example pages at `paths`, under `title`. Nothing when there are none. Paths of pages that aren't
+/// compiled in are skipped.
+#[cfg(feature = "examples-basic")]
+#[component]
+fn PageLinks(
+    cx: Scope,
+    title: &'static str,
+    #[prop(into)] paths: Signal<Vec<String>>,
+) -> impl IntoView {
+    // `A` is the router's link: it navigates without reloading the page, the way `RecordVisits` (in `App`) sees it.
+    let links = move || {
+        paths
+            .get()
+            .into_iter()
+            .filter_map(|path| {
+                let title = example_title(&path)?;
+                Some(view! { cx, <li><A href=path class="underline">{title}</A></li> })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! { cx,
+        {move || {
+            let links = links();
+            (!links.is_empty())
+                .then(|| view! { cx,
+                    <nav class="mx-auto mt-8 text-sm">
+                        <h2 class="font-bold">{title}</h2>
+                        <ul>{links}</ul>
+                    </nav>
+                })
+        }}
+    }
+}
+
+// The range of the counter on the home page.
+#[cfg(feature = "examples-basic")]
+const COUNTER_MIN: i32 = 0;
+#[cfg(feature = "examples-basic")]
+const COUNTER_MAX: i32 = 200;
+
+// Where the counter on the home page is saved, see `Home`.
+#[cfg(feature = "examples-basic")]
+const COUNTER_STORAGE_KEY: &str = "counter";
+
+/// The saved counter, if there is one and it is in range. Missing localStorage, a missing entry or one
+/// that isn't a number all give `None`.
+#[cfg(feature = "examples-basic")]
+fn saved_counter() -> Option<i32> {
+    window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(COUNTER_STORAGE_KEY).ok().flatten())
+        .and_then(|saved| saved.parse().ok())
+        .filter(|saved| (COUNTER_MIN..=COUNTER_MAX).contains(saved))
+}
+
+// What the counter's buttons need to know, see `status` in `Home`.
+#[cfg(feature = "examples-basic")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct CounterStatus {
+    can_decrement: bool,
+    can_increment: bool,
+    percent: i32,
+}
+
+#[cfg(feature = "examples-basic")]
 #[component]
 // cx is has a type of Scope, which is used to manage memory within the Reactive system. You can think of it like "context"
 fn ProgressBar(
     cx: Scope,
     // Adding this prop default attribute, allows you to set a default value for a prop. There is also an `option` variant to
     // to make prop optional.
-    #[prop(default = 100)] max: u16,
+    // `MaybeSignal` is either a plain value or a signal, and `into` converts whichever you pass: `max=200` stays fixed,
+    // `max=ceiling` follows the signal `ceiling`. With `into` the default has to be a `MaybeSignal` already.
+    #[prop(into, default = MaybeSignal::Static(100))] max: MaybeSignal<u16>,
     // `ReadSignal` is the type of the signal that is crated to hold the state of this component. Because it is a signal it is reactive.
     progress: ReadSignal<i32>,
     // IntoView is a trait, but it basically converts the value into a `View`.
 ) -> impl IntoView {
-    // The `view!` macro lets you write JSX like syntax.
-    view! { cx, <progress max=max value=progress></progress> }
+    // The `view!` macro lets you write JSX like syntax. Reading `max` in a closure makes the attribute reactive, for a
+    // static `max` the closure just always returns the same number.
+    view! { cx, <progress max=move || max.get() value=progress></progress> }
 }
 
-// Naive Mario
+// Same idea as `ProgressBar`, but we don't know how much is left, so it's an indeterminate bar
+// that crawls towards 80% while `active` is true and then quickly fills up and fades out.
+// It is generic over `F` like the generic Mario described below, which lets us pass a plain closure as a prop.
 #[component]
-fn ItsMeMario(cx: Scope, width: ReadSignal<i32>) -> impl IntoView {
+fn TopProgressBar<F>(cx: Scope, active: F) -> impl IntoView
+where
+    F: Fn() -> bool + 'static,
+{
+    let (width, set_width) = create_signal(cx, 0);
+    let (visible, set_visible) = create_signal(cx, false);
+    // Bumped on every start/finish so a fade-out timer that fires after the bar started again does nothing.
+    let generation = store_value(cx, 0);
+
+    // Effects receive their previous return value, which we use to detect when `active` changes.
+    create_effect(cx, move |was_active: Option<bool>| {
+        let active = active();
+        if active != was_active.unwrap_or(false) {
+            generation.update_value(|generation| *generation += 1);
+            if active {
+                set_visible(true);
+                set_width(10);
+                // Let the browser paint 10% first, otherwise there is nothing to transition from.
+                request_animation_frame(move || set_width(80));
+            } else {
+                set_width(100);
+                let finished = generation.get_value();
+                set_timeout(
+                    move || {
+                        if generation.get_value() == finished {
+                            set_visible(false);
+                        }
+                    },
+                    Duration::from_millis(300),
+                );
+            }
+        }
+        active
+    });
+
     view! { cx,
-        <img src="https://upload.wikimedia.org/wikipedia/en/thumb/a/a9/MarioNSMBUDeluxe.png/220px-MarioNSMBUDeluxe.png" width=width />
+        <div
+            class="fixed top-0 left-0 z-50 h-1 bg-blue-500 print:hidden"
+            style:width=move || format!("{}%", width.get())
+            style:opacity=move || if visible.get() { "1" } else { "0" }
+            // Jump straight back to the start (no shrinking animation), slow crawl while loading,
+            // fast finish once done.
+            style:transition=move || match width.get() {
+                10 => "opacity 300ms",
+                100 => "width 200ms ease-out, opacity 300ms",
+                _ => "width 3s ease-out, opacity 300ms",
+            }
+        ></div>
     }
 }
 
-// Generic Mario: First method for dealing with derived signals
-// There are several differences here. First we add the generic type `<F>` and assign that type to `width`, so `width` is now generic, which means
-// it can take other types. What type to take in is defined in the `where` clause right below the first line. This basically says, we have a type
-// that is a closure (Fn) and it returns an integer of type i32.
-// #[component]
-// fn ItsMeMario<F>(cx: Scope, width: F) -> impl IntoView
-// where
-//     F: Fn() -> i32 + 'static,
-// {
-//     view! { cx,
-//         <img src="https://upload.wikimedia.org/wikipedia/en/thumb/a/a9/MarioNSMBUDeluxe.png/220px-MarioNSMBUDeluxe.png" width=width />
-//     }
-// }
-
-// Into Mario using #[prop(into)]: Second method for dealing with derived signals
-// #[component]
-// fn ItsMeMario(cx: Scope, #[prop(into)] width: Signal<i32>) -> impl IntoView {
-//     view! { cx,
-//         <img src="https://upload.wikimedia.org/wikipedia/en/thumb/a/a9/MarioNSMBUDeluxe.png/220px-MarioNSMBUDeluxe.png" width=width />
-//     }
-// }
+// Mario's `width` could have been a plain `width: ReadSignal<i32>`. That takes `value` but not `pixel_value`, which is
+// a derived signal, a closure: "expected `ReadSignal<i32>`, found closure". One way around that is a generic
+// component, `fn ItsMeMario<F>(cx: Scope, width: F)` with `where F: Fn() -> i32 + 'static`, which takes any closure
+// that returns an i32. The other is the one we use, `#[prop(into)]` with a `Signal`.
+// `Signal` is the general kind of signal: it can be made from a `ReadSignal`, an `RwSignal`, a `Memo` or, with
+// `Signal::derive`, a closure. `#[prop(into)]` does the converting for the ones that convert by themselves.
+#[cfg(feature = "examples-basic")]
+#[component]
+fn ItsMeMario(cx: Scope, #[prop(into)] width: Signal<i32>) -> impl IntoView {
+    view! { cx,
+        <img src="https://upload.wikimedia.org/wikipedia/en/thumb/a/a9/MarioNSMBUDeluxe.png/220px-MarioNSMBUDeluxe.png" width=width />
+    }
+}
 
 /// Text input example (binding, conditional classes)
+#[cfg(feature = "examples-basic")]
 #[component]
 fn TextInput(cx: Scope) -> impl IntoView {
     // Create a basic signal like before, but instead of an integer use text.
-    let (text, set_text) = create_signal(cx, "Enter some text".to_string());
+    let (text, set_text) = use_permalink(cx, "text", "Enter some text".to_string());
 
     view! { cx,
         <main class="max-w-2xl mx-auto mt-12">
@@ -203,15 +772,23 @@ fn TextInput(cx: Scope) -> impl IntoView {
 }
 
 /// Control flow example
+#[cfg(feature = "examples-basic")]
+#[component]
 fn ControlFlow(cx: Scope) -> impl IntoView {
     // Implement control with using native Rust code
     // * Because the chrono crate supports different time formats you need to be particular
     // * in which format to pass to the browser, because browsers only accept dates in "%Y-%m-%d" format.
-    let (date, set_date) = create_signal(cx, NaiveDate::from_ymd_opt(2023, 07, 22).unwrap());
+    // * `NaiveDate` formats and parses as "%Y-%m-%d", so it can be stored in the URL as is.
+    // * What we show the user is written the way their locale writes dates instead, see locale.rs.
+    let (date, set_date) = use_permalink(cx, "date", NaiveDate::from_ymd_opt(2023, 7, 22).unwrap());
+    let locale = use_locale(cx);
 
     view! { cx,
         <div class="max-w-2xl mx-auto mt-10">
             <h1 class="text-3xl">"Control Flow"</h1>
+            <p class="mt-2">
+                <LocalePicker/>
+            </p>
             <input
                 type="date"
                 class="mt-6"
@@ -228,12 +805,13 @@ fn ControlFlow(cx: Scope) -> impl IntoView {
                 {move || {
                     // Basically if the date value's weekday() method returns somethings that is either "Sat" or "Sun" return
                     // weekend else return weekday.
+                    let shown = format_date_localized(date.get(), locale.get());
                     if date.get().weekday().to_string().contains("Sat")
                         || date.get().weekday().to_string().contains("Sun")
                     {
-                        date.get().format("%Y-%m-%d").to_string() + " is a weekend"
+                        shown + " is a weekend"
                     } else {
-                        date.get().format("%Y-%m-%d").to_string() + " is a weekday"
+                        shown + " is a weekday"
                     }
                 }}
             </p>
@@ -242,6 +820,7 @@ fn ControlFlow(cx: Scope) -> impl IntoView {
 }
 
 /// ParentChildCommunication
+#[cfg(feature = "examples-basic")]
 #[component]
 fn ParentChildCommunication(cx: Scope) -> impl IntoView {
     let (object_cover, set_object_cover) = create_signal(cx, false);
@@ -275,6 +854,7 @@ fn ParentChildCommunication(cx: Scope) -> impl IntoView {
     }
 }
 
+#[cfg(feature = "examples-basic")]
 #[component]
 // Pass signal to the child
 pub fn ObjectCover(
@@ -287,18 +867,25 @@ pub fn ObjectCover(
     }
 }
 
+#[cfg(feature = "examples-basic")]
 #[component]
 // Set an event listener on a component. Basically the component becomes a simple placeholder.
 pub fn ObjectScaleDown(cx: Scope) -> impl IntoView {
     view! { cx, <button>"Object Scale Down"</button> }
 }
 
+#[cfg(feature = "examples-basic")]
 #[component]
 // Use `provide context` and `use_context` to facilitate parent-child communication
 pub fn ObjectContain(cx: Scope) -> impl IntoView {
     // The ObjectContainContext type is an alias we defined at the very top of this file.
     // `use_context` basically allows you to reach into context and pull out a value.
-    let setter: WriteSignal<bool> = use_context::<ObjectContainContext>(cx).unwrap().0;
+    // `expect_context` (see context.rs) is `use_context` plus a clear panic message, naming what is missing and
+    // which component should have provided it, for when you forget the `provide_context`.
+    let setter: WriteSignal<bool> =
+        expect_context::<ObjectContainContext>(cx, "ParentChildCommunication").0;
+    // The same works for the whole app: `App` provides a `ThemeContext` (see theme.rs), and the dark mode toggle in the
+    // nav reaches for it with `use_theme`, however deep down it is.
 
     view! { cx,
         <button on:click=move |_| setter.update(|value| *value = !*value)>"Object Contain"</button>
@@ -306,6 +893,7 @@ pub fn ObjectContain(cx: Scope) -> impl IntoView {
 }
 
 /// Pass children in a component
+#[cfg(feature = "examples-basic")]
 #[component]
 pub fn PassChildren(cx: Scope) -> impl IntoView {
     view! { cx,
@@ -316,6 +904,7 @@ pub fn PassChildren(cx: Scope) -> impl IntoView {
     }
 }
 
+#[cfg(feature = "examples-basic")]
 #[component]
 pub fn AcceptsChildren(cx: Scope, children: ChildrenFn) -> impl IntoView {
     // childrenFn is short of Box<dyn Fn(Scope) -> Fragment>
@@ -357,42 +946,201 @@ pub fn AcceptsChildren(cx: Scope, children: ChildrenFn) -> impl IntoView {
 
 /// Fetch Example (struct mapping, logging)
 // https://www.amiiboapi.com/api/amiibo/?name=mario
+// The API uses camelCase names (`amiiboSeries`), Rust uses snake_case (`amiibo_series`). `rename_all` maps one to the
+// other, so our fields can follow Rust's naming. The `alias`es also accept snake_case names in the JSON, in case the
+// data comes from a different source (or the API changes its mind).
+#[cfg(feature = "examples-advanced")]
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct Amiibo {
-    amiiboSeries: String,
+    #[serde(alias = "amiibo_series")]
+    amiibo_series: String,
     character: String,
-    gameSeries: String,
+    #[serde(alias = "game_series")]
+    game_series: String,
+    // `head` and `tail` together are the amiibo's id, no two amiibo have the same pair.
     head: String,
+    tail: String,
     image: String,
     name: String,
 }
 
+// What pairs up the amiibo of two fetches in the diff, see diff.rs.
+#[cfg(feature = "examples-advanced")]
+fn amiibo_id(amiibo: &Amiibo) -> (String, String) {
+    (amiibo.head.clone(), amiibo.tail.clone())
+}
+
+#[cfg(feature = "examples-advanced")]
+fn describe_amiibo(amiibo: &Amiibo) -> String {
+    format!(
+        "{} ({}, {})",
+        amiibo.name, amiibo.amiibo_series, amiibo.game_series
+    )
+}
+
+#[cfg(feature = "examples-advanced")]
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Data {
     // A Vec is basically like a dynamic array, list in Rust. There are arrays in Rust but they are static (length cannot change)
+    // One malformed amiibo shouldn't hide all the others, so entries that don't parse are skipped (see fetch.rs).
+    #[serde(deserialize_with = "skip_invalid")]
     amiibo: Vec<Amiibo>,
 }
 
 // An async fetch function we call later.
 // The Result type is very common Rust enum which either results in an OK (the thing were trying worked so give me back my data) or an Error
 // in that case send me an error.
-async fn fetch_character() -> Result<Vec<Amiibo>> {
-    // reqwasm is a HTTP request library for WASM apps. Provides Rust binding for the web_sys
-    // fetch and WebSocket API.
-    // Async await looks similar to how it is in JavaScript.
-    let res =
-        reqwasm::http::Request::get(&format!("https://www.amiiboapi.com/api/amiibo/?name=mario",))
-            .send()
-            .await?
-            // convert it to JSON
-            .json::<Data>()
-            .await?;
+// It runs in both places: on the server while it renders the page, so the list is already in the HTML, and in the
+// browser for every search after that.
+#[cfg(feature = "examples-advanced")]
+async fn fetch_character(cx: Scope, name: String) -> Result<Vec<Amiibo>, FetchError> {
+    // The outer `?` is our own server failing to answer (`ServerFnError`), the inner one the Amiibo API.
+    // The Amiibo API answers a name without any amiibo with a 404. For a search that just means no results.
+    let res: Data = match get_amiibo(cx, name).await? {
+        Err(FetchError::Status(404)) => return Ok(Vec::new()),
+        res => res?,
+    };
 
     // From our response, give me back the amiibo "array". I just want the "array" because I want to loop through the elements later.
     let response = res.amiibo;
     Ok(response)
 }
 
+// A server function: the body only exists in the server binary. In the browser `get_amiibo` has the same signature, but
+// calling it POSTs the name to `/api/get_amiibo...` and the server runs the body and sends back the `Data` it returns
+// (that is why `Data` and `Amiibo` are `Serialize` and `Deserialize`). While the server renders the page it simply calls
+// the body. Either way the request to the Amiibo API is made by the server, with reqwest: the browser never talks to
+// the Amiibo API, so its CORS headers don't matter and its URL stays on the server.
+// It asks the same way `/api/amiibo` does (see `ask_amiibo_api` in api.rs, it shares the cache), and `read_json` checks
+// the answer exactly like `get_json` checks a response in the browser. The result is a `Result` of its own: what went
+// wrong with the Amiibo API (a 404, a 429 with its `Retry-After`, a field that didn't parse) reaches the browser as
+// the same `FetchError`, a `ServerFnError` would only keep the message. The `AppState` is in context for the pages and
+// for server functions, see main.rs.
+#[cfg(feature = "examples-advanced")]
+#[server(GetAmiibo, "/api")]
+pub async fn get_amiibo(
+    cx: Scope,
+    name: String,
+) -> Result<Result<Data, FetchError>, ServerFnError> {
+    use crate::api::{ask_amiibo_api, AppState};
+    use crate::fetch::read_json;
+
+    let state = use_context::<AppState>(cx).ok_or_else(|| {
+        ServerFnError::ServerError("The server has no AppState in context".to_string())
+    })?;
+    let answer = ask_amiibo_api(&state, Some(name)).await;
+    Ok(read_json(
+        answer.status.as_u16(),
+        answer
+            .retry_after
+            .as_ref()
+            .and_then(|value| value.to_str().ok()),
+        answer.content_type.to_str().ok(),
+        &String::from_utf8_lossy(&answer.body),
+    ))
+}
+
+/// What the fetch example shows instead of the list when fetching it failed: what went wrong and a button to try again.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn FetchFailed<F>(
+    cx: Scope,
+    /// The errors the `ErrorBoundary` caught.
+    errors: RwSignal<Errors>,
+    /// Whether a fetch is running. There is nothing to retry until it is done.
+    #[prop(into)]
+    busy: MaybeSignal<bool>,
+    /// Fetches the list again.
+    retry: F,
+) -> impl IntoView
+where
+    F: Fn() + 'static,
+{
+    view! { cx,
+        <li class="col-span-3 list-none text-red-700" role="alert">
+            {move || errors
+                .get()
+                .into_iter()
+                .map(|(_, error)| view! { cx, <p>{error.to_string()}</p> })
+                .collect_view(cx)}
+            // Retries happen on their own, but not for every error and not while the circuit is open. This
+            // one is up to you.
+            <button
+                class="px-3 py-1 mt-2 text-white bg-blue-700 rounded shadow disabled:bg-gray-400"
+                disabled=move || busy.get()
+                on:click=move |_| retry()
+            >
+                "Retry"
+            </button>
+        </li>
+    }
+}
+
+/// One result of the fetch example: the amiibo's picture and its game series.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn AmiiboCard(
+    cx: Scope,
+    /// The amiibo to show.
+    amiibo: Amiibo,
+) -> impl IntoView {
+    // A search can find a few hundred amiibo, each with a picture of its own. `loading="lazy"` leaves the pictures
+    // further down the page to the browser, which only fetches them once you scroll close to them.
+    // Until a picture is there its box pulses gray (a "skeleton"), so the card has its final size from the start and
+    // nothing jumps around when the picture arrives. A picture that fails to load stops the pulsing too, the `alt` text
+    // shows instead.
+    let (loaded, set_loaded) = create_signal(cx, false);
+    let image = create_node_ref::<html::Img>(cx);
+    // A picture can finish loading before the WASM does, e.g. one the server-rendered page already showed. Its `load`
+    // event was over before anybody listened, so we ask the element.
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || {
+            if image.get_untracked().is_some_and(|image| image.complete()) {
+                _ = set_loaded.try_set(true);
+            }
+        });
+    }
+
+    view! { cx,
+        <li class="flex flex-col items-center gap-2 p-2 mt-2 list-none border rounded shadow">
+            <img
+                node_ref=image
+                class="object-contain w-32 h-32 rounded"
+                class=("bg-gray-200", move || !loaded.get())
+                class=("dark:bg-gray-700", move || !loaded.get())
+                class=("animate-pulse", move || !loaded.get())
+                src=amiibo.image
+                alt=amiibo.name
+                loading="lazy"
+                on:load=move |_| set_loaded(true)
+                on:error=move |_| set_loaded(true)
+            />
+            <span class="text-center">{amiibo.game_series}</span>
+        </li>
+    }
+}
+
+// Where the fetch is at. `Fetch` drives a state machine (see state_machine.rs) with these, so the page is always in
+// exactly one of them.
+#[cfg(feature = "examples-advanced")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum FetchState {
+    Idle,
+    Loading,
+    Loaded,
+    Failed,
+}
+
+#[cfg(feature = "examples-advanced")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum FetchEvent {
+    Start,
+    Succeed,
+    Fail,
+}
+
+#[cfg(feature = "examples-advanced")]
 #[component]
 pub fn Fetch(cx: Scope) -> impl IntoView {
     // Bonus: This is how we log to the browser's console. We use the `console_log` crate to add this capability.
@@ -400,40 +1148,3247 @@ pub fn Fetch(cx: Scope) -> impl IntoView {
     // Show up in the browser console as an info log.
     info!("This log will appear in the browser console");
 
+    let pending = use_pending_async(cx);
+    // Failed fetches are retried, unless they keep failing. Then the circuit breaker (see circuit_breaker.rs) stops
+    // us from retrying for a while.
+    let (breaker, set_breaker) = create_signal(cx, Breaker::default());
+    // The name to search for. `name` follows the input on every keystroke, so the input stays snappy. `search` is what
+    // the request is made with, and it only follows `name` once you stop typing for a moment: each keystroke cancels the
+    // timer of the one before (see debounce.rs), so typing "luigi" is one request and not five. How long that moment is
+    // isn't set here (`None`), it's the app-wide debounce delay, 300ms unless changed at /timings.
+    let (name, set_name) = create_signal(cx, "mario".to_string());
+    let search = use_debounced(cx, move || name.get().trim().to_string(), None);
     let character_series =
-        // Just run once using a non-reactive empty source signal. You can create something that runs on every trigger by 
-        // passing in a signal instead of something empty.
-        // A resource is Leptos's way of handling data returned by an asynchronous task. 
-        create_local_resource(cx, || (), |_| async move { fetch_character().await });
+        // A resource is Leptos's way of handling data returned by an asynchronous task. The first argument is its source:
+        // every time `search` changes the resource runs again with the new name.
+        // Not a local resource: the server runs the first fetch while rendering the page, and the list is in the HTML it
+        // sends. The result goes along to the browser (serialized, which is why `Amiibo` and `FetchError` are
+        // `Serialize`), so hydrating doesn't fetch the same list again.
+        create_resource(cx, move || search.get(), move |name| async move {
+            // Nothing to search for, so no request either.
+            if name.is_empty() {
+                return Ok(Vec::new());
+            }
+            // The guard counts this fetch as pending until it is dropped at the end of the block, even if the fetch fails.
+            let _pending = pending.start();
+            let result = fetch_character(cx, name).await;
+            // The breaker is about the browser's requests, and only the browser has the clock it keeps time with.
+            if !leptos_dom::is_server() {
+                let now = js_sys::Date::now();
+                set_breaker.update(|breaker| {
+                    *breaker = match &result {
+                        Ok(_) => breaker.on_success(),
+                        Err(error) if error.is_retryable() => breaker.on_failure(now),
+                        // The service is up, it just didn't like our request. Nothing for the breaker to do.
+                        Err(_) => *breaker,
+                    }
+                });
+            }
+            result
+        });
+
+    // The lifecycle of the fetch, with the moves it is allowed to make. A finished fetch (either way) can only start
+    // again. A hydrated page is the one exception to "start before finish": the server already fetched the list and sent
+    // it along, so the first thing the browser sees is the result, straight from `Idle`.
+    let lifecycle = use_state_machine(
+        cx,
+        FetchState::Idle,
+        [
+            (FetchState::Idle, FetchEvent::Start, FetchState::Loading),
+            (FetchState::Idle, FetchEvent::Succeed, FetchState::Loaded),
+            (FetchState::Idle, FetchEvent::Fail, FetchState::Failed),
+            (FetchState::Loading, FetchEvent::Succeed, FetchState::Loaded),
+            (FetchState::Loading, FetchEvent::Fail, FetchState::Failed),
+            (FetchState::Loaded, FetchEvent::Start, FetchState::Loading),
+            (FetchState::Failed, FetchEvent::Start, FetchState::Loading),
+        ],
+    );
+    // The resource tells us whether it is loading and what it got. The memo turns that into the event it means and only
+    // changes when the event does, so each one is sent once. (While a fetch is running its result can still be the
+    // previous one, `loading` comes first.)
+    let fetch_event = create_memo(cx, move |_| {
+        if character_series.loading().get() {
+            Some(FetchEvent::Start)
+        } else {
+            character_series.with(cx, |result| match result {
+                Ok(_) => FetchEvent::Succeed,
+                Err(_) => FetchEvent::Fail,
+            })
+        }
+    });
+    create_effect(cx, move |_| {
+        if let Some(event) = fetch_event.get() {
+            lifecycle.send(event);
+        }
+    });
+
+    // The current time, updated every second. It drives the "retrying in Xs" countdown and closes the
+    // cool-down once it is over.
+    let (now, set_now) = create_signal(cx, 0.0);
+    let retry = store_value(cx, None::<TimeoutHandle>);
+    if !leptos_dom::is_server() {
+        let clock = set_interval_with_handle(
+            move || {
+                let time = js_sys::Date::now();
+                set_now(time);
+                let before = breaker.get_untracked();
+                let after = before.on_tick(time);
+                if after != before {
+                    // Cool-down over, time for a trial request.
+                    set_breaker(after);
+                    character_series.refetch();
+                }
+            },
+            Duration::from_secs(1),
+        );
+        on_cleanup(cx, move || {
+            if let Ok(clock) = clock {
+                clock.clear();
+            }
+            if let Some(retry) = retry.get_value() {
+                retry.clear();
+            }
+        });
+    }
+    // Runs every time a fetch finishes: a failure is retried after a short pause while the circuit allows it. Only
+    // failures that might go away are retried though (see `FetchError::is_retryable`), a 404 stays a 404. When we are
+    // rate limited the server usually says how long to wait (the `Retry-After` header), then we wait exactly that long.
+    create_effect(cx, move |_| {
+        let retry_in = character_series
+            .with(cx, |result| match result {
+                Err(error) if error.is_retryable() => {
+                    Some(error.retry_after().unwrap_or(Duration::from_secs(2)))
+                }
+                _ => None,
+            })
+            .flatten();
+        if let Some(delay) = retry_in {
+            if breaker.get_untracked().allows_request() {
+                let handle = set_timeout_with_handle(move || character_series.refetch(), delay);
+                retry.set_value(handle.ok());
+            }
+        }
+    });
+    let breaker_message = move || match breaker.get() {
+        Breaker::Closed { failures: 0 } => None,
+        Breaker::Closed { failures } => Some(format!(
+            "Request failed, retrying (attempt {})",
+            failures + 1
+        )),
+        Breaker::HalfOpen => Some("Trying again...".to_string()),
+        open => open
+            .seconds_left(now.get())
+            .map(|seconds| format!("Service unavailable, retrying in {seconds}s")),
+    };
+
+    // Tell screen reader users when the results arrive, they can't see the list appear.
+    let announcer = use_announcer(cx);
+    create_effect(cx, move |_| {
+        character_series.with(cx, |data| match data {
+            Ok(data) => announcer.announce(format!("{} results loaded", data.len())),
+            Err(_) => announcer.announce_assertive("Loading the results failed"),
+        });
+    });
+
+    // With auto-refresh turned on at /settings the results are fetched again every so often, as if you clicked "Fetch
+    // again". Not while the circuit breaker holds requests back, it has its own timer for the next try.
+    use_auto_refresh(cx, move || {
+        if breaker.get_untracked().allows_request() {
+            character_series.refetch();
+        }
+    });
+
+    // The last two lists that arrived, older first. Every successful fetch pushes the newer one back, so after "Fetch
+    // again" we can show what changed in between. Failed fetches don't count, they'd make everything look removed.
+    let (versions, set_versions) = create_signal(cx, (None::<Vec<Amiibo>>, None::<Vec<Amiibo>>));
+    create_effect(cx, move |_| {
+        if let Some(Ok(data)) = character_series.read(cx) {
+            set_versions.update(|(before, after)| *before = after.replace(data));
+        }
+    });
+    // Another name, other amiibo: the lists before and after would have nothing to do with each other. Start over.
+    create_effect(cx, move |previous: Option<String>| {
+        let search = search.get();
+        if previous.is_some_and(|previous| previous != search) {
+            set_versions((None, None));
+        }
+        search
+    });
+    let before = Signal::derive(cx, move || {
+        versions.with(|(before, _)| before.clone().unwrap_or_default())
+    });
+    let after = Signal::derive(cx, move || {
+        versions.with(|(_, after)| after.clone().unwrap_or_default())
+    });
 
     // A big verbose, but basically we read the data that is contained in `character_series`, the resource defined earlier.
-    // We then map through each data, which is basically an element with a structure of `Amiibo` then do some custom rendering.
+    // This closure only decides what to show: the list, the empty state or (through the `ErrorBoundary`) the error. It
+    // reads a memo of just that, so a new list doesn't run it again. If it did, the `<For>` below would be built anew
+    // and every row with it.
+    let outcome = create_memo(cx, move |_| {
+        character_series
+            .read(cx)
+            .map(|data| data.map(|data| data.is_empty()))
+    });
     let character_series_view = move || {
-        character_series.read(cx).map(|data| {
-            data.map(|data| {
-                data.into_iter()
-                    .map(|s| view! { cx, <li>{s.gameSeries}</li> })
-                    .collect_view(cx)
+        // A failed fetch stays an `Err` here. Rendering a `Result` renders the `Ok` side, and hands an `Err` to the
+        // closest `ErrorBoundary` above it, which shows its fallback instead (see the view below).
+        outcome.get().map(|empty| {
+            empty.map(|empty| {
+                // Without this an empty result would just be a blank page, which looks like something broke.
+                if empty {
+                    let message = if search.with(String::is_empty) {
+                        "Type a name to search"
+                    } else {
+                        "No amiibo found"
+                    };
+                    return view! { cx,
+                        <li class="list-none col-span-3">
+                            <EmptyState message=message>"?"</EmptyState>
+                        </li>
+                    }
+                    .into_view(cx);
+                }
+                // We then go through each amiibo, an element with the structure of `Amiibo`, and do some custom
+                // rendering. `<For>` keeps a row per key: when the next search has an amiibo this one had too, its `<li>`
+                // stays as it is, only the rows of new keys are rendered and the rows of missing ones removed. The key
+                // is `head` and `tail`, an amiibo's id (`head` alone is shared by all amiibo of a character).
+                view! { cx,
+                    <For
+                        each=move || character_series.read(cx).and_then(Result::ok).unwrap_or_default()
+                        key=amiibo_id
+                        view=move |cx, amiibo: Amiibo| view! { cx, <AmiiboCard amiibo=amiibo/> }
+                    />
+                }
+                .into_view(cx)
             })
         })
     };
 
-    // * Uncomment the code below for another example.
+    // Show the results as a grid on wide windows and as a list on narrow ones. The decision is debounced, so while you
+    // drag the window edge across the breakpoint the layout doesn't flicker, it switches once you stop.
+    let window_size = use_window_size(cx);
+    let wide = use_debounced(
+        cx,
+        move || window_size.get().width >= 768.0,
+        Duration::from_millis(250),
+    );
+
+    // The response exactly as the server sent it, before our structs picked out the fields they know. Only fetched once
+    // you ask for it: the source is `inspect` and the name, and while `inspect` is `false` (or there is no name) there is
+    // nothing to fetch. The server caches the Amiibo API's answer, so this second request is cheap.
+    let (inspect, set_inspect) = create_signal(cx, false);
+    let raw_response = create_local_resource(
+        cx,
+        move || (inspect.get(), search.get()),
+        |(inspect, name)| async move {
+            if inspect && !name.is_empty() {
+                let url = format!(
+                    "/api/amiibo?name={}",
+                    String::from(js_sys::encode_uri_component(&name))
+                );
+                Some(get_json::<serde_json::Value>(&url).await)
+            } else {
+                None
+            }
+        },
+    );
+    let raw_response_view = move || {
+        raw_response.read(cx).flatten().map(|raw| match raw {
+            Ok(raw) => view! { cx, <JsonTree value=raw open_depth=2/> }.into_view(cx),
+            Err(error) => {
+                view! { cx, <p class="text-red-700">{error.to_string()}</p> }.into_view(cx)
+            }
+        })
+    };
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <label class="block">
+                "Search for an amiibo by name: "
+                <input
+                    type="search"
+                    class="p-1 border"
+                    on:input=move |event| set_name(event_target_value(&event))
+                    prop:value=name
+                />
+            </label>
+            <h1 class="mt-4 text-2xl font-bold">"Results"</h1>
+            <p class="text-red-700">{breaker_message}</p>
+            <ul class=("grid", wide) class=("grid-cols-3", wide) class=("gap-2", wide)>
+                // Until the data is there `read` returns `None` and the list would just stay empty, which looks broken
+                // on a slow connection. `Suspense` notices that a resource read inside it is still loading and shows the
+                // fallback instead, then swaps in the list once it has arrived.
+                <Suspense fallback=move || view! { cx,
+                    <li class="flex items-center col-span-3 gap-2 list-none" role="status">
+                        <span class="w-4 h-4 border-2 border-blue-700 rounded-full border-t-transparent animate-spin" aria-hidden="true"></span>
+                        "Loading..."
+                    </li>
+                }>
+                    // Shows what went wrong instead of the list, whether the request never made it (e.g. offline) or the
+                    // answer didn't fit our structs ("Failed to parse field 'amiibo': ..."). Once a retry succeeds the
+                    // error is gone and the list shows up again.
+                    <ErrorBoundary fallback=move |cx, errors| view! { cx,
+                        <FetchFailed
+                            errors=errors
+                            busy=Signal::derive(cx, move || lifecycle.get() == FetchState::Loading)
+                            // The resource runs again by itself when the name changes. Retrying is the same name
+                            // once more, the source hasn't changed, so `refetch` runs it.
+                            retry=move || character_series.refetch()
+                        />
+                    }>
+                        {character_series_view}
+                    </ErrorBoundary>
+                </Suspense>
+            </ul>
+            // One fetch at a time: while one is running there is nothing to fetch again yet.
+            <button
+                class="block mt-6 text-blue-700 underline disabled:text-gray-400 disabled:no-underline"
+                disabled=move || lifecycle.get() == FetchState::Loading
+                on:click=move |_| character_series.refetch()
+            >
+                "Fetch again"
+            </button>
+            <Show when=move || versions.with(|(before, _)| before.is_some()) fallback=|_| ()>
+                <h2 class="mt-4 text-xl">"What changed since the last fetch"</h2>
+                <DiffView before=before after=after key=amiibo_id describe=describe_amiibo/>
+            </Show>
+            <button class="mt-6 text-blue-700 underline" on:click=move |_| set_inspect.update(|inspect| *inspect = !*inspect)>
+                {move || if inspect.get() { "Hide the raw response" } else { "Inspect the raw response" }}
+            </button>
+            <div class="mt-2">{raw_response_view}</div>
+        </div>
+    }
+}
+
+/// Modal example (overlays, focus trapping)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ModalExample(cx: Scope) -> impl IntoView {
+    // `RwSignal` is a signal where the getter and setter live in one value. Handy when both the parent
+    // (to open the modal) and the modal itself (to close it) need to write to it.
+    let open = create_rw_signal(cx, false);
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Modal"</h1>
+            <button class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow" on:click=move |_| open.set(true)>
+                "Open modal"
+            </button>
+            // Press Tab while the modal is open: focus cycles between the input and the buttons and never
+            // reaches the "Open modal" button behind the backdrop. Closing the modal puts focus back on it.
+            <Modal open=open>
+                <h2 class="text-xl">"Hello from the modal"</h2>
+                <input type="text" class="block mt-4 border" placeholder="Type something"/>
+                <button class="px-3 py-2 mt-4 mr-2 text-white bg-blue-700 rounded" on:click=move |_| open.set(false)>
+                    "Close"
+                </button>
+                <button class="px-3 py-2 mt-4 border rounded">"Does nothing"</button>
+            </Modal>
+        </div>
+    }
+}
+
+/// Toast example (context, timers, queues)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ToastExample(cx: Scope) -> impl IntoView {
+    // The toasts live in a context provided by `App`, so they keep going even if you navigate away.
+    let toasts = use_toasts(cx);
+    let announcer = use_announcer(cx);
+    let (count, set_count) = create_signal(cx, 0);
+
+    let push = move || {
+        set_count.update(|count| *count += 1);
+        toasts.push(format!("Toast number {}", count.get_untracked()));
+    };
+
+    let fruit_basket = || {
+        vec![
+            "Apple".to_string(),
+            "Banana".to_string(),
+            "Cherry".to_string(),
+        ]
+    };
+    let (fruits, set_fruits) = create_signal(cx, fruit_basket());
+    // Deleting shows a toast with an "Undo" button. The closure we pass along owns everything it needs
+    // to put the fruit back where it was.
+    let delete = move |index: usize| {
+        let mut removed = String::new();
+        set_fruits.update(|fruits| removed = fruits.remove(index));
+        announcer.announce(format!("{removed} deleted"));
+        toasts.push_with_action(format!("Deleted {removed}"), "Undo", move || {
+            set_fruits.update(|fruits| fruits.insert(index.min(fruits.len()), removed))
+        });
+    };
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Toasts"</h1>
+            <p class="mt-4">
+                {format!("At most {MAX_VISIBLE} toasts are shown at once, the rest wait in a queue.")}
+            </p>
+            <button class="px-3 py-2 mt-4 mr-2 text-white bg-blue-700 rounded shadow" on:click=move |_| push()>
+                "Show a toast"
+            </button>
+            <button
+                class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| (0..10).for_each(|_| push())
+            >
+                "Show 10 toasts"
+            </button>
+            // `Show` renders the `fallback` instead of the list once every fruit has been deleted.
+            <Show
+                when=move || !fruits.with(Vec::is_empty)
+                fallback=move |cx| {
+                    view! { cx,
+                        <EmptyState
+                            message="All the fruit is gone"
+                            action=EmptyAction::new("Refill the basket", move || set_fruits(fruit_basket()))
+                        >
+                            "🧺"
+                        </EmptyState>
+                    }
+                }
+            >
+                <ul class="mt-6">
+                    {move || {
+                        fruits
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, fruit)| {
+                                view! { cx,
+                                    <li class="flex justify-between max-w-xs mt-2">
+                                        {fruit}
+                                        <button class="text-red-700" on:click=move |_| delete(index)>
+                                            "Delete"
+                                        </button>
+                                    </li>
+                                }
+                            })
+                            .collect_view(cx)
+                    }}
+                </ul>
+            </Show>
+        </div>
+    }
+}
+
+/// All examples on one long page (scroll spy)
+// The sections of the page, as (element id, menu title), in the order they appear.
+#[cfg(feature = "examples-basic")]
+const SECTIONS: [(&str, &str); 5] = [
+    ("counter", "Counter"),
+    ("text_input", "Text input"),
+    ("control_flow", "Control flow"),
+    ("parent_child_communication", "Parent child communication"),
+    ("pass_children", "Pass children"),
+];
+
+#[cfg(feature = "examples-basic")]
+#[component]
+pub fn AllExamples(cx: Scope) -> impl IntoView {
+    // The id of the section that is currently on screen. It changes as you scroll (see scroll_spy.rs).
+    let active = use_scroll_spy(cx, SECTIONS.map(|(id, _)| id).to_vec());
+
+    let menu = SECTIONS
+        .into_iter()
+        .map(|(id, title)| {
+            view! { cx,
+                <li>
+                    <button
+                        class="text-left"
+                        class=("font-bold", move || active.get() == Some(id))
+                        class=("text-blue-700", move || active.get() == Some(id))
+                        // Scroll the section into view instead of changing the URL, the router doesn't need to know.
+                        on:click=move |_| {
+                            if let Some(section) = document().get_element_by_id(id) {
+                                section.scroll_into_view();
+                            }
+                        }
+                    >
+                        {title}
+                    </button>
+                </li>
+            }
+        })
+        .collect_view(cx);
+
+    // How far down this long page you are, in tenths of a percent so the bar moves smoothly (see scroll_progress.rs).
+    let read = use_scroll_progress(cx, 1000);
+
+    view! { cx,
+        // The same `ProgressBar` as the counter's, stretched over the top of the window.
+        <div class="fixed inset-x-0 top-0 z-40 [&>progress]:w-full [&>progress]:h-1 [&>progress]:block">
+            <ProgressBar max=1000 progress=read/>
+        </div>
+        <div class="flex max-w-5xl mx-auto mt-12">
+            <nav class="sticky self-start w-56 top-12 shrink-0">
+                <ul>{menu}</ul>
+            </nav>
+            <div class="flex-1">
+                <section id="counter" class="mb-24">
+                    <Home/>
+                </section>
+                <section id="text_input" class="min-h-screen">
+                    <TextInput/>
+                </section>
+                <section id="control_flow" class="min-h-screen">
+                    <ControlFlow/>
+                </section>
+                <section id="parent_child_communication" class="min-h-screen">
+                    <ParentChildCommunication/>
+                </section>
+                <section id="pass_children" class="min-h-screen">
+                    <PassChildren/>
+                </section>
+            </div>
+        </div>
+    }
+}
+
+/// Feature flags example (runtime feature gating with context)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn FeatureFlagsExample(cx: Scope) -> impl IntoView {
+    let flags = use_feature_flags(cx);
+
+    let rows = DEFAULT_FLAGS
+        .into_iter()
+        .map(|(flag, _, description)| {
+            view! { cx,
+                <li class="mt-2">
+                    <label>
+                        <input
+                            type="checkbox"
+                            class="mr-2"
+                            prop:checked=move || flags.enabled(flag)
+                            on:change=move |event| flags.set(flag, event_target_checked(&event))
+                        />
+                        <code>{flag}</code>
+                        " - "
+                        {description}
+                    </label>
+                </li>
+            }
+        })
+        .collect_view(cx);
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Feature flags"</h1>
+            <p class="mt-4">
+                "Flags are read from context by the components that use them. Your choices are saved in localStorage, "
+                "and can also be set through the URL, e.g. "
+                <code>"?enable=mario_party&disable=title_badge"</code>
+            </p>
+            <ul class="mt-4">{rows}</ul>
+        </div>
+    }
+}
+
+/// Paginated example (cursor-based API, infinite scroll, prefetching)
+// A pretend API that hands out 50 items, 8 at a time. The cursor is just the position of the next page here, a real
+// API usually gives you some opaque string that you pass back as it is. Each page takes a second, like a slow network,
+// so you can tell a prefetched page (there right away) from one that wasn't.
+#[cfg(feature = "examples-advanced")]
+async fn mock_page(cursor: Option<String>) -> std::result::Result<Page<String>, FetchError> {
+    const TOTAL: usize = 50;
+    const PAGE_SIZE: usize = 8;
+    sleep(Duration::from_secs(1)).await;
+    let start = cursor.and_then(|cursor| cursor.parse().ok()).unwrap_or(0);
+    let end = (start + PAGE_SIZE).min(TOTAL);
+    Ok(Page {
+        items: (start..end).map(|n| format!("Item {}", n + 1)).collect(),
+        next: (end < TOTAL).then(|| end.to_string()),
+    })
+}
+
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn PaginatedExample(cx: Scope) -> impl IntoView {
+    // Only the first page is loaded here, `InfiniteScroll` asks for the others as you scroll down. Each page that
+    // arrives has the next one prefetched, so by the time you reach the bottom it is usually there already.
+    let paginated = use_paginated(cx, mock_page);
+    let next_page = move || {
+        if paginated.next_prefetched() {
+            "Next page: prefetched"
+        } else if paginated.has_more() {
+            "Next page: not yet"
+        } else {
+            "Next page: none, this is everything"
+        }
+    };
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Paginated"</h1>
+            <p class="sticky top-0 py-2 text-sm text-gray-500 bg-white dark:bg-gray-900">
+                {next_page}
+            </p>
+            <ul class="mt-6">
+                <For
+                    each=paginated.items()
+                    key=|item| item.clone()
+                    view=move |cx, item| view! { cx, <li class="py-6 border-b">{item}</li> }
+                />
+            </ul>
+            <InfiniteScroll paginated=paginated/>
+        </div>
+    }
+}
+
+/// Chat example (WebSocket, keyed lists, auto-scroll)
+#[cfg(feature = "examples-advanced")]
+#[derive(Clone)]
+struct ChatMessage {
+    id: usize,
+    text: String,
+    // Sent by us, or received from the server.
+    mine: bool,
+}
+
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ChatExample(cx: Scope) -> impl IntoView {
+    // An echo server: it sends every message straight back to us. That way we don't need a chat server to try this out.
+    let socket = use_websocket(cx, "wss://echo.websocket.org");
+    let (messages, set_messages) = create_signal(cx, Vec::<ChatMessage>::new());
+    let next_id = store_value(cx, 0);
+    let (draft, set_draft) = create_signal(cx, String::new());
+    let list = create_node_ref::<Div>(cx);
+
+    let add = move |text: String, mine: bool| {
+        // Follow the new message only when the user is already looking at the newest ones. If they scrolled up to read
+        // something older, jumping to the bottom under their nose would be rude.
+        let at_bottom = list.get_untracked().is_none_or(|list| {
+            list.scroll_top() + list.client_height() + 20 >= list.scroll_height()
+        });
+        next_id.update_value(|id| *id += 1);
+        let id = next_id.get_value();
+        set_messages.update(|messages| messages.push(ChatMessage { id, text, mine }));
+        if at_bottom {
+            // The new message is only in the page on the next frame, scroll after that.
+            request_animation_frame(move || {
+                if let Some(list) = list.get_untracked() {
+                    list.set_scroll_top(list.scroll_height());
+                }
+            });
+        }
+    };
+
+    // `socket.message` holds the latest message from the server, every new one goes into the list.
+    create_effect(cx, move |_| {
+        if let Some(text) = socket.message.get() {
+            add(text, false);
+        }
+    });
+
+    let send = move |event: ev::SubmitEvent| {
+        // Don't let the browser submit the form, that would reload the page.
+        event.prevent_default();
+        let text = draft.get_untracked();
+        if !text.trim().is_empty() && socket.send(&text) {
+            add(text, true);
+            set_draft(String::new());
+        }
+    };
+
+    let status = move || match socket.status.get() {
+        WebSocketStatus::Connecting => "Connecting...",
+        WebSocketStatus::Open => "Connected",
+        WebSocketStatus::Reconnecting => "Connection lost, reconnecting...",
+        WebSocketStatus::Closed => "Disconnected",
+    };
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Chat"</h1>
+            <p class="mt-2 text-gray-500">{status}</p>
+            <div node_ref=list class="p-2 mt-4 overflow-y-auto border rounded h-96">
+                // Keyed by id: a new message adds one `<p>`, the ones already on screen are left alone.
+                <For
+                    each=messages
+                    key=|message| message.id
+                    view=move |cx, message| {
+                        view! { cx,
+                            <p class="my-1" class=("text-right", message.mine) class=("text-blue-700", message.mine)>
+                                {message.text}
+                            </p>
+                        }
+                    }
+                />
+            </div>
+            <form class="flex mt-2" on:submit=send>
+                <input
+                    class="flex-1 p-2 border rounded"
+                    placeholder="Say something"
+                    prop:value=draft
+                    on:input=move |event| set_draft(event_target_value(&event))
+                />
+                <button
+                    class="px-3 py-2 ml-2 text-white bg-blue-700 rounded shadow disabled:opacity-50"
+                    disabled=move || socket.status.get() != WebSocketStatus::Open
+                >
+                    "Send"
+                </button>
+            </form>
+        </div>
+    }
+}
+
+/// Server-sent events example (EventSource)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ServerSentEventsExample(cx: Scope) -> impl IntoView {
+    // Our own server streams the current time to this url, one event every second.
+    let events = use_event_source(cx, "/api/ticks");
+    let status = move || match events.status.get() {
+        EventSourceStatus::Connecting => "Connecting...",
+        EventSourceStatus::Open => "Connected",
+        EventSourceStatus::Closed => "Disconnected",
+    };
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Server-sent events"</h1>
+            <p class="mt-2 text-gray-500">{status}</p>
+            <p class="mt-4 text-xl">
+                "Server time: " {move || events.message.get().unwrap_or_else(|| "-".to_string())}
+            </p>
+        </div>
+    }
+}
+
+/// Client-only example (time and randomness without hydration mismatches)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ClientOnlyExample(cx: Scope) -> impl IntoView {
+    // A number from 1 to 6. The server and the browser would each roll their own, so we roll in the browser only and
+    // show a "?" until then.
+    let roll = || (js_sys::Math::random() * 6.0) as u8 + 1;
+    let die = use_client_only(cx, roll);
+
+    view! { cx,
+        <div class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Client-only values"</h1>
+            <p class="mt-2">
+                <LocalePicker/>
+            </p>
+            <p class="mt-4">"The time here: " <Clock/></p>
+            <p class="mt-4">
+                "You rolled: " {move || die.get().map_or("?".to_string(), |die| die.to_string())}
+            </p>
+        </div>
+    }
+}
+
+/// The local time, updated every second. The server doesn't know the user's time zone, so it renders
+/// a placeholder and the browser fills in the time.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn Clock(cx: Scope) -> impl IntoView {
+    let locale = use_locale(cx);
+    let (tick, set_tick) = create_signal(cx, 0);
+    if !leptos_dom::is_server() {
+        let interval = set_interval_with_handle(
+            move || set_tick.update(|tick| *tick += 1),
+            Duration::from_secs(1),
+        );
+        on_cleanup(cx, move || {
+            if let Ok(interval) = interval {
+                interval.clear();
+            }
+        });
+    }
+
+    view! { cx,
+        <ClientOnly fallback=|_| "--:--:--">
+            {move || {
+                // Reading `tick` makes this run again every second.
+                tick.track();
+                // "3:04:05 PM" or "15:04:05", see locale.rs.
+                format_time_localized(Local::now().time(), locale.get())
+            }}
+        </ClientOnly>
+    }
+}
 
-    // let character_series_view = move || {
-    //     character_series.read(cx).map(|data| {
-    //         data.map(|data| {
-    //             data.into_iter()
-    //                 .map(|s| view! { cx, <img class="p-2 mx-auto mt-6 border shadow" src={s.image} /> })
-    //                 .collect_view(cx)
-    //         })
-    //     })
-    // };
+/// Tabs example (lazy and re-created tab panels)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn TabsExample(cx: Scope) -> impl IntoView {
+    let modes = [
+        (
+            TabMount::Eager,
+            "eager",
+            "Eager: all panels are built right away",
+        ),
+        (
+            TabMount::Lazy,
+            "lazy",
+            "Lazy: a panel is built when its tab is first opened, then kept",
+        ),
+        (
+            TabMount::Recreate,
+            "recreate",
+            "Recreate: only the open panel exists",
+        ),
+    ];
 
     view! { cx,
         <div class="max-w-2xl mx-auto mt-12">
-            <h1 class="text-2xl font-bold">"Results"</h1>
-            <ul>{character_series_view}</ul>
+            <h1 class="text-3xl">"Tabs"</h1>
+            <p class="mt-4">
+                "Type something into a panel, switch to another tab and come back. The counters show how often each panel was built."
+            </p>
+            <p class="mt-2">
+                "The tabs can be dragged into another order, or moved with Ctrl and the left/right arrow keys."
+            </p>
+            {modes
+                .into_iter()
+                .map(|(mode, id, title)| {
+                    // How often each of the three panels has been built, shown inside the panels.
+                    let builds = [(); 3].map(|_| create_rw_signal(cx, 0));
+                    let tabs = ["Overview", "Notes", "Stats"]
+                        .into_iter()
+                        .zip(builds)
+                        .map(|(label, builds)| {
+                            Tab::new(label, move |cx| view! { cx, <DemoPanel label=label builds=builds/> })
+                        })
+                        .collect::<Vec<_>>();
+                    view! { cx,
+                        <h2 class="mt-8 text-xl">{title}</h2>
+                        <Tabs id=id tabs=tabs mount=mode reorderable=true/>
+                    }
+                })
+                .collect_view(cx)}
         </div>
     }
 }
+
+// The content of a tab in the example. Counts itself in `builds` every time it is built.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn DemoPanel(cx: Scope, label: &'static str, builds: RwSignal<u32>) -> impl IntoView {
+    builds.update(|builds| *builds += 1);
+
+    view! { cx,
+        <p>{label} " panel, built " {builds} " time(s)."</p>
+        <input class="p-1 mt-2 border" placeholder="Type something"/>
+    }
+}
+
+/// Memo example (caching an expensive computation)
+// How many primes there are below `limit`, with the sieve of Eratosthenes. Takes a noticeable moment for a few million.
+#[cfg(feature = "examples-advanced")]
+fn count_primes(limit: usize) -> usize {
+    let mut is_prime = vec![true; limit];
+    let mut count = 0;
+    for n in 2..limit {
+        if is_prime[n] {
+            count += 1;
+            // Smaller multiples were crossed out by smaller primes already. `saturating_mul`, because `n * n`
+            // doesn't fit in a 32 bit `usize` (WASM) for the larger `n`.
+            for multiple in (n.saturating_mul(n)..limit).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    count
+}
+
+// `count_primes` for the worker example. `#[wasm_bindgen]` exports it from the WASM, that's how the worker calls it.
+// Numbers cross over to JS as `f64`.
+#[cfg(feature = "examples-advanced")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn count_primes_below(limit: f64) -> f64 {
+    count_primes(limit as usize) as f64
+}
+
+// A millisecond clock for the timings. `performance.now()` in the browser, the server has no `window`.
+#[cfg(feature = "examples-advanced")]
+fn now_ms() -> f64 {
+    if leptos_dom::is_server() {
+        chrono::Utc::now().timestamp_micros() as f64 / 1000.0
+    } else {
+        window()
+            .performance()
+            .map_or(0.0, |performance| performance.now())
+    }
+}
+
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn MemoExample(cx: Scope) -> impl IntoView {
+    let (limit, set_limit) = create_signal(cx, 2_000_000);
+    // Has nothing to do with the primes. Clicking the button only makes both results below render again.
+    let (clicks, set_clicks) = create_signal(cx, 0);
+
+    // Counts the primes and logs how long that took. `runs` counts how often `source` did it.
+    let timed_count = move |source: &str, runs: StoredValue<u32>| {
+        let start = now_ms();
+        let count = count_primes(limit.get());
+        let elapsed = now_ms() - start;
+        runs.update_value(|runs| *runs += 1);
+        info!(
+            "{source}: counted the primes below {} in {elapsed:.1} ms",
+            limit.get_untracked()
+        );
+        (count, elapsed)
+    };
+
+    // A plain closure: it counts again every time it is called, which is every time the view that calls it renders.
+    let closure_runs = store_value(cx, 0);
+    let primes_closure = move || timed_count("closure", closure_runs);
+
+    // A memo calls its function once and keeps the result. It only calls it again when a signal it read (`limit`)
+    // changes, reading the memo in between hands out the kept result for free.
+    let memo_runs = store_value(cx, 0);
+    let primes_memo = create_memo(cx, move |_| timed_count("memo", memo_runs));
+
+    // Both results render again on every click, because they read `clicks`.
+    let result = move |(count, elapsed): (usize, f64), runs: StoredValue<u32>| {
+        format!(
+            "{count} primes, counted in {elapsed:.1} ms. Counted {} times so far, rendered for click {}.",
+            runs.get_value(),
+            clicks.get()
+        )
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Memo"</h1>
+            <p class="mt-4">
+                "Primes below "
+                <input
+                    type="number"
+                    class="w-32 border"
+                    min="2"
+                    max="10000000"
+                    on:change=move |event| {
+                        if let Ok(value) = event_target_value(&event).parse::<usize>() {
+                            set_limit(value.clamp(2, 10_000_000));
+                        }
+                    }
+                    prop:value=limit
+                />
+            </p>
+            <button
+                class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| set_clicks.update(|clicks| *clicks += 1)
+            >
+                "Render again"
+            </button>
+            <h2 class="mt-6 text-xl">"Plain closure"</h2>
+            <p>{move || result(primes_closure(), closure_runs)}</p>
+            <h2 class="mt-6 text-xl">"Memo"</h2>
+            <p>{move || result(primes_memo.get(), memo_runs)}</p>
+            <p class="mt-4 text-xs">
+                "Click \"Render again\" and watch the counts, the timings are also logged to the browser console."
+            </p>
+        </main>
+    }
+}
+
+/// Web worker example (heavy computation off the main thread)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn WorkerExample(cx: Scope) -> impl IntoView {
+    let (limit, set_limit) = create_signal(cx, 30_000_000);
+    let (result, set_result) = create_signal(cx, None::<String>);
+
+    // Ticks ten times a second. It stops while the main thread is busy, that's how you can tell.
+    let (ticks, set_ticks) = create_signal(cx, 0);
+    if !leptos_dom::is_server() {
+        let interval = set_interval_with_handle(
+            move || set_ticks.update(|ticks| *ticks += 1),
+            Duration::from_millis(100),
+        );
+        on_cleanup(cx, move || {
+            if let Ok(interval) = interval {
+                interval.clear();
+            }
+        });
+    }
+
+    // `create_action` runs the async block and tracks whether it is still running (`pending`). It hands the block to
+    // `spawn_local`, which runs it to the end, also after you have left the page. So the worker is wrapped in
+    // `abortable`, and leaving the page aborts it: the future of `run_in_worker` is dropped, and with it the worker,
+    // which stops it (see worker.rs). Without that the worker would keep counting for a page that is gone.
+    let running = store_value(cx, None::<AbortHandle>);
+    on_cleanup(cx, move || {
+        if let Some(running) = running.try_update_value(Option::take).flatten() {
+            running.abort();
+        }
+    });
+    let in_worker = create_action(cx, move |limit: &usize| {
+        let limit = *limit;
+        let (count, handle) = abortable(run_in_worker(
+            "count_primes_below",
+            JsValue::from(limit as f64),
+        ));
+        running.set_value(Some(handle));
+        async move {
+            let start = now_ms();
+            // Aborted, the page is gone and there is nobody to show the result to.
+            let Ok(count) = count.await else {
+                return;
+            };
+            let elapsed = now_ms() - start;
+            set_result(Some(match count {
+                Ok(count) => format!(
+                    "Worker: {} primes in {elapsed:.0} ms",
+                    count.as_f64().unwrap_or_default()
+                ),
+                Err(error) => error.to_string(),
+            }));
+        }
+    });
+    let on_main_thread = move |_| {
+        let start = now_ms();
+        let count = count_primes(limit.get_untracked());
+        let elapsed = now_ms() - start;
+        set_result(Some(format!(
+            "Main thread: {count} primes in {elapsed:.0} ms"
+        )));
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Web worker"</h1>
+            <p class="mt-4">
+                "Count the primes below "
+                <input
+                    type="number"
+                    class="w-32 border"
+                    min="2"
+                    max="100000000"
+                    on:change=move |event| {
+                        if let Ok(value) = event_target_value(&event).parse::<usize>() {
+                            set_limit(value.clamp(2, 100_000_000));
+                        }
+                    }
+                    prop:value=limit
+                />
+            </p>
+            <div class="flex gap-2 mt-4">
+                <button
+                    class="px-3 py-2 text-white bg-blue-700 rounded shadow disabled:opacity-50"
+                    disabled=move || in_worker.pending().get()
+                    on:click=move |_| in_worker.dispatch(limit.get_untracked())
+                >
+                    "In a worker"
+                </button>
+                <button class="px-3 py-2 text-white bg-gray-700 rounded shadow" on:click=on_main_thread>
+                    "On the main thread"
+                </button>
+            </div>
+            <p class="mt-4">
+                {move || if in_worker.pending().get() { "Counting in the worker...".to_string() } else { result.get().unwrap_or_default() }}
+            </p>
+            <p class="mt-4 text-gray-500">"Ticks: " {ticks}</p>
+            <p class="mt-4 text-xs">
+                "The ticks keep going while the worker counts. On the main thread they stop, and so does the rest of the page."
+            </p>
+        </main>
+    }
+}
+
+// Waits for `duration` without blocking anything, like `setTimeout` but as a future you can `.await`.
+#[cfg(feature = "examples-advanced")]
+async fn sleep(duration: Duration) {
+    let done = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(move || _ = resolve.call0(&JsValue::NULL), duration);
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(done).await;
+}
+
+/// Background tasks example (status and cancellation)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn TasksExample(cx: Scope) -> impl IntoView {
+    let tasks = use_tasks(cx);
+    let (started, set_started) = create_signal(cx, 0);
+
+    // Each job just waits a while. A real one would fetch or save something.
+    let start_job = move |seconds: u64, fail: bool| {
+        set_started.update(|started| *started += 1);
+        let name = format!("Job {} ({seconds}s)", started.get_untracked());
+        tasks.try_spawn(name, async move {
+            sleep(Duration::from_secs(seconds)).await;
+            if fail {
+                Err("the server said no")
+            } else {
+                Ok(())
+            }
+        });
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Background tasks"</h1>
+            <div class="flex flex-wrap gap-2 mt-4">
+                <button class="px-3 py-2 text-white bg-blue-700 rounded shadow" on:click=move |_| start_job(3, false)>
+                    "Start a 3s job"
+                </button>
+                <button class="px-3 py-2 text-white bg-blue-700 rounded shadow" on:click=move |_| start_job(10, false)>
+                    "Start a 10s job"
+                </button>
+                <button class="px-3 py-2 text-white bg-red-700 rounded shadow" on:click=move |_| start_job(2, true)>
+                    "Start a job that fails"
+                </button>
+            </div>
+            <div class="flex gap-2 mt-2">
+                <button class="px-3 py-2 border rounded" on:click=move |_| tasks.cancel_all()>"Cancel all"</button>
+                <button class="px-3 py-2 border rounded" on:click=move |_| tasks.clear_finished()>"Clear finished"</button>
+            </div>
+            <p class="mt-4">{move || format!("{} running", tasks.running().len())}</p>
+            <div class="mt-2">
+                <TaskList/>
+            </div>
+            <p class="mt-4 text-xs">
+                "The tasks belong to the app, not to this page: leave the page and come back, they are still going. The title counts them too."
+            </p>
+        </main>
+    }
+}
+
+/// How often the broken effect below may run before we stop it.
+#[cfg(feature = "examples-advanced")]
+const RUNAWAY_LIMIT: u32 = 20;
+
+/// Effects example (`create_effect` vs derived signals and memos)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn EffectsExample(cx: Scope) -> impl IntoView {
+    let (celsius, set_celsius) = create_signal(cx, 20.0);
+
+    // Wrong: a value computed in an effect and written to a second signal. It works, sort of. The effect runs after
+    // `celsius` has changed, so for a moment the two disagree, and everything that reads `fahrenheit_copy`
+    // updates a second time. Effects also don't run on the server, so the HTML it sends has the wrong value.
+    let (fahrenheit_copy, set_fahrenheit_copy) = create_signal(cx, 0.0);
+    create_effect(cx, move |_| {
+        set_fahrenheit_copy(celsius.get() * 9.0 / 5.0 + 32.0)
+    });
+
+    // Right: a value computed from other signals is a derived signal (a closure) or, when it is expensive or read
+    // in many places, a memo. It is never out of date and works on the server too.
+    let fahrenheit = create_memo(cx, move |_| celsius.get() * 9.0 / 5.0 + 32.0);
+
+    // Effects are for side effects: things outside of Leptos that should follow a signal, like a log line, the
+    // document title, localStorage or a third party widget.
+    create_effect(cx, move |_| {
+        info!("The temperature is now {} °C", celsius.get())
+    });
+
+    // Very wrong: an effect that writes a signal it reads. Writing the signal runs the effect again, which writes
+    // it again, and so on until the browser gives up. The page would freeze or crash, so this one has a brake: it only runs after the button
+    // was clicked, and stops itself after `RUNAWAY_LIMIT` runs.
+    let (runs, set_runs) = create_signal(cx, 0);
+    let (armed, set_armed) = create_signal(cx, false);
+    create_effect(cx, move |_| {
+        let current = runs.get();
+        if !armed.get() {
+            return;
+        }
+        if current >= RUNAWAY_LIMIT {
+            set_armed(false);
+            return;
+        }
+        set_runs(current + 1);
+    });
+    // The fix: the count is not derived from anything, it changes because the user did something. So it is
+    // written where that happens, in the event handler, and no effect is needed at all.
+    let (clicks, set_clicks) = create_signal(cx, 0);
+
+    let content = create_node_ref::<Div>(cx);
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Effects and derived values"</h1>
+            // Made from the headings below once they're on the page, see toc.rs.
+            <div class="p-2 mt-4 border rounded">
+                <TableOfContents content=content/>
+            </div>
+            <div node_ref=content>
+                <p class="mt-4">
+                    <input
+                        type="number"
+                        class="w-24 border"
+                        on:input=move |event| {
+                            if let Ok(value) = event_target_value(&event).parse::<f64>() {
+                                set_celsius(value);
+                            }
+                        }
+                        prop:value=celsius
+                    />
+                    " °C"
+                </p>
+                <h2 class="mt-6 text-xl">"Computed in an effect (wrong)"</h2>
+                <p>{move || format!("{:.1} °F", fahrenheit_copy.get())}</p>
+                <h2 class="mt-6 text-xl">"Memo (right)"</h2>
+                <p>{move || format!("{:.1} °F", fahrenheit.get())}</p>
+                <h2 class="mt-6 text-xl">"An effect that writes what it reads (very wrong)"</h2>
+                <button
+                    class="px-3 py-2 mt-2 text-white bg-red-700 rounded shadow"
+                    on:click=move |_| {
+                        set_runs(0);
+                        set_armed(true);
+                    }
+                >
+                    "Start the loop"
+                </button>
+                <p class="mt-2">
+                    {move || match runs.get() {
+                        0 => String::new(),
+                        RUNAWAY_LIMIT => format!("Stopped after {RUNAWAY_LIMIT} runs. Without the brake it would never end."),
+                        runs => format!("{runs} runs"),
+                    }}
+                </p>
+                <h2 class="mt-6 text-xl">"The fix: write it in the event handler"</h2>
+                <button
+                    class="px-3 py-2 mt-2 text-white bg-blue-700 rounded shadow"
+                    on:click=move |_| set_clicks.update(|clicks| *clicks += 1)
+                >
+                    {move || format!("Clicked {} times", clicks.get())}
+                </button>
+                <p class="mt-4 text-xs">
+                    "Reload the page with JavaScript turned off: the effect's °F is 0.0 in the HTML from the server, the memo's is right."
+                </p>
+            </div>
+        </main>
+    }
+}
+
+/// Cleanup example (`on_cleanup` when a component goes away)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn CleanupExample(cx: Scope) -> impl IntoView {
+    let (mounted, set_mounted) = create_signal(cx, true);
+    // What happened, newest last. It lives here in the parent, so it survives the `Ticker` coming and going.
+    let (log, set_log) = create_signal(cx, Vec::<String>::new());
+    let (generation, set_generation) = create_signal(cx, 1);
+
+    // The same component mounted and unmounted over and over, once removing its window listener in `on_cleanup` and
+    // once not. Each side counts its listeners, see `ListenerRegistry`.
+    let leak_demo = move |title: &'static str, clean_up: bool| {
+        let registry = ListenerRegistry::new(cx);
+        let (shown, set_shown) = create_signal(cx, false);
+        // Every `set` mounts or unmounts the watcher right away, so this really is ten components coming and going.
+        let cycle = move |_| {
+            for _ in 0..10 {
+                set_shown(true);
+                set_shown(false);
+            }
+        };
+        view! { cx,
+            <section class="flex-1 p-4 border rounded">
+                <h3 class="text-lg">{title}</h3>
+                <button class="px-3 py-1 mt-2 text-white bg-blue-700 rounded shadow" on:click=cycle>
+                    "Mount and unmount 10 times"
+                </button>
+                <Show when=move || shown.get() fallback=|_| ()>
+                    <ResizeWatcher registry=registry clean_up=clean_up/>
+                </Show>
+                <p class="mt-2">"Listeners on the window: " <strong>{registry.attached}</strong></p>
+                <p>"Resize events handled: " <strong>{registry.calls}</strong></p>
+            </section>
+        }
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Cleanup"</h1>
+            <button
+                class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| {
+                    if mounted.get_untracked() {
+                        set_generation.update(|generation| *generation += 1);
+                    }
+                    set_mounted.update(|mounted| *mounted = !*mounted);
+                }
+            >
+                {move || if mounted.get() { "Unmount the ticker" } else { "Mount the ticker" }}
+            </button>
+            // `Show` creates the ticker in a scope of its own when `when` turns `true`, and disposes of that scope
+            // when it turns `false`. Disposing is what runs the ticker's `on_cleanup`.
+            <Show when=move || mounted.get() fallback=|cx| view! { cx, <p class="mt-4 text-gray-500">"No ticker"</p> }>
+                <Ticker name=format!("Ticker {}", generation.get_untracked()) log=set_log/>
+            </Show>
+            <h2 class="mt-6 text-xl">"Log"</h2>
+            <ul class="font-mono text-sm">
+                {move || log.get().into_iter().map(|line| view! { cx, <li>{line}</li> }).collect_view(cx)}
+            </ul>
+            <h2 class="mt-6 text-xl">"Leaking listeners"</h2>
+            <p class="mt-2">
+                "Mount and unmount on both sides, then resize the window. None of the components are on the page anymore, "
+                "but the listeners on the right still are, and every one of them still runs."
+            </p>
+            <div class="flex flex-col gap-4 mt-4 sm:flex-row">
+                {leak_demo("With on_cleanup", true)}
+                {leak_demo("Without", false)}
+            </div>
+        </main>
+    }
+}
+
+/// Counts seconds with an interval, which it stops again in `on_cleanup`.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn Ticker(cx: Scope, name: String, log: WriteSignal<Vec<String>>) -> impl IntoView {
+    let (seconds, set_seconds) = create_signal(cx, 0);
+    let add = move |line: String| {
+        info!("{line}");
+        log.update(|log| log.push(line));
+    };
+
+    // Timers only make sense in the browser, the server renders the page once and is done with it.
+    if !leptos_dom::is_server() {
+        // Logged a moment later: writing the parent's log while the page is still being hydrated would make it
+        // differ from the HTML the server sent.
+        let mounted = format!("{name}: mounted, starting the interval");
+        queue_microtask(move || add(mounted));
+        let interval = set_interval_with_handle(
+            move || set_seconds.update(|seconds| *seconds += 1),
+            Duration::from_secs(1),
+        );
+
+        // Runs when the scope of this component is disposed, i.e. when the `Show` above removes it. Signals and
+        // effects created with `cx` go away by themselves, but Leptos knows nothing about the interval: without
+        // this it would keep firing after the ticker is gone, setting a signal that doesn't exist anymore.
+        let name = name.clone();
+        on_cleanup(cx, move || {
+            if let Ok(interval) = interval {
+                interval.clear();
+            }
+            add(format!(
+                "{name}: cleaned up after {} seconds, interval stopped",
+                seconds.get_untracked()
+            ));
+        });
+    }
+
+    view! { cx,
+        <p class="mt-4">{name} ": " {seconds} " s"</p>
+    }
+}
+
+/// Keeps count of the "resize" listeners `ResizeWatcher`s add to the window, and of how often they ran.
+#[cfg(feature = "examples-advanced")]
+#[derive(Copy, Clone)]
+struct ListenerRegistry {
+    attached: RwSignal<usize>,
+    calls: RwSignal<usize>,
+}
+
+#[cfg(feature = "examples-advanced")]
+impl ListenerRegistry {
+    fn new(cx: Scope) -> Self {
+        Self {
+            attached: create_rw_signal(cx, 0),
+            calls: create_rw_signal(cx, 0),
+        }
+    }
+
+    /// Adds a "resize" listener to the window and counts it. The returned function removes it again.
+    fn add_resize_listener(self) -> impl FnOnce() {
+        // `try_update`: a leaked listener outlives the page, and with it these signals.
+        let listener =
+            Closure::<dyn Fn()>::new(move || _ = self.calls.try_update(|calls| *calls += 1));
+        _ = window().add_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+        self.attached.update(|attached| *attached += 1);
+        move || {
+            _ = window()
+                .remove_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+            _ = self.attached.try_update(|attached| *attached -= 1);
+        }
+    }
+}
+
+/// Listens for "resize" on the window while it is mounted, if `clean_up` is set. Otherwise the listener stays.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn ResizeWatcher(cx: Scope, registry: ListenerRegistry, clean_up: bool) -> impl IntoView {
+    if !leptos_dom::is_server() {
+        let remove = registry.add_resize_listener();
+        if clean_up {
+            on_cleanup(cx, remove);
+        } else {
+            // What forgetting the cleanup amounts to. Just dropping `remove` would drop the Rust closure while the
+            // browser still holds on to it, and the next resize would throw an error instead of quietly leaking.
+            std::mem::forget(remove);
+        }
+    }
+    view! { cx, <p class="mt-2 text-gray-500">"Listening..."</p> }
+}
+
+// The context of the context example: a color name and the classes that go with it.
+#[cfg(feature = "examples-advanced")]
+#[derive(Copy, Clone)]
+struct Accent {
+    name: &'static str,
+    class: &'static str,
+}
+
+#[cfg(feature = "examples-advanced")]
+impl Default for Accent {
+    fn default() -> Self {
+        Self {
+            name: "gray",
+            class: "border-gray-500 text-gray-500",
+        }
+    }
+}
+
+/// Context example (nested providers, the nearest one wins)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ContextExample(cx: Scope) -> impl IntoView {
+    // The page provides blue for everything below it.
+    provide_context(
+        cx,
+        Accent {
+            name: "blue",
+            class: "border-blue-700 text-blue-700",
+        },
+    );
+
+    // `use_context` looks in the scope it is called with, then in its parent, and so on up to the root. The first
+    // `Accent` it finds wins, so a provider further down hides (shadows) the one above it, but only for its own
+    // children. Every component gets a scope of its own, so what it provides can't reach its siblings or parents.
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Context"</h1>
+            <AccentLevel label="The page"/>
+            <ProvideAccent name="red" class="border-red-700 text-red-700">
+                <AccentLevel label="Inside the red section"/>
+                <ProvideAccent name="green" class="border-green-700 text-green-700">
+                    <AccentLevel label="Inside the green section, inside the red one"/>
+                </ProvideAccent>
+                <AccentLevel label="Back in the red section, after the green one"/>
+            </ProvideAccent>
+            <AccentLevel label="After the red section"/>
+        </main>
+    }
+}
+
+/// Provides another `Accent` to its children, and shows which one it replaces.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn ProvideAccent(
+    cx: Scope,
+    name: &'static str,
+    class: &'static str,
+    children: Children,
+) -> impl IntoView {
+    // Read before providing our own, so this is still the accent from above. Gray when there is none, so a
+    // `ProvideAccent` also works on its own.
+    let outer = use_context_or_default::<Accent>(cx).name;
+    provide_context(cx, Accent { name, class });
+
+    view! { cx,
+        <section class=format!("p-4 mt-4 ml-4 border-l-4 {class}")>
+            <p class="text-sm">{format!("Provides {name} instead of {outer}")}</p>
+            // `children` runs with our `cx`, so they find our `Accent` first.
+            {children(cx)}
+        </section>
+    }
+}
+
+/// Shows the `Accent` it gets from context.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn AccentLevel(cx: Scope, label: &'static str) -> impl IntoView {
+    let accent = expect_context::<Accent>(cx, "ContextExample");
+
+    view! { cx,
+        <p class=format!("p-2 mt-4 border {}", accent.class)>{label} ": " {accent.name}</p>
+    }
+}
+
+// The rows of the table example live on the server (see characters.rs), so that edits are shared: open the table in
+// two windows, edit the same row in both, and the second save finds out about the first. Every row has a version,
+// and a save only goes through while the row still has the version the edit started from (see versioned.rs).
+
+/// All rows of the table example, with their versions.
+#[cfg(feature = "examples-advanced")]
+#[server(ListCharacters, "/api")]
+pub async fn list_characters(cx: Scope) -> Result<Vec<Versioned<Character>>, ServerFnError> {
+    use crate::api::AppState;
+
+    let state = use_context::<AppState>(cx).ok_or_else(|| {
+        ServerFnError::ServerError("The server has no AppState in context".to_string())
+    })?;
+    Ok(state.characters.list())
+}
+
+/// Saves an edit of row `id` of the table example, which started from `version` of the row.
+#[cfg(feature = "examples-advanced")]
+#[server(SaveCharacter, "/api")]
+pub async fn save_character(
+    cx: Scope,
+    id: usize,
+    version: u64,
+    name: String,
+    series: String,
+    year: u16,
+) -> Result<Saved<Character>, ServerFnError> {
+    use crate::api::AppState;
+
+    let state = use_context::<AppState>(cx).ok_or_else(|| {
+        ServerFnError::ServerError("The server has no AppState in context".to_string())
+    })?;
+    let (name, series) = (name.trim().to_string(), series.trim().to_string());
+    if name.is_empty() || series.is_empty() {
+        return Err(ServerFnError::Args(
+            "A character needs a name and a series".to_string(),
+        ));
+    }
+    Ok(state
+        .characters
+        .save(id, version, Character { name, series, year }))
+}
+
+/// The row being edited in the table example.
+#[cfg(feature = "examples-advanced")]
+#[derive(Clone, Debug, PartialEq)]
+struct TableEdit {
+    /// The row as it was when the edit started. Saving sends its version along.
+    base: Versioned<Character>,
+    /// What the inputs hold.
+    draft: Character,
+    /// The row as someone else saved it in the meantime, once a save of this edit was turned down because of it.
+    conflict: Option<Versioned<Character>>,
+    /// The fields that both this edit and the other one changed, after a merge.
+    clashes: Vec<&'static str>,
+}
+
+#[cfg(feature = "examples-advanced")]
+impl TableEdit {
+    fn new(row: Versioned<Character>) -> Self {
+        Self {
+            draft: row.value.clone(),
+            base: row,
+            conflict: None,
+            clashes: Vec::new(),
+        }
+    }
+
+    /// Continues from the row as it is now, with this edit's changes on top. Saving then sends the new version.
+    fn merge(&mut self, theirs: Versioned<Character>) {
+        let (draft, clashes) = self.draft.merge(&self.base.value, &theirs.value);
+        *self = Self {
+            base: theirs,
+            draft,
+            conflict: None,
+            clashes,
+        };
+    }
+}
+
+#[cfg(feature = "examples-advanced")]
+const TABLE_PAGE_SIZE: usize = 8;
+
+#[cfg(feature = "examples-advanced")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum SortColumn {
+    #[default]
+    Name,
+    Series,
+    Year,
+}
+
+#[cfg(feature = "examples-advanced")]
+impl SortColumn {
+    fn key(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Series => "series",
+            Self::Year => "year",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        [Self::Name, Self::Series, Self::Year]
+            .into_iter()
+            .find(|column| column.key() == key)
+    }
+}
+
+/// Everything that decides which rows of the table are shown. All of it lives in the URL, e.g.
+/// `/table?q=ki&series=Kirby&sort=-year&page=2`, see `use_permalink_state` in permalink.rs.
+#[cfg(feature = "examples-advanced")]
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TableView {
+    /// Only names that contain this, ignoring case.
+    search: String,
+    /// Only this series, or all of them.
+    series: Option<String>,
+    sort: SortColumn,
+    descending: bool,
+    /// Counted from 0, the URL counts from 1 like people do.
+    page: usize,
+}
+
+#[cfg(feature = "examples-advanced")]
+impl QueryState for TableView {
+    fn from_query(query: &ParamsMap) -> Self {
+        // The sort order is one parameter: the column, with a `-` in front when it is descending.
+        let sort = query.get("sort").map(String::as_str).unwrap_or_default();
+        let (descending, sort) = match sort.strip_prefix('-') {
+            Some(sort) => (true, sort),
+            None => (false, sort),
+        };
+        let sort = SortColumn::from_key(sort);
+        Self {
+            search: query.get("q").cloned().unwrap_or_default(),
+            series: query
+                .get("series")
+                .cloned()
+                .filter(|series| !series.is_empty()),
+            // A descending order without a (valid) column makes no sense, both go back to the default.
+            descending: descending && sort.is_some(),
+            sort: sort.unwrap_or_default(),
+            page: query
+                .get("page")
+                .and_then(|page| page.parse::<usize>().ok())
+                .map_or(0, |page| page.saturating_sub(1)),
+        }
+    }
+
+    fn to_query(&self, query: &mut ParamsMap) {
+        let mut set = |key: &str, value: Option<String>| match value {
+            Some(value) => query.insert(key.to_string(), value),
+            None => query.remove(key),
+        };
+        set(
+            "q",
+            Some(self.search.clone()).filter(|search| !search.is_empty()),
+        );
+        set("series", self.series.clone());
+        let sort = (self.sort, self.descending) != (SortColumn::default(), false);
+        set(
+            "sort",
+            sort.then(|| {
+                format!(
+                    "{}{}",
+                    if self.descending { "-" } else { "" },
+                    self.sort.key()
+                )
+            }),
+        );
+        set("page", (self.page > 0).then(|| (self.page + 1).to_string()));
+    }
+}
+
+/// Data table example (filters, sort order and page kept in the URL, rows edited on the server)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn TableExample(cx: Scope) -> impl IntoView {
+    let (table, set_table) = use_permalink_state::<TableView>(cx);
+    // Anything that changes which rows match starts over on the first page.
+    let filter = move |change: &dyn Fn(&mut TableView)| {
+        set_table.update(|table| {
+            change(table);
+            table.page = 0;
+        })
+    };
+    let sort_by = move |column: SortColumn| {
+        set_table.update(|table| {
+            // Clicking the current column again flips the order.
+            table.descending = table.sort == column && !table.descending;
+            table.sort = column;
+        })
+    };
+
+    // The rows as the server has them. With auto-refresh turned on at /settings they are fetched again every so often,
+    // so changes saved elsewhere show up here.
+    let characters = create_resource(cx, || (), move |_| list_characters(cx));
+    use_auto_refresh(cx, move || characters.refetch());
+    let all_rows = move || characters.read(cx).and_then(Result::ok).unwrap_or_default();
+    let load_error = move || {
+        characters
+            .read(cx)
+            .and_then(Result::err)
+            .map(|error| format!("Loading the characters failed: {error}"))
+    };
+
+    // The matching rows in order. A memo, so the pages and the counts don't filter and sort again.
+    let rows = create_memo(cx, move |_| {
+        let mut rows = all_rows();
+        table.with(|table| {
+            let search = table.search.to_lowercase();
+            rows.retain(|row| {
+                row.value.name.to_lowercase().contains(&search)
+                    && table
+                        .series
+                        .as_deref()
+                        .is_none_or(|wanted| row.value.series == wanted)
+            });
+            // Ties are broken by name, which stays A to Z either way.
+            rows.sort_by(|a, b| {
+                let (a, b) = (&a.value, &b.value);
+                let order = match table.sort {
+                    SortColumn::Name => a.name.cmp(&b.name),
+                    SortColumn::Series => a.series.cmp(&b.series),
+                    SortColumn::Year => a.year.cmp(&b.year),
+                };
+                let order = if table.descending {
+                    order.reverse()
+                } else {
+                    order
+                };
+                order.then(a.name.cmp(&b.name))
+            });
+        });
+        rows
+    });
+    let pages = move || {
+        rows.with(|rows| rows.len().div_ceil(TABLE_PAGE_SIZE))
+            .max(1)
+    };
+    // A page number from an old link may be past the end now, then the last page is shown.
+    let page = move || table.with(|table| table.page).min(pages() - 1);
+
+    // Every matching row, not just the ones on this page, as JSON and as CSV. Two files would be two downloads, so
+    // they go into one zip, which is put together right here in the browser (see download.rs).
+    let toasts = use_toasts(cx);
+    let download_all = move |_| {
+        let (json, csv) = rows.with_untracked(|rows| (characters_json(rows), characters_csv(rows)));
+        let saved = zip_files([
+            ("characters.json", json.as_bytes()),
+            ("characters.csv", csv.as_bytes()),
+        ])
+        .map_err(|error| error.to_string())
+        .and_then(|archive| {
+            download_bytes(&archive, "application/zip", "characters.zip")
+                .map_err(|error| format!("{error:?}"))
+        });
+        if let Err(error) = saved {
+            toasts.push(format!("Download failed: {error}"));
+        }
+    };
+
+    // The series to filter by. Edits can add one, so they come from the rows.
+    let series = move || {
+        let mut series: Vec<_> = all_rows().into_iter().map(|row| row.value.series).collect();
+        series.sort();
+        series.dedup();
+        series
+    };
+
+    // "Export to PDF" opens the browser's print dialog, which can save the page as a PDF. What ends up on paper is
+    // decided by the `print:` classes below: the search box and the buttons are left out, and instead of the current
+    // page all matching rows are printed, with a line saying which rows these are.
+    let export_pdf = move |_| {
+        _ = window().print();
+    };
+    let description = move || {
+        table.with(|table| {
+            let mut description = format!("{} characters", rows.with(Vec::len));
+            if let Some(series) = &table.series {
+                description.push_str(&format!(" from the {series} series"));
+            }
+            if !table.search.is_empty() {
+                description.push_str(&format!(" whose name contains \"{}\"", table.search));
+            }
+            description.push_str(&format!(
+                ", sorted by {}{}.",
+                table.sort.key(),
+                if table.descending { ", descending" } else { "" }
+            ));
+            description
+        })
+    };
+
+    // One row at a time can be edited. The edit remembers the row it started from, and saving only goes through
+    // while the server still has that version of the row. If someone else saved it in the meantime the server says
+    // so and sends their row: then you pick between merging (their changes plus yours, to look over and save again)
+    // and discarding yours.
+    let (editing, set_editing) = create_signal(cx, None::<TableEdit>);
+    let edit_draft = move |change: &dyn Fn(&mut Character)| {
+        set_editing.update(|edit| {
+            if let Some(edit) = edit {
+                change(&mut edit.draft);
+            }
+        })
+    };
+    let save = create_action(cx, move |edit: &TableEdit| {
+        let (id, version, draft) = (edit.base.id, edit.base.version, edit.draft.clone());
+        async move { save_character(cx, id, version, draft.name, draft.series, draft.year).await }
+    });
+    create_effect(cx, move |_| {
+        let Some(outcome) = save.value().get() else {
+            return;
+        };
+        match outcome {
+            Ok(Saved::Saved(_)) => set_editing(None),
+            Ok(Saved::Conflict(theirs)) => set_editing.update(|edit| {
+                if let Some(edit) = edit {
+                    edit.conflict = Some(theirs);
+                }
+            }),
+            Ok(Saved::Missing) => {
+                set_editing(None);
+                toasts.push("This row doesn't exist anymore");
+            }
+            Err(error) => toasts.push(format!("Saving failed: {error}")),
+        }
+        // Whatever happened, the rows on the server are what to show now.
+        characters.refetch();
+    });
+    // A refresh can bring a newer version of the row being edited. Saving would be turned down, better to say so now.
+    let changed_meanwhile = move || {
+        editing.with(|edit| {
+            edit.as_ref().is_some_and(|edit| {
+                edit.conflict.is_none()
+                    && characters.with(cx, |rows| {
+                        rows.as_ref().is_ok_and(|rows| {
+                            rows.iter().any(|row| {
+                                row.id == edit.base.id && row.version != edit.base.version
+                            })
+                        })
+                    }) == Some(true)
+            })
+        })
+    };
+
+    let row = move |row: &Versioned<Character>| {
+        let character = row.value.clone();
+        let label = format!("Edit {}", character.name);
+        let start_editing = {
+            let row = row.clone();
+            move |_| set_editing(Some(TableEdit::new(row.clone())))
+        };
+        view! { cx,
+            // Not split over two sheets of paper.
+            <tr class="border-b break-inside-avoid">
+                <td class="p-2">{character.name}</td>
+                <td class="p-2">{character.series}</td>
+                <td class="p-2">{character.year}</td>
+                <td class="p-2 text-right print:hidden">
+                    <button
+                        class="text-blue-700 underline disabled:text-gray-400 disabled:no-underline"
+                        disabled=move || editing.with(Option::is_some)
+                        aria-label=label
+                        on:click=start_editing
+                    >
+                        "Edit"
+                    </button>
+                </td>
+            </tr>
+        }
+        .into_view(cx)
+    };
+    let edit_row = move |edit: TableEdit| {
+        let conflict = edit.conflict.clone().map(|theirs| {
+            let merge = {
+                let theirs = theirs.clone();
+                move |_| {
+                    set_editing.update(|edit| {
+                        if let Some(edit) = edit {
+                            edit.merge(theirs.clone());
+                        }
+                    })
+                }
+            };
+            let Character { name, series, year } = theirs.value;
+            view! { cx,
+                <tr class="bg-yellow-100 dark:bg-yellow-900" role="alert">
+                    <td class="p-2" colspan="4">
+                        <p>
+                            "Someone else saved this row while you were editing it. It is now: "
+                            <b>{format!("{name}, {series}, {year}")}</b>
+                            ". Your edit wasn't saved."
+                        </p>
+                        <div class="flex gap-2 mt-2">
+                            <button class="px-3 py-1 border rounded" on:click=merge>
+                                "Merge"
+                            </button>
+                            <button class="px-3 py-1 border rounded" on:click=move |_| set_editing(None)>
+                                "Discard my changes"
+                            </button>
+                        </div>
+                        <p class="mt-1 text-xs">
+                            "Merge keeps their changes and yours, so you can look it over and save again."
+                        </p>
+                    </td>
+                </tr>
+            }
+        });
+        let clashes = (!edit.clashes.is_empty()).then(|| {
+            view! { cx,
+                <tr>
+                    <td class="p-2 text-sm" colspan="4">
+                        {format!(
+                            "You both changed the {}. Yours is kept, have a look before saving.",
+                            edit.clashes.join(" and ")
+                        )}
+                    </td>
+                </tr>
+            }
+        });
+        let draft = move || {
+            editing.with(|edit| {
+                edit.as_ref()
+                    .map(|edit| edit.draft.clone())
+                    .unwrap_or_default()
+            })
+        };
+        let blocked = edit.conflict.is_some();
+        view! { cx,
+            <tr class="border-b">
+                <td class="p-1">
+                    <input
+                        class="w-full p-1 border dark:bg-gray-800"
+                        aria-label="Name"
+                        on:input=move |event| {
+                            let name = event_target_value(&event);
+                            edit_draft(&move |draft| draft.name = name.clone())
+                        }
+                        prop:value=move || draft().name
+                    />
+                </td>
+                <td class="p-1">
+                    <input
+                        class="w-full p-1 border dark:bg-gray-800"
+                        aria-label="Series"
+                        on:input=move |event| {
+                            let series = event_target_value(&event);
+                            edit_draft(&move |draft| draft.series = series.clone())
+                        }
+                        prop:value=move || draft().series
+                    />
+                </td>
+                <td class="p-1">
+                    <input
+                        type="number"
+                        class="w-24 p-1 border dark:bg-gray-800"
+                        aria-label="First appeared"
+                        on:input=move |event| {
+                            if let Ok(year) = event_target_value(&event).parse() {
+                                edit_draft(&move |draft| draft.year = year)
+                            }
+                        }
+                        prop:value=move || draft().year
+                    />
+                </td>
+                <td class="p-1 text-right whitespace-nowrap">
+                    <button
+                        class="px-2 py-1 text-white bg-blue-700 rounded disabled:bg-gray-400"
+                        disabled=move || {
+                            let draft = draft();
+                            blocked || save.pending().get() || draft.name.trim().is_empty() || draft.series.trim().is_empty()
+                        }
+                        on:click=move |_| {
+                            if let Some(edit) = editing.get_untracked() {
+                                save.dispatch(edit);
+                            }
+                        }
+                    >
+                        "Save"
+                    </button>
+                    <button class="px-2 py-1 ml-1 border rounded" disabled=blocked on:click=move |_| set_editing(None)>
+                        "Cancel"
+                    </button>
+                </td>
+            </tr>
+            {clashes}
+            <Show when=changed_meanwhile fallback=|_| ()>
+                <tr>
+                    <td class="p-2 text-sm" colspan="4">
+                        "Someone else changed this row since you started. Saving will ask what to keep."
+                    </td>
+                </tr>
+            </Show>
+            {conflict}
+        }
+        .into_view(cx)
+    };
+
+    let header = move |column: SortColumn, label: &'static str| {
+        let arrow = move || {
+            table.with(|table| match (table.sort == column, table.descending) {
+                (false, _) => "",
+                (true, false) => " ▲",
+                (true, true) => " ▼",
+            })
+        };
+        view! { cx,
+            <th class="p-2 text-left">
+                <button on:click=move |_| sort_by(column)>{label} {arrow}</button>
+            </th>
+        }
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Data table"</h1>
+            // Until the rows have arrived everything below would be empty, as if no character matched. While they are
+            // fetched again (a refresh, or after a save) `Transition` keeps showing the rows it has.
+            <Transition fallback=move || view! { cx, <p class="mt-4" role="status">"Loading..."</p> }>
+                <p class="hidden mt-2 print:block">{description}</p>
+                <div class="flex gap-2 mt-4 print:hidden">
+                    <input
+                        type="search"
+                        placeholder="Search names"
+                        class="flex-1 p-1 border"
+                        on:input=move |event| {
+                            let search = event_target_value(&event);
+                            filter(&move |table| table.search = search.clone())
+                        }
+                        prop:value=move || table.with(|table| table.search.clone())
+                    />
+                    <select
+                        class="p-1 border"
+                        on:change=move |event| {
+                            let series = event_target_value(&event);
+                            filter(&move |table| table.series = Some(series.clone()).filter(|series| !series.is_empty()))
+                        }
+                        prop:value=move || table.with(|table| table.series.clone().unwrap_or_default())
+                    >
+                        <option value="">"All series"</option>
+                        {move || series()
+                            .into_iter()
+                            .map(|series| view! { cx, <option value=series.clone()>{series}</option> })
+                            .collect_view(cx)}
+                    </select>
+                </div>
+                <p class="mt-2 text-red-700" role="alert">{load_error}</p>
+                <table class="w-full mt-4">
+                    <thead class="border-b">
+                        <tr>
+                            {header(SortColumn::Name, "Name")}
+                            {header(SortColumn::Series, "Series")}
+                            {header(SortColumn::Year, "First appeared")}
+                            <th class="print:hidden"></th>
+                        </tr>
+                    </thead>
+                    // On screen, the current page, with the row being edited as inputs.
+                    <tbody class="print:hidden">
+                        {move || {
+                            // `Transition` only waits for resources read with its own `cx`, the one in here. The memo
+                            // above reads with the page's, so this read is what holds back the server's HTML until the
+                            // rows are there. Without it the server would send an empty table.
+                            characters.with(cx, |_| ())?;
+                            let start = page() * TABLE_PAGE_SIZE;
+                            let edit = editing.get();
+                            Some(rows.with(|rows| {
+                                rows.iter()
+                                    .skip(start)
+                                    .take(TABLE_PAGE_SIZE)
+                                    .map(|current| match &edit {
+                                        Some(edit) if edit.base.id == current.id => edit_row(edit.clone()),
+                                        _ => row(current),
+                                    })
+                                    .collect_view(cx)
+                            }))
+                        }}
+                    </tbody>
+                    // On paper, every matching row. The browser repeats the header on every sheet.
+                    <tbody class="hidden print:table-row-group">
+                        {move || rows.with(|rows| rows.iter().map(row).collect_view(cx))}
+                    </tbody>
+                </table>
+                <div class="flex items-center gap-4 mt-4 print:hidden">
+                    <button
+                        class="px-3 py-1 border rounded disabled:opacity-50"
+                        disabled=move || page() == 0
+                        on:click=move |_| set_table.update(|table| table.page = page() - 1)
+                    >
+                        "Previous"
+                    </button>
+                    <span>{move || format!("Page {} of {}, {} matches", page() + 1, pages(), rows.with(Vec::len))}</span>
+                    <button
+                        class="px-3 py-1 border rounded disabled:opacity-50"
+                        disabled=move || page() + 1 >= pages()
+                        on:click=move |_| set_table.update(|table| table.page = page() + 1)
+                    >
+                        "Next"
+                    </button>
+                    <button class="px-3 py-1 ml-auto border rounded" on:click=download_all>
+                        "Download all"
+                    </button>
+                    <button class="px-3 py-1 border rounded" on:click=export_pdf>
+                        "Export to PDF"
+                    </button>
+                </div>
+            </Transition>
+            <p class="mt-4 text-xs print:hidden">"Everything you pick ends up in the address bar. Copy it, and the link opens this exact view."</p>
+        </main>
+    }
+}
+
+#[cfg(feature = "examples-advanced")]
+fn characters_json(rows: &[Versioned<Character>]) -> String {
+    let rows: Vec<_> = rows.iter().map(|row| &row.value).collect();
+    serde_json::to_string_pretty(&rows).unwrap_or_default()
+}
+
+// One line per row, with a header line. A field that contains a comma, a quote or a line break goes in quotes, with
+// its quotes doubled, so spreadsheets read it as one field.
+#[cfg(feature = "examples-advanced")]
+fn characters_csv(rows: &[Versioned<Character>]) -> String {
+    let field = |text: &str| {
+        if text.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    };
+    let mut csv = String::from("name,series,year\r\n");
+    for Versioned { value, .. } in rows {
+        csv.push_str(&format!(
+            "{},{},{}\r\n",
+            field(&value.name),
+            field(&value.series),
+            value.year
+        ));
+    }
+    csv
+}
+
+// Everything the sign up form holds, as one value: what submitting sends, and what a saved draft holds (as JSON, see
+// `FormExample`). Thanks to `default` a draft doesn't need every field, the missing ones are left empty. So a half
+// filled in draft loads, and so does one saved before a field was added here.
+#[cfg(feature = "examples-advanced")]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct SignUp {
+    username: String,
+    email: String,
+    // Left empty in drafts, and then left out of the JSON altogether. A password doesn't belong in localStorage.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    password: String,
+    phones: Vec<String>,
+}
+
+/// Where the form's draft is saved.
+#[cfg(feature = "examples-advanced")]
+const SIGN_UP_DRAFT_KEY: &str = "sign-up-draft";
+
+/// Form validation example (rules per field, errors shown once a field was left, a username check on the server)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn FormExample(cx: Scope) -> impl IntoView {
+    let toasts = use_toasts(cx);
+    let (username, set_username) = create_signal(cx, String::new());
+    let (email_address, set_email_address) = create_signal(cx, String::new());
+    let (password, set_password) = create_signal(cx, String::new());
+    let (repeated, set_repeated) = create_signal(cx, String::new());
+
+    // Each field gets its rules, see validate.rs. They are checked again on every keystroke.
+    // Whether the username is taken only the server knows. That check waits until you stop typing for a moment, and
+    // only asks about names that pass the other rules.
+    let username_check = use_async_validation(
+        cx,
+        move || username.get(),
+        vec![
+            required(),
+            min_len(3),
+            max_len(20),
+            matches("^[a-z0-9_]*$", "Only lowercase letters, digits and _"),
+        ],
+        AsyncRule::new(username_available),
+        Duration::from_millis(400),
+    );
+    let email_check = use_validation(cx, move || email_address.get(), vec![required(), email()]);
+    let password_check = use_validation(
+        cx,
+        move || password.get(),
+        vec![
+            required(),
+            min_len(8),
+            matches("[0-9]", "Use at least one digit"),
+            matches(
+                "[^a-zA-Z0-9]",
+                "Use at least one character that is not a letter or digit",
+            ),
+        ],
+    );
+    // This rule reads the `password` signal. It runs inside the validation's memo, so typing in the password field
+    // checks this field again too.
+    let repeated_check = use_validation(
+        cx,
+        move || repeated.get(),
+        vec![
+            required().with_message("Type the password again"),
+            Rule::new("The passwords are not the same", move |value| {
+                value == password.get()
+            }),
+        ],
+    );
+    let fields = [username_check, email_check, password_check, repeated_check];
+    // As many phone numbers as you like, one signal each (see repeater.rs). The first one is there from the start.
+    let phones = create_rw_signal(cx, vec![create_rw_signal(cx, String::new())]);
+
+    // The signals, collected into one `SignUp`, and back.
+    let form = move || SignUp {
+        username: username.get_untracked(),
+        email: email_address.get_untracked(),
+        password: password.get_untracked(),
+        phones: phones
+            .with_untracked(|phones| phones.iter().map(|phone| phone.get_untracked()).collect()),
+    };
+    let fill_in = move |form: SignUp| {
+        set_username(form.username);
+        set_email_address(form.email);
+        set_password(form.password);
+        // New rows, new keys: the repeater renders them from scratch. There is always at least the one row to type in.
+        let mut rows: Vec<_> = form
+            .phones
+            .into_iter()
+            .map(|phone| create_rw_signal(cx, phone))
+            .collect();
+        if rows.is_empty() {
+            rows.push(create_rw_signal(cx, String::new()));
+        }
+        phones.set(rows);
+    };
+
+    // A draft can be saved however far you got and picked up later, e.g. after closing the tab.
+    let save_draft = move |_| {
+        let draft = SignUp {
+            password: String::new(),
+            ..form()
+        };
+        let saved = window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .zip(serde_json::to_string(&draft).ok())
+            .is_some_and(|(storage, json)| storage.set_item(SIGN_UP_DRAFT_KEY, &json).is_ok());
+        toasts.push(if saved {
+            "Draft saved"
+        } else {
+            "The draft couldn't be saved"
+        });
+    };
+    let restore_draft = move |_| {
+        let json = window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(SIGN_UP_DRAFT_KEY).ok().flatten());
+        match json.map(|json| serde_json::from_str::<SignUp>(&json)) {
+            Some(Ok(draft)) => fill_in(draft),
+            Some(Err(error)) => toasts.push(format!("The saved draft couldn't be read: {error}")),
+            None => toasts.push("No draft saved yet"),
+        }
+    };
+
+    // What the last successful submit would have sent to a server.
+    let (submitted, set_submitted) = create_signal(cx, None::<String>);
+
+    let on_submit = move |event: ev::SubmitEvent| {
+        // The browser would send the form and load another page, we handle it here instead.
+        event.prevent_default();
+        if validate_all(&fields) {
+            let mut sign_up = form();
+            // Rows left empty are not phone numbers.
+            sign_up.phones.retain(|phone| !phone.trim().is_empty());
+            set_submitted(serde_json::to_string_pretty(&sign_up).ok());
+            toasts.push(format!("Signed up as {}", sign_up.username));
+        } else if fields.iter().any(Validation::is_pending) {
+            toasts.push("Still checking the username, try again in a moment");
+        }
+    };
+
+    // A labelled input with the errors of its field below it. `touch` on `blur`: errors show up once you leave a
+    // field, not while you are still typing the first letters.
+    let field = move |label: &'static str,
+                      kind: &'static str,
+                      value: ReadSignal<String>,
+                      set: WriteSignal<String>,
+                      check: Validation,
+                      all: bool| {
+        view! { cx,
+            <label class="block mt-4">
+                <span class="block">{label}</span>
+                <input
+                    type=kind
+                    class="w-full p-1 border"
+                    class=("border-red-700", move || !check.visible_errors().is_empty())
+                    aria-invalid=move || (!check.visible_errors().is_empty()).to_string()
+                    on:input=move |event| set(event_target_value(&event))
+                    on:blur=move |_| check.touch()
+                    prop:value=value
+                />
+            </label>
+            <FieldErrors validation=check all=all/>
+        }
+    };
+
+    view! { cx,
+        <main class="max-w-md mx-auto mt-12">
+            <h1 class="text-3xl">"Sign up"</h1>
+            <p class="mt-2 text-gray-500">"Taken usernames: admin, root, leptos, ferris and tailwind."</p>
+            // `novalidate` turns off the browser's own checks, ours do the job.
+            <form novalidate=true on:submit=on_submit>
+                {field("Username", "text", username, set_username, username_check, false)}
+                {field("Email", "email", email_address, set_email_address, email_check, false)}
+                // All the password's errors at once, so you know everything it still needs.
+                {field("Password", "password", password, set_password, password_check, true)}
+                {field("Password again", "password", repeated, set_repeated, repeated_check, false)}
+                <fieldset class="mt-4">
+                    <legend>"Phone numbers (optional)"</legend>
+                    <Repeater
+                        items=phones
+                        new_item=String::new
+                        add_label="Add another phone number"
+                        row={|cx, phone: RwSignal<String>| view! { cx,
+                            <input
+                                type="tel"
+                                class="w-full p-1 border"
+                                aria-label="Phone number"
+                                on:input=move |event| phone.set(event_target_value(&event))
+                                prop:value=phone
+                            />
+                        }}
+                    />
+                </fieldset>
+                <div class="flex items-center gap-4 mt-6">
+                    <button type="submit" class="px-3 py-2 text-white bg-blue-700 rounded shadow">
+                        "Sign up"
+                    </button>
+                    <button type="button" class="text-blue-700 underline" on:click=save_draft>
+                        "Save draft"
+                    </button>
+                    <button type="button" class="text-blue-700 underline" on:click=restore_draft>
+                        "Restore draft"
+                    </button>
+                </div>
+            </form>
+            {move || submitted.get().map(|json| view! { cx,
+                <h2 class="mt-6 text-xl">"Submitted"</h2>
+                <pre class="p-2 mt-2 overflow-x-auto text-sm border">{json}</pre>
+            })}
+        </main>
+    }
+}
+
+// `None` when nobody has `name` yet, see `/api/username` in api.rs. The rules only let through letters, digits and
+// `_`, so the name goes into the url as it is.
+#[cfg(feature = "examples-advanced")]
+async fn username_available(name: String) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Availability {
+        available: bool,
+    }
+
+    match get_json::<Availability>(&format!("/api/username?name={name}")).await {
+        Ok(Availability { available: true }) => None,
+        Ok(Availability { available: false }) => Some(format!("{name} is taken")),
+        Err(error) => Some(format!("Couldn't check the username: {error}")),
+    }
+}
+
+/// Text statistics example (several derived values from one signal)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn TextStatsExample(cx: Scope) -> impl IntoView {
+    let (text, set_text) = create_signal(cx, String::new());
+
+    // Every number below is derived from `text` and nothing else, each in its own memo. A memo only tells the page
+    // about a new value when it really is different: typing a letter in the middle of a word changes the characters
+    // but not the words, so the word count and the reading time aren't touched.
+    let words = create_memo(cx, move |_| text.with(|text| word_count(text)));
+    let lines = create_memo(cx, move |_| text.with(|text| line_count(text)));
+    let characters = create_memo(cx, move |_| text.with(|text| character_count(text)));
+    let without_spaces = create_memo(cx, move |_| {
+        text.with(|text| character_count_without_spaces(text))
+    });
+    // Derived from another derived value, it only runs when the word count changes.
+    let reading = create_memo(cx, move |_| reading_time(words.get()));
+
+    let stat = move |label: &'static str, value: Memo<String>| {
+        view! { cx,
+            <div class="p-2 border rounded">
+                <dt class="text-xs text-gray-500">{label}</dt>
+                <dd class="text-2xl">{value}</dd>
+            </div>
+        }
+    };
+    let number = move |count: Memo<usize>| create_memo(cx, move |_| count.get().to_string());
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Text statistics"</h1>
+            <textarea
+                class="w-full h-48 p-2 mt-4 border dark:bg-gray-800"
+                placeholder="Type or paste some text"
+                on:input=move |event| set_text(event_target_value(&event))
+                prop:value=text
+            />
+            <dl class="grid grid-cols-2 gap-2 mt-4 sm:grid-cols-5">
+                {stat("Words", number(words))}
+                {stat("Lines", number(lines))}
+                {stat("Characters", number(characters))}
+                {stat("Without spaces", number(without_spaces))}
+                {stat("Reading time", reading)}
+            </dl>
+        </main>
+    }
+}
+
+// Words are whatever is between whitespace, so several spaces in a row, tabs and line breaks all count as one gap.
+// `split_whitespace` knows Unicode's spaces too, like the no-break space.
+#[cfg(feature = "examples-advanced")]
+fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+// An empty text has no lines. Otherwise there is one more line than there are line breaks, also when the last line is
+// still empty: that's the line the cursor is on after pressing Enter.
+#[cfg(feature = "examples-advanced")]
+fn line_count(text: &str) -> usize {
+    if text.is_empty() {
+        0
+    } else {
+        text.split('\n').count()
+    }
+}
+
+// What people see as one character. Neither bytes (`len()`, "é" is 2) nor `char`s: "👍🏽" is two `char`s (a thumb and a
+// skin tone), and "é" can be an "e" followed by an accent. Unicode calls what we want a grapheme cluster.
+#[cfg(feature = "examples-advanced")]
+fn character_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+#[cfg(feature = "examples-advanced")]
+fn character_count_without_spaces(text: &str) -> usize {
+    text.graphemes(true)
+        .filter(|grapheme| !grapheme.chars().all(char::is_whitespace))
+        .count()
+}
+
+// At about 200 words a minute, rounded up: 201 words are a 2 minute read.
+#[cfg(feature = "examples-advanced")]
+fn reading_time(words: usize) -> String {
+    const WORDS_PER_MINUTE: usize = 200;
+    match words {
+        0 => "-".to_string(),
+        words if words < WORDS_PER_MINUTE => "< 1 min".to_string(),
+        words => format!("{} min", words.div_ceil(WORDS_PER_MINUTE)),
+    }
+}
+
+#[cfg(feature = "examples-advanced")]
+const EDITOR_TEXT: &str = "Leptos is a Rust framework. In Leptos, a signal holds a value and tells whoever reads it when it changes.
+A memo is a signal too: its value is computed from other signals.
+Signals are cheap to copy, and a signal's setter is a signal setter, not a signal.";
+
+/// Find and replace example (matches derived from the text and the search options)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn EditorExample(cx: Scope) -> impl IntoView {
+    let (text, set_text) = create_signal(cx, EDITOR_TEXT.to_string());
+    let (query, set_query) = create_signal(cx, "signal".to_string());
+    let (replacement, set_replacement) = create_signal(cx, String::new());
+    let (case_sensitive, set_case_sensitive) = create_signal(cx, false);
+    let (whole_word, set_whole_word) = create_signal(cx, false);
+    // Which match "Replace" replaces. Counted among the matches, so it's kept in range below when there are fewer.
+    let (current, set_current) = create_signal(cx, 0);
+
+    let options = move || FindOptions {
+        case_sensitive: case_sensitive.get(),
+        whole_word: whole_word.get(),
+    };
+    // Four signals decide the matches. The memo runs again when any of them changes, and only then.
+    let matches = create_memo(cx, move |_| {
+        let options = options();
+        query.with(|query| text.with(|text| find_matches(text, query, options)))
+    });
+    let current_match = Signal::derive(cx, move || {
+        let count = matches.with(Vec::len);
+        (count > 0).then(|| current.get().min(count - 1))
+    });
+
+    let step = move |forward: bool| {
+        let count = matches.with_untracked(Vec::len);
+        if count > 0 {
+            let at = current_match.get_untracked().unwrap_or(0);
+            set_current(if forward {
+                (at + 1) % count
+            } else {
+                (at + count - 1) % count
+            });
+        }
+    };
+    let replace = move |_| {
+        let Some(at) = current_match.get_untracked() else {
+            return;
+        };
+        let range = matches.with_untracked(|matches| matches[at].clone());
+        let replacement = replacement.get_untracked();
+        let replaced = text.with_untracked(|text| replace_range(text, range.clone(), &replacement));
+        // Go on with the first match after the replacement, not one inside it: replacing "signal" by "signals" would
+        // otherwise find the new "signal" at the same spot and replace it again and again.
+        let continue_at = range.start + replacement.len();
+        let next = query
+            .with_untracked(|query| find_matches(&replaced, query, cx.untrack(options)))
+            .iter()
+            .position(|found| found.start >= continue_at)
+            .unwrap_or(0);
+        set_text(replaced);
+        set_current(next);
+    };
+    let replace_every = move |_| {
+        let replacement = replacement.get_untracked();
+        let replaced = text.with_untracked(|text| {
+            matches.with_untracked(|matches| replace_all(text, matches, &replacement))
+        });
+        set_text(replaced);
+        set_current(0);
+    };
+
+    let status = move || match (current_match.get(), matches.with(Vec::len)) {
+        (_, 0) if query.with(String::is_empty) => String::new(),
+        (_, 0) => "No matches".to_string(),
+        (Some(at), count) => format!("{} of {count}", at + 1),
+        (None, _) => String::new(),
+    };
+    let toggle = move |label: &'static str, value: ReadSignal<bool>, set: WriteSignal<bool>| {
+        view! { cx,
+            <label class="mr-4">
+                <input
+                    type="checkbox"
+                    class="mr-1"
+                    prop:checked=value
+                    on:change=move |event| {
+                        set(event_target_checked(&event));
+                        set_current(0);
+                    }
+                />
+                {label}
+            </label>
+        }
+    };
+    let button = "px-3 py-1 border rounded disabled:opacity-50";
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Find and replace"</h1>
+            <div class="flex items-center gap-2 mt-4">
+                <input
+                    type="search"
+                    placeholder="Find"
+                    class="flex-1 p-1 border dark:bg-gray-800"
+                    on:input=move |event| {
+                        set_query(event_target_value(&event));
+                        set_current(0);
+                    }
+                    // Enter goes to the next match, Shift+Enter to the previous one, like in most editors.
+                    on:keydown=move |event| {
+                        if event.key() == "Enter" {
+                            event.prevent_default();
+                            step(!event.shift_key());
+                        }
+                    }
+                    prop:value=query
+                />
+                <span class="w-24 text-sm text-gray-500" aria-live="polite">{status}</span>
+                <button class=button aria-label="Previous match" disabled=move || matches.with(Vec::is_empty) on:click=move |_| step(false)>"↑"</button>
+                <button class=button aria-label="Next match" disabled=move || matches.with(Vec::is_empty) on:click=move |_| step(true)>"↓"</button>
+            </div>
+            <div class="flex items-center gap-2 mt-2">
+                <input
+                    type="text"
+                    placeholder="Replace with"
+                    class="flex-1 p-1 border dark:bg-gray-800"
+                    on:input=move |event| set_replacement(event_target_value(&event))
+                    prop:value=replacement
+                />
+                <button class=button disabled=move || matches.with(Vec::is_empty) on:click=replace>"Replace"</button>
+                <button class=button disabled=move || matches.with(Vec::is_empty) on:click=replace_every>"Replace all"</button>
+            </div>
+            <p class="mt-2 text-sm">
+                {toggle("Match case", case_sensitive, set_case_sensitive)}
+                {toggle("Whole word", whole_word, set_whole_word)}
+            </p>
+            <textarea
+                class="w-full h-32 p-2 mt-4 border dark:bg-gray-800"
+                on:input=move |event| set_text(event_target_value(&event))
+                prop:value=text
+            />
+            // A textarea can't mark parts of its text, so the matches are shown in a copy of it below.
+            <div class="p-2 mt-2 border rounded">
+                <Highlight text=text ranges=matches current=current_match/>
+            </div>
+        </main>
+    }
+}
+
+/// Rich text example (contenteditable with a formatting toolbar)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn RichTextExample(cx: Scope) -> impl IntoView {
+    // The editor writes its HTML here on every change, see rich_text.rs.
+    let html = create_rw_signal(
+        cx,
+        "Select some text and make it <b>bold</b> or <i>italic</i>. Undo takes back the formatting too.".to_string(),
+    );
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Rich text"</h1>
+            <div class="mt-4">
+                <RichTextEditor html=html/>
+            </div>
+            <h2 class="mt-6 text-xl">"The signal"</h2>
+            // Shown as text, not as HTML: whatever is typed (or pasted) into the editor ends up here.
+            <pre class="p-2 mt-2 text-sm whitespace-pre-wrap bg-gray-100 dark:bg-gray-800">{html}</pre>
+        </main>
+    }
+}
+
+/// Reactive graph example (which signals, memos and effects run when something changes)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn ReactiveGraphExample(cx: Scope) -> impl IntoView {
+    // Everything below reports to the tracker when it runs, and the graph lights up what ran (see reactive_graph.rs).
+    let tracker = use_tracker(cx);
+    let (a, set_a) = create_signal(cx, 1);
+    let (b, set_b) = create_signal(cx, 2);
+    let sum = create_memo(cx, move |_| {
+        tracker.ran("sum");
+        a.get() + b.get()
+    });
+    let a_is_even = create_memo(cx, move |_| {
+        tracker.ran("even");
+        a.get() % 2 == 0
+    });
+    // Effects only run in the browser. These two just log, look at the console.
+    create_effect(cx, move |_| {
+        tracker.ran("log_sum");
+        info!("a + b is {}", sum.get());
+    });
+    create_effect(cx, move |_| {
+        tracker.ran("log_even");
+        info!("a is {}", if a_is_even.get() { "even" } else { "odd" });
+    });
+
+    let nodes = vec![
+        GraphNode {
+            id: "a",
+            label: "a",
+            kind: NodeKind::Signal,
+            column: 0,
+            row: 0,
+        },
+        GraphNode {
+            id: "b",
+            label: "b",
+            kind: NodeKind::Signal,
+            column: 0,
+            row: 1,
+        },
+        GraphNode {
+            id: "even",
+            label: "a is even",
+            kind: NodeKind::Memo,
+            column: 1,
+            row: 0,
+        },
+        GraphNode {
+            id: "sum",
+            label: "a + b",
+            kind: NodeKind::Memo,
+            column: 1,
+            row: 1,
+        },
+        GraphNode {
+            id: "log_even",
+            label: "log even/odd",
+            kind: NodeKind::Effect,
+            column: 2,
+            row: 0,
+        },
+        GraphNode {
+            id: "log_sum",
+            label: "log a + b",
+            kind: NodeKind::Effect,
+            column: 2,
+            row: 1,
+        },
+    ];
+    let edges = vec![
+        ("a", "even"),
+        ("a", "sum"),
+        ("b", "sum"),
+        ("even", "log_even"),
+        ("sum", "log_sum"),
+    ];
+
+    // A diamond for stepping through: `a × 2` and `a × 3` both read `a`, and the sum reads both of them. When `a`
+    // changes, the sum runs once, after both, and never sees a new `a × 2` next to an old `a × 3` (a "glitch"). These
+    // report after they computed, with what they computed, so the steps come out in the order the values were done.
+    let diamond = use_tracker(cx);
+    let (x, set_x) = create_signal(cx, 1);
+    let double = create_memo(cx, move |_| {
+        let double = x.get() * 2;
+        diamond.ran_with("double", format!("a × 2 = {double}"));
+        double
+    });
+    let triple = create_memo(cx, move |_| {
+        let triple = x.get() * 3;
+        diamond.ran_with("triple", format!("a × 3 = {triple}"));
+        triple
+    });
+    let total = create_memo(cx, move |_| {
+        let (double, triple) = (double.get(), triple.get());
+        diamond.ran_with(
+            "total",
+            format!("a × 2 + a × 3 = {double} + {triple} = {}", double + triple),
+        );
+        double + triple
+    });
+    create_effect(cx, move |_| {
+        let total = total.get();
+        diamond.ran_with("log_total", format!("the effect logs {total}"));
+        info!("a × 2 + a × 3 is {total}");
+    });
+    let diamond_nodes = vec![
+        GraphNode {
+            id: "a",
+            label: "a",
+            kind: NodeKind::Signal,
+            column: 0,
+            row: 1,
+        },
+        GraphNode {
+            id: "double",
+            label: "a × 2",
+            kind: NodeKind::Memo,
+            column: 1,
+            row: 0,
+        },
+        GraphNode {
+            id: "triple",
+            label: "a × 3",
+            kind: NodeKind::Memo,
+            column: 1,
+            row: 2,
+        },
+        GraphNode {
+            id: "total",
+            label: "a × 2 + a × 3",
+            kind: NodeKind::Memo,
+            column: 2,
+            row: 1,
+        },
+        GraphNode {
+            id: "log_total",
+            label: "log the sum",
+            kind: NodeKind::Effect,
+            column: 3,
+            row: 1,
+        },
+    ];
+    let diamond_edges = vec![
+        ("a", "double"),
+        ("a", "triple"),
+        ("double", "total"),
+        ("triple", "total"),
+        ("total", "log_total"),
+    ];
+    // How many steps of the update are shown. A new update starts over at its first step, the write.
+    let steps = create_rw_signal(cx, 1);
+
+    // A button that writes to a signal. The update starts here, so the tracker hears about it first.
+    let button = move |label: &'static str, id: &'static str, change: Box<dyn Fn()>| {
+        view! { cx,
+            <button
+                class="px-3 py-1 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| {
+                    tracker.update(id);
+                    change();
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Reactive graph"</h1>
+            <p class="mt-2">{move || format!("a = {}, b = {}, a + b = {}", a.get(), b.get(), sum.get())}</p>
+            <div class="flex flex-wrap gap-2 mt-4">
+                {button("a + 1", "a", Box::new(move || set_a.update(|a| *a += 1)))}
+                // `a is even` runs again, but comes out the same. A memo that didn't change doesn't wake up what reads it,
+                // so "log even/odd" stays dark.
+                {button("a + 2", "a", Box::new(move || set_a.update(|a| *a += 2)))}
+                {button("b + 1", "b", Box::new(move || set_b.update(|b| *b += 1)))}
+                // Setting a signal notifies whoever reads it, even with the value it already had. The memos run, but
+                // neither changes, so no effect runs.
+                {button("a = a", "a", Box::new(move || set_a.update(|_| ())))}
+            </div>
+            <div class="mt-6">
+                <ReactiveGraph tracker=tracker nodes=nodes edges=edges/>
+            </div>
+            <h2 class="mt-8 text-2xl">"Step through an update"</h2>
+            <p class="mt-2">
+                "Change " <code>"a"</code> ", then go through what ran, one step at a time. The update itself is over in an "
+                "instant, the steps replay it."
+            </p>
+            <button
+                class="px-3 py-1 mt-4 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| {
+                    diamond.update("a");
+                    set_x.update(|x| *x += 1);
+                    steps.set(1);
+                }
+            >
+                "a + 1"
+            </button>
+            <div class="mt-4">
+                <ReactiveGraph tracker=diamond nodes=diamond_nodes edges=diamond_edges steps=steps/>
+            </div>
+            <div class="mt-4">
+                <UpdateSteps tracker=diamond steps=steps/>
+            </div>
+        </main>
+    }
+}
+
+/// Batching example (several signal updates at once, with and without `batch`)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn BatchExample(cx: Scope) -> impl IntoView {
+    // Both sides are the same: three signals, and one effect that reads all three. Each side counts how often its effect
+    // ran and keeps what it saw.
+    let side = move |title: &'static str, batched: bool| {
+        let (first, set_first) = create_signal(cx, "Ada");
+        let (last, set_last) = create_signal(cx, "Lovelace");
+        let (age, set_age) = create_signal(cx, 36);
+        let (runs, set_runs) = create_signal(cx, 0);
+        let (seen, set_seen) = create_signal(cx, Vec::<String>::new());
+        create_effect(cx, move |_| {
+            let person = format!("{} {}, {}", first.get(), last.get(), age.get());
+            // `update` doesn't subscribe the effect to what it updates, so these don't make the effect run again.
+            set_runs.update(|runs| *runs += 1);
+            set_seen.update(|seen| seen.push(person));
+        });
+
+        let switch = move |_| {
+            let (new_first, new_last, new_age) = if first.get_untracked() == "Ada" {
+                ("Grace", "Hopper", 85)
+            } else {
+                ("Ada", "Lovelace", 36)
+            };
+            let change = move || {
+                set_first(new_first);
+                set_last(new_last);
+                set_age(new_age);
+            };
+            // Without `batch` each `set` runs the effect right away, three times for one change, and the first two runs see
+            // a person that never existed, like "Grace Lovelace, 36". Inside `batch` the effects wait until the closure is
+            // done, then run once with all three values new.
+            if batched {
+                cx.batch(change);
+            } else {
+                change();
+            }
+        };
+
+        view! { cx,
+            <section class="flex-1 p-4 border rounded">
+                <h2 class="text-xl">{title}</h2>
+                <p class="mt-2 text-4xl font-bold">{runs}</p>
+                <p class="text-sm text-gray-500">"effect runs, the first one included"</p>
+                <button class="px-3 py-1 mt-4 text-white bg-blue-700 rounded shadow" on:click=switch>
+                    "Switch person"
+                </button>
+                <h3 class="mt-4">"What the effect saw"</h3>
+                // Newest first, the last few.
+                <ol class="text-sm">
+                    {move || {
+                        seen.with(|seen| {
+                            seen.iter().rev().take(6).map(|person| view! { cx, <li>{person.clone()}</li> }).collect_view(cx)
+                        })
+                    }}
+                </ol>
+            </section>
+        }
+    };
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Batching"</h1>
+            <p class="mt-2">
+                "Switching the person sets three signals. Click it on both sides and compare how often the effect ran."
+            </p>
+            <div class="flex flex-col gap-4 mt-4 sm:flex-row">
+                {side("One by one", false)}
+                {side("In a batch", true)}
+            </div>
+        </main>
+    }
+}
+
+/// How many numbers the lazy example generates.
+#[cfg(feature = "examples-advanced")]
+const DATASET_SIZE: usize = 500_000;
+
+/// Lazy initialization example (costly state built on first use, once)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn LazyExample(cx: Scope) -> impl IntoView {
+    let (log, set_log) = create_signal(cx, Vec::<String>::new());
+    let (builds, set_builds) = create_signal(cx, 0);
+
+    // Half a million numbers, from a simple pseudo random generator so they are the same every time. Nothing is built
+    // here yet: `use_lazy` keeps the function and runs it when the dataset is first used, in `DatasetSummary`.
+    let dataset = use_lazy(cx, move || {
+        let mut state: u32 = 12345;
+        let numbers: Vec<u32> = (0..DATASET_SIZE)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) % 1000
+            })
+            .collect();
+        info!("Built the dataset");
+        // Logged a moment later: this runs while the summary is being rendered, writing signals the page shows right
+        // in the middle of that is asking for trouble.
+        queue_microtask(move || {
+            set_builds.update(|builds| *builds += 1);
+            set_log.update(|log| log.push(format!("Built the dataset: {DATASET_SIZE} numbers")));
+        });
+        numbers
+    });
+
+    let (shown, set_shown) = create_signal(cx, false);
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Lazy initialization"</h1>
+            <p class="mt-2">
+                "The dataset isn't built when the page loads, only when the summary is shown for the first time. Hide it "
+                "and show it again: the summary is worked out again, the dataset is not built again."
+            </p>
+            <button
+                class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow"
+                on:click=move |_| {
+                    set_log.update(|log| {
+                        log.push(if shown.get_untracked() { "Summary hidden" } else { "Summary shown" }.to_string())
+                    });
+                    set_shown.update(|shown| *shown = !*shown);
+                }
+            >
+                {move || if shown.get() { "Hide the summary" } else { "Show the summary" }}
+            </button>
+            <p class="mt-2">"Times the dataset was built: " <strong>{builds}</strong></p>
+            <Show when=move || shown.get() fallback=|_| ()>
+                <DatasetSummary dataset=dataset/>
+            </Show>
+            <h2 class="mt-6 text-xl">"Log"</h2>
+            <ul class="font-mono text-sm">
+                {move || log.get().into_iter().map(|line| view! { cx, <li>{line}</li> }).collect_view(cx)}
+            </ul>
+        </main>
+    }
+}
+
+/// The smallest, largest and average number of the lazy example's dataset.
+#[cfg(feature = "examples-advanced")]
+#[component]
+fn DatasetSummary(cx: Scope, dataset: Lazy<Vec<u32>>) -> impl IntoView {
+    // The first time the summary is shown, this is where the dataset gets built.
+    let (min, max, mean) = dataset.with(|numbers| {
+        let min = numbers.iter().min().copied().unwrap_or_default();
+        let max = numbers.iter().max().copied().unwrap_or_default();
+        let sum: u64 = numbers.iter().map(|&number| u64::from(number)).sum();
+        (min, max, sum as f64 / numbers.len().max(1) as f64)
+    });
+
+    view! { cx,
+        <dl class="grid grid-cols-2 gap-1 mt-4 w-fit">
+            <dt>"Smallest"</dt>
+            <dd>{min}</dd>
+            <dt>"Largest"</dt>
+            <dd>{max}</dd>
+            <dt>"Average"</dt>
+            <dd>{format!("{mean:.2}")}</dd>
+        </dl>
+    }
+}
+
+/// Timings example (app-wide debounce and throttle defaults, changed live)
+#[cfg(feature = "examples-advanced")]
+#[component]
+pub fn TimingsExample(cx: Scope) -> impl IntoView {
+    // The defaults live in context (see debounce.rs), every hook below that passes `None` uses them. Changing them here
+    // changes them for the whole app, e.g. the search at /fetch too.
+    let timings = use_timings(cx);
+    let millis = |duration: RwSignal<Duration>| move || duration.get().as_millis().to_string();
+    let set_millis = |duration: RwSignal<Duration>| {
+        move |event| {
+            if let Ok(millis) = event_target_value(&event).parse() {
+                duration.set(Duration::from_millis(millis));
+            }
+        }
+    };
+
+    let (text, set_text) = create_signal(cx, String::new());
+    let debounced = use_debounced(cx, move || text.get(), None);
+    let (pointer, set_pointer) = create_signal(cx, (0, 0));
+    let throttled = use_throttled(cx, move || pointer.get(), None);
+
+    // How often each value changed, to compare the raw ones with what the hooks let through.
+    let typed = count_changes(cx, move || text.track());
+    let settled = count_changes(cx, move || debounced.track());
+    let moved = count_changes(cx, move || pointer.track());
+    let passed = count_changes(cx, move || throttled.track());
+
+    view! { cx,
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Timings"</h1>
+            <p class="mt-2">
+                "The debounce delay and the throttle interval every page uses unless it brings its own. Changes apply to "
+                "the next wait, here and elsewhere: the search at /fetch waits as long as set here, the username check at "
+                "/form passes its own 400ms and doesn't care."
+            </p>
+            <div class="grid grid-cols-[auto_1fr_auto] gap-2 items-center mt-4">
+                <label for="debounce">"Debounce"</label>
+                <input
+                    id="debounce"
+                    type="range"
+                    min="0"
+                    max="2000"
+                    step="50"
+                    prop:value=millis(timings.debounce)
+                    on:input=set_millis(timings.debounce)
+                />
+                <span>{millis(timings.debounce)} "ms"</span>
+                <label for="throttle">"Throttle"</label>
+                <input
+                    id="throttle"
+                    type="range"
+                    min="0"
+                    max="1000"
+                    step="50"
+                    prop:value=millis(timings.throttle)
+                    on:input=set_millis(timings.throttle)
+                />
+                <span>{millis(timings.throttle)} "ms"</span>
+            </div>
+            <button
+                class="mt-2 text-blue-700 underline"
+                on:click=move |_| {
+                    timings.debounce.set(DEFAULT_DEBOUNCE);
+                    timings.throttle.set(DEFAULT_THROTTLE);
+                }
+            >
+                "Back to the defaults"
+            </button>
+
+            <h2 class="mt-6 text-xl">"Debounced"</h2>
+            <input
+                class="w-full p-2 mt-2 border rounded"
+                placeholder="Type something"
+                prop:value=text
+                on:input=move |event| set_text(event_target_value(&event))
+            />
+            <p class="mt-2">"Debounced: " <strong>{debounced}</strong></p>
+            <p class="text-sm text-gray-500">
+                {move || format!("{} changes typed, {} let through", typed.get(), settled.get())}
+            </p>
+
+            <h2 class="mt-6 text-xl">"Throttled"</h2>
+            <div
+                class="h-40 mt-2 bg-gray-100 border rounded touch-none dark:bg-gray-800"
+                on:pointermove=move |event| set_pointer((event.offset_x(), event.offset_y()))
+            >
+                <p class="p-2 text-sm text-gray-500">"Move the pointer around in here"</p>
+            </div>
+            <p class="mt-2">"Throttled: " <strong>{move || format!("{:?}", throttled.get())}</strong></p>
+            <p class="text-sm text-gray-500">
+                {move || format!("{} moves, {} let through", moved.get(), passed.get())}
+            </p>
+        </main>
+    }
+}
+
+/// How often the signals that `read` reads have changed since now.
+#[cfg(feature = "examples-advanced")]
+fn count_changes(cx: Scope, read: impl Fn() + 'static) -> ReadSignal<u32> {
+    let (changes, set_changes) = create_signal(cx, 0);
+    create_effect(cx, move |previous: Option<()>| {
+        read();
+        // The first run only subscribes, nothing has changed yet.
+        if previous.is_some() {
+            set_changes.update(|changes| *changes += 1);
+        }
+    });
+    changes
+}
+
+#[cfg(all(test, feature = "examples-advanced"))]
+mod tests {
+    use super::*;
+
+    fn amiibo(name: &str, head: &str, tail: &str) -> Amiibo {
+        Amiibo {
+            amiibo_series: "Super Smash Bros.".to_string(),
+            character: "Mario".to_string(),
+            game_series: "Super Mario".to_string(),
+            head: head.to_string(),
+            tail: tail.to_string(),
+            image: String::new(),
+            name: name.to_string(),
+        }
+    }
+
+    // Every Mario amiibo has the same `head`, which is why the keyed list can't use `head` alone.
+    #[test]
+    fn amiibo_id_tells_apart_amiibo_with_the_same_head() {
+        let marios = [
+            amiibo("Mario", "00000000", "00340102"),
+            amiibo("Mario - Gold Edition", "00000000", "003c0102"),
+            amiibo("Mario - Silver Edition", "00000000", "003d0102"),
+            amiibo("Dr. Mario", "00000000", "00660102"),
+        ];
+        assert!(marios.windows(2).all(|pair| pair[0].head == pair[1].head));
+        let ids: std::collections::HashSet<_> = marios.iter().map(amiibo_id).collect();
+        assert_eq!(ids.len(), marios.len());
+    }
+
+    #[test]
+    fn amiibo_id_is_the_same_for_the_same_amiibo() {
+        let fetched = amiibo("Mario", "00000000", "00340102");
+        let fetched_again = amiibo("Mario (renamed)", "00000000", "00340102");
+        assert_eq!(amiibo_id(&fetched), amiibo_id(&fetched_again));
+    }
+
+    // As the Amiibo API sends it, with fields we don't read.
+    #[test]
+    fn amiibo_reads_the_camel_case_of_the_api() {
+        let json = r#"{
+            "amiiboSeries": "Super Smash Bros.",
+            "character": "Mario",
+            "gameSeries": "Super Mario",
+            "head": "00000000",
+            "image": "https://raw.githubusercontent.com/N3evin/AmiiboAPI/master/images/icon_00000000-00000002.png",
+            "name": "Mario",
+            "release": {"eu": "2014-11-28", "jp": "2014-12-06", "na": "2014-11-21"},
+            "tail": "00000002",
+            "type": "Figure"
+        }"#;
+        let parsed: Amiibo = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.amiibo_series, "Super Smash Bros.");
+        assert_eq!(parsed.game_series, "Super Mario");
+        assert_eq!(parsed.name, "Mario");
+    }
+
+    #[test]
+    fn amiibo_reads_snake_case_too() {
+        let json = r#"{
+            "amiibo_series": "Super Smash Bros.",
+            "character": "Mario",
+            "game_series": "Super Mario",
+            "head": "00000000",
+            "image": "",
+            "name": "Mario",
+            "tail": "00000002"
+        }"#;
+        let parsed: Amiibo = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.amiibo_series, "Super Smash Bros.");
+        assert_eq!(parsed.game_series, "Super Mario");
+    }
+
+    #[test]
+    fn empty_text_has_nothing_to_count() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(line_count(""), 0);
+        assert_eq!(character_count(""), 0);
+        assert_eq!(character_count_without_spaces(""), 0);
+        assert_eq!(word_count("   \n\t "), 0);
+    }
+
+    #[test]
+    fn runs_of_spaces_separate_words_once() {
+        assert_eq!(word_count("one   two \t three"), 3);
+        assert_eq!(word_count("  leading and trailing  "), 3);
+        assert_eq!(character_count_without_spaces("a  b"), 2);
+    }
+
+    #[test]
+    fn windows_line_breaks_count_like_unix_ones() {
+        let text = "first line\r\nsecond line\r\n";
+        assert_eq!(line_count(text), 3);
+        assert_eq!(word_count(text), 4);
+        // "\r\n" is a single grapheme, and it is whitespace.
+        assert_eq!(character_count_without_spaces(text), 19);
+    }
+
+    #[test]
+    fn characters_are_what_people_see() {
+        // "e" plus a combining acute accent, and a thumb with a skin tone.
+        let text = "cafe\u{301} 👍🏽";
+        assert_eq!(text.chars().count(), 8);
+        assert_eq!(character_count(text), 6);
+        assert_eq!(character_count_without_spaces(text), 5);
+        assert_eq!(word_count(text), 2);
+    }
+
+    #[test]
+    fn reading_time_rounds_up_to_whole_minutes() {
+        assert_eq!(reading_time(0), "-");
+        assert_eq!(reading_time(1), "< 1 min");
+        assert_eq!(reading_time(199), "< 1 min");
+        assert_eq!(reading_time(200), "1 min");
+        assert_eq!(reading_time(201), "2 min");
+        assert_eq!(reading_time(400), "2 min");
+        assert_eq!(reading_time(401), "3 min");
+    }
+
+    // `Fetch` itself needs the contexts of the whole `App` and a server that reaches the Amiibo API. Its fallback doesn't:
+    // a failed fetch, rendered as a `Result`, ends up in the `ErrorBoundary`, which shows `FetchFailed`.
+    #[cfg(feature = "ssr")]
+    fn render_failed_fetch(busy: bool) -> String {
+        leptos::ssr::render_to_string(move |cx| {
+            let result: Result<Vec<Amiibo>, FetchError> = Err(FetchError::Status(502));
+            view! { cx,
+                <ErrorBoundary fallback=move |cx, errors| view! { cx,
+                    <FetchFailed errors=errors busy=busy retry=|| ()/>
+                }>
+                    {result.map(|list| list.len())}
+                </ErrorBoundary>
+            }
+        })
+        .to_string()
+    }
+
+    // Whether the `<button>` tag has the `disabled` attribute. Its classes mention `disabled:` too, those don't count.
+    #[cfg(feature = "ssr")]
+    fn retry_disabled(html: &str) -> bool {
+        let button = &html[html.find("<button").expect("a button")..];
+        button[..button.find('>').expect("a whole tag")]
+            .split_whitespace()
+            .any(|attribute| attribute == "disabled" || attribute.starts_with("disabled="))
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn a_failed_fetch_shows_the_error_and_a_retry_button() {
+        let html = render_failed_fetch(false);
+        assert!(html.contains("role=\"alert\""), "{html}");
+        assert!(
+            html.contains(">Server responded with status 502</p>"),
+            "{html}"
+        );
+        assert!(html.contains(">Retry</button>"), "{html}");
+        assert!(!retry_disabled(&html), "{html}");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn retry_waits_for_the_fetch_that_is_running() {
+        let html = render_failed_fetch(true);
+        assert!(retry_disabled(&html), "{html}");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::debounce::use_timings;
+use crate::favorites::use_favorites;
+use crate::flags::{use_feature_flags, DEFAULT_FLAGS};
+use crate::locale::{use_locale, Locale};
+use crate::preferences::{use_preferences, REFRESH_INTERVALS};
+use crate::theme::{use_theme, ThemePreference};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// All the settings in one JSON file, to keep a copy or to take them to another browser: the theme,
+// the locale, the timings, the other preferences, the feature flags and the favorites. Each of them
+// lives in its own context and is saved by it, a backup is only a snapshot of all of them.
+//
+// An imported file is checked completely before anything is applied. A file that isn't a backup, is
+// from a newer version or has a value we don't know (a theme, a locale, a flag, an interval out of
+// range) changes nothing, and the error says which value it was.
+
+/// The version of the file format. Bump it when a field changes its meaning.
+const VERSION: u32 = 1;
+/// The range of the sliders at /settings and /timings.
+const MAX_DEBOUNCE_MS: u64 = 2000;
+const MAX_THROTTLE_MS: u64 = 1000;
+
+/// The settings as they are written to the file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Backup {
+    version: u32,
+    theme: String,
+    locale: String,
+    debounce_ms: u64,
+    throttle_ms: u64,
+    /// `None` when auto-refresh is off.
+    refresh_seconds: Option<u64>,
+    reduce_motion: bool,
+    // A `BTreeMap` so the flags are written in the same order every time.
+    feature_flags: BTreeMap<String, bool>,
+    favorites: Vec<String>,
+}
+
+impl Backup {
+    /// The settings as they are right now.
+    pub fn collect(cx: Scope) -> Self {
+        let timings = use_timings(cx);
+        let preferences = use_preferences(cx);
+        Backup {
+            version: VERSION,
+            theme: use_theme(cx).preference.get_untracked().tag().to_string(),
+            locale: use_locale(cx).get().tag().to_string(),
+            debounce_ms: millis(timings.debounce.get_untracked()),
+            throttle_ms: millis(timings.throttle.get_untracked()),
+            refresh_seconds: preferences
+                .refresh_interval
+                .get_untracked()
+                .map(|interval| interval.as_secs()),
+            reduce_motion: preferences.reduce_motion.get_untracked(),
+            feature_flags: use_feature_flags(cx).all().into_iter().collect(),
+            favorites: use_favorites(cx).paths(),
+        }
+    }
+
+    /// Reads and checks a backup file. The error is a message for the user.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let backup: Backup = serde_json::from_str(json)
+            .map_err(|error| format!("This is not a settings file ({error})"))?;
+        if backup.version > VERSION {
+            return Err(format!(
+                "The file is from a newer version of the app (format {}, this one reads up to {VERSION})",
+                backup.version
+            ));
+        }
+        if ThemePreference::from_tag(&backup.theme).is_none() {
+            return Err(format!("Unknown theme \"{}\"", backup.theme));
+        }
+        if Locale::from_tag(&backup.locale).is_none() {
+            return Err(format!("Unknown locale \"{}\"", backup.locale));
+        }
+        if backup.debounce_ms > MAX_DEBOUNCE_MS {
+            return Err(format!(
+                "The debounce delay can be at most {MAX_DEBOUNCE_MS}ms"
+            ));
+        }
+        if backup.throttle_ms > MAX_THROTTLE_MS {
+            return Err(format!(
+                "The throttle interval can be at most {MAX_THROTTLE_MS}ms"
+            ));
+        }
+        if !REFRESH_INTERVALS
+            .iter()
+            .any(|(seconds, _)| *seconds == backup.refresh_seconds)
+        {
+            return Err("Unknown auto-refresh interval".to_string());
+        }
+        if let Some(flag) = backup
+            .feature_flags
+            .keys()
+            .find(|flag| !DEFAULT_FLAGS.iter().any(|(known, _, _)| known == flag))
+        {
+            return Err(format!("Unknown feature flag \"{flag}\""));
+        }
+        if let Some(path) = backup.favorites.iter().find(|path| !path.starts_with('/')) {
+            return Err(format!("\"{path}\" is not the path of a page"));
+        }
+        Ok(backup)
+    }
+
+    /// Applies the settings. Every context saves its part, just as if it had been changed by hand.
+    pub fn apply(self, cx: Scope) {
+        let timings = use_timings(cx);
+        let preferences = use_preferences(cx);
+        let flags = use_feature_flags(cx);
+        // `parse` checked these, the `if let`s never fail for a parsed backup.
+        if let Some(theme) = ThemePreference::from_tag(&self.theme) {
+            use_theme(cx).preference.set(theme);
+        }
+        if let Some(locale) = Locale::from_tag(&self.locale) {
+            use_locale(cx).set(locale);
+        }
+        timings
+            .debounce
+            .set(Duration::from_millis(self.debounce_ms));
+        timings
+            .throttle
+            .set(Duration::from_millis(self.throttle_ms));
+        preferences
+            .refresh_interval
+            .set(self.refresh_seconds.map(Duration::from_secs));
+        preferences.reduce_motion.set(self.reduce_motion);
+        for (flag, enabled) in &self.feature_flags {
+            flags.set(flag, *enabled);
+        }
+        use_favorites(cx).replace(self.favorites);
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn valid() -> Value {
+        json!({
+            "version": VERSION,
+            "theme": "dark",
+            "locale": "de-DE",
+            "debounce_ms": 300,
+            "throttle_ms": 100,
+            "refresh_seconds": 60,
+            "reduce_motion": true,
+            "feature_flags": { "title_badge": false },
+            "favorites": ["/fetch", "/timings"],
+        })
+    }
+
+    /// `valid()` with `field` set to `value`.
+    fn with(field: &str, value: Value) -> String {
+        let mut backup = valid();
+        backup[field] = value;
+        backup.to_string()
+    }
+
+    /// `valid()` without `field`.
+    fn without(field: &str) -> String {
+        let mut backup = valid();
+        backup.as_object_mut().unwrap().remove(field);
+        backup.to_string()
+    }
+
+    #[test]
+    fn parse_reads_a_valid_backup() {
+        let backup = Backup::parse(&valid().to_string()).unwrap();
+        assert_eq!(backup.theme, "dark");
+        assert_eq!(backup.refresh_seconds, Some(60));
+        assert_eq!(backup.feature_flags.get("title_badge"), Some(&false));
+    }
+
+    #[test]
+    fn parse_reads_back_what_to_json_wrote() {
+        let backup = Backup::parse(&valid().to_string()).unwrap();
+        assert_eq!(Backup::parse(&backup.to_json()), Ok(backup));
+    }
+
+    #[test]
+    fn parse_accepts_the_limits() {
+        assert!(Backup::parse(&with("debounce_ms", json!(MAX_DEBOUNCE_MS))).is_ok());
+        assert!(Backup::parse(&with("throttle_ms", json!(MAX_THROTTLE_MS))).is_ok());
+        assert!(Backup::parse(&with("refresh_seconds", Value::Null)).is_ok());
+        assert!(Backup::parse(&with("favorites", json!([]))).is_ok());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_backups() {
+        let cases = [
+            ("not JSON", "{".to_string(), "This is not a settings file"),
+            (
+                "not a backup",
+                json!({ "hello": "world" }).to_string(),
+                "This is not a settings file",
+            ),
+            (
+                "a missing field",
+                without("theme"),
+                "This is not a settings file",
+            ),
+            (
+                "a wrong type",
+                with("debounce_ms", json!("300")),
+                "This is not a settings file",
+            ),
+            (
+                "a newer version",
+                with("version", json!(VERSION + 1)),
+                "newer version",
+            ),
+            (
+                "an unknown theme",
+                with("theme", json!("sepia")),
+                "Unknown theme \"sepia\"",
+            ),
+            (
+                "an unknown locale",
+                with("locale", json!("xx-XX")),
+                "Unknown locale \"xx-XX\"",
+            ),
+            (
+                "a debounce out of range",
+                with("debounce_ms", json!(MAX_DEBOUNCE_MS + 1)),
+                "debounce delay",
+            ),
+            (
+                "a throttle out of range",
+                with("throttle_ms", json!(MAX_THROTTLE_MS + 1)),
+                "throttle interval",
+            ),
+            (
+                "an unknown refresh interval",
+                with("refresh_seconds", json!(45)),
+                "auto-refresh interval",
+            ),
+            (
+                "an unknown flag",
+                with("feature_flags", json!({ "time_travel": true })),
+                "Unknown feature flag \"time_travel\"",
+            ),
+            (
+                "a favorite that is not a path",
+                with("favorites", json!(["fetch"])),
+                "\"fetch\" is not the path of a page",
+            ),
+        ];
+        for (case, json, expected) in cases {
+            match Backup::parse(&json) {
+                Ok(_) => panic!("{case} was accepted"),
+                Err(error) => assert!(error.contains(expected), "{case}: {error}"),
+            }
+        }
+    }
+}
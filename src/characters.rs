@@ -0,0 +1,136 @@
+use crate::versioned::{merge_field, Merged, Versioned};
+use serde::{Deserialize, Serialize};
+
+// The rows of the data table example: Nintendo characters with their series and the year they first
+// appeared. The server keeps them (`CharacterStore` in api.rs), starting out with `CHARACTERS`, and
+// the table reads and edits them through server functions. Each row has a version, see versioned.rs.
+
+const CHARACTERS: [(&str, &str, u16); 24] = [
+    ("Mario", "Mario", 1981),
+    ("Donkey Kong", "Donkey Kong", 1981),
+    ("Luigi", "Mario", 1983),
+    ("Princess Peach", "Mario", 1985),
+    ("Bowser", "Mario", 1985),
+    ("Toad", "Mario", 1985),
+    ("Link", "Zelda", 1986),
+    ("Princess Zelda", "Zelda", 1986),
+    ("Ganondorf", "Zelda", 1986),
+    ("Samus Aran", "Metroid", 1986),
+    ("Ridley", "Metroid", 1986),
+    ("Kirby", "Kirby", 1992),
+    ("King Dedede", "Kirby", 1992),
+    ("Meta Knight", "Kirby", 1993),
+    ("Yoshi", "Mario", 1990),
+    ("Wario", "Mario", 1992),
+    ("Diddy Kong", "Donkey Kong", 1994),
+    ("Dixie Kong", "Donkey Kong", 1995),
+    ("Waluigi", "Mario", 2000),
+    ("Rosalina", "Mario", 2007),
+    ("Midna", "Zelda", 2006),
+    ("Impa", "Zelda", 1987),
+    ("Dark Samus", "Metroid", 2004),
+    ("Bandana Waddle Dee", "Kirby", 2011),
+];
+
+/// One row of the data table.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Character {
+    pub name: String,
+    pub series: String,
+    /// The year the character first appeared.
+    pub year: u16,
+}
+
+impl Character {
+    /// Merges this edit of `base` with `theirs`, which was saved in the meantime (see `merge_field`).
+    /// Where both changed a field, this edit's value is kept and the field is listed in the second
+    /// half of the result, so the user can be told and look at it again.
+    pub fn merge(&self, base: &Self, theirs: &Self) -> (Self, Vec<&'static str>) {
+        let mut clashes = Vec::new();
+        let mut pick = |label: &'static str, merged| match merged {
+            Merged::Agreed(value) => value,
+            Merged::Clash { mine, .. } => {
+                clashes.push(label);
+                mine
+            }
+        };
+        let name = pick("name", merge_field(&base.name, &self.name, &theirs.name));
+        let series = pick(
+            "series",
+            merge_field(&base.series, &self.series, &theirs.series),
+        );
+        let year = match merge_field(&base.year, &self.year, &theirs.year) {
+            Merged::Agreed(year) => year,
+            Merged::Clash { mine, .. } => {
+                clashes.push("first appeared");
+                mine
+            }
+        };
+        (Self { name, series, year }, clashes)
+    }
+}
+
+/// The rows everybody starts with, all at version 1.
+pub fn initial_characters() -> Vec<Versioned<Character>> {
+    CHARACTERS
+        .into_iter()
+        .enumerate()
+        .map(|(id, (name, series, year))| Versioned {
+            id,
+            version: 1,
+            value: Character {
+                name: name.to_string(),
+                series: series.to_string(),
+                year,
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(name: &str, series: &str, year: u16) -> Character {
+        Character {
+            name: name.to_string(),
+            series: series.to_string(),
+            year,
+        }
+    }
+
+    #[test]
+    fn keeps_the_changes_of_both_sides() {
+        let base = character("Toad", "Mario", 1985);
+        let mine = character("Captain Toad", "Mario", 1985);
+        let theirs = character("Toad", "Mario", 1986);
+        assert_eq!(
+            mine.merge(&base, &theirs),
+            (character("Captain Toad", "Mario", 1986), vec![])
+        );
+    }
+
+    #[test]
+    fn keeps_mine_where_both_changed_a_field() {
+        let base = character("Toad", "Mario", 1985);
+        let mine = character("Captain Toad", "Mario", 1984);
+        let theirs = character("Toadsworth", "Mario", 1986);
+        assert_eq!(
+            mine.merge(&base, &theirs),
+            (
+                character("Captain Toad", "Mario", 1984),
+                vec!["name", "first appeared"]
+            )
+        );
+    }
+
+    #[test]
+    fn every_initial_row_has_its_own_id() {
+        let rows = initial_characters();
+        assert_eq!(rows.len(), CHARACTERS.len());
+        assert!(rows
+            .iter()
+            .enumerate()
+            .all(|(id, row)| row.id == id && row.version == 1));
+    }
+}
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+// A circuit breaker for a flaky API. While requests succeed the circuit is "closed" and failed requests
+// are retried. After `FAILURE_THRESHOLD` failures in a row it "opens": we stop sending requests for
+// `COOLDOWN` so we don't hammer a service that is down. When the cool-down is over it is "half open",
+// the next request is a trial: success closes the circuit again, failure opens it for another round.
+//
+// The transitions are plain functions of the current state and the time, so they are easy to follow
+// (and to test) without any signals or timers involved. Times are milliseconds, as returned by `Date.now()`.
+
+/// Consecutive failures after which the circuit opens.
+pub const FAILURE_THRESHOLD: u32 = 3;
+/// How long the circuit stays open.
+pub const COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Breaker {
+    /// Requests go through. `failures` counts the failures in a row.
+    Closed { failures: u32 },
+    /// No requests until `until`.
+    Open { until: f64 },
+    /// The cool-down is over, the next request decides.
+    HalfOpen,
+}
+
+impl Default for Breaker {
+    fn default() -> Self {
+        Breaker::Closed { failures: 0 }
+    }
+}
+
+impl Breaker {
+    pub fn on_success(self) -> Self {
+        Breaker::Closed { failures: 0 }
+    }
+
+    pub fn on_failure(self, now: f64) -> Self {
+        let open = Breaker::Open {
+            until: now + COOLDOWN.as_millis() as f64,
+        };
+        match self {
+            Breaker::Closed { failures } if failures + 1 < FAILURE_THRESHOLD => Breaker::Closed {
+                failures: failures + 1,
+            },
+            Breaker::Closed { .. } | Breaker::HalfOpen => open,
+            // A request that was already running when the circuit opened, the cool-down doesn't restart.
+            Breaker::Open { until } => Breaker::Open { until },
+        }
+    }
+
+    /// Moves an open circuit to half open once its cool-down has passed.
+    pub fn on_tick(self, now: f64) -> Self {
+        match self {
+            Breaker::Open { until } if now >= until => Breaker::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Whether a request may be sent right now.
+    pub fn allows_request(self) -> bool {
+        !matches!(self, Breaker::Open { .. })
+    }
+
+    /// Whole seconds left in the cool-down, `None` unless the circuit is open.
+    pub fn seconds_left(self, now: f64) -> Option<u64> {
+        match self {
+            Breaker::Open { until } => Some(((until - now).max(0.0) / 1000.0).ceil() as u64),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN_MS: f64 = COOLDOWN.as_millis() as f64;
+
+    fn fail(times: u32, now: f64) -> Breaker {
+        (0..times).fold(Breaker::default(), |breaker, _| breaker.on_failure(now))
+    }
+
+    #[test]
+    fn failures_below_the_threshold_keep_the_circuit_closed() {
+        let breaker = fail(FAILURE_THRESHOLD - 1, 0.0);
+        assert_eq!(
+            breaker,
+            Breaker::Closed {
+                failures: FAILURE_THRESHOLD - 1
+            }
+        );
+        assert!(breaker.allows_request());
+    }
+
+    #[test]
+    fn a_success_resets_the_failure_count() {
+        let breaker = fail(FAILURE_THRESHOLD - 1, 0.0).on_success();
+        assert_eq!(breaker, Breaker::default());
+        assert_eq!(breaker.on_failure(0.0), Breaker::Closed { failures: 1 });
+    }
+
+    #[test]
+    fn the_threshold_opens_the_circuit_for_the_cooldown() {
+        let breaker = fail(FAILURE_THRESHOLD, 1000.0);
+        assert_eq!(
+            breaker,
+            Breaker::Open {
+                until: 1000.0 + COOLDOWN_MS
+            }
+        );
+        assert!(!breaker.allows_request());
+        assert_eq!(breaker.seconds_left(1000.0), Some(COOLDOWN.as_secs()));
+        assert_eq!(breaker.seconds_left(1000.0 + COOLDOWN_MS - 1500.0), Some(2));
+    }
+
+    #[test]
+    fn a_failure_while_open_does_not_restart_the_cooldown() {
+        let breaker = fail(FAILURE_THRESHOLD, 0.0);
+        assert_eq!(breaker.on_failure(5000.0), breaker);
+    }
+
+    #[test]
+    fn the_circuit_is_half_open_once_the_cooldown_is_over() {
+        let breaker = fail(FAILURE_THRESHOLD, 0.0);
+        assert_eq!(breaker.on_tick(COOLDOWN_MS - 1.0), breaker);
+        let breaker = breaker.on_tick(COOLDOWN_MS);
+        assert_eq!(breaker, Breaker::HalfOpen);
+        assert!(breaker.allows_request());
+        assert_eq!(breaker.seconds_left(COOLDOWN_MS), None);
+    }
+
+    #[test]
+    fn the_trial_request_decides() {
+        assert_eq!(Breaker::HalfOpen.on_success(), Breaker::default());
+        assert_eq!(
+            Breaker::HalfOpen.on_failure(0.0),
+            Breaker::Open { until: COOLDOWN_MS }
+        );
+    }
+}
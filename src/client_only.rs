@@ -0,0 +1,45 @@
+use leptos::*;
+
+// Values that only make sense in the browser. With server-side rendering a page is rendered twice:
+// once on the server to HTML, then again in the browser, which "hydrates" that HTML by walking it and
+// expecting to find exactly what it would have rendered itself. Something like the current time or a
+// random number comes out different the second time, the HTML doesn't match and Leptos complains in
+// the console (or attaches event handlers to the wrong elements).
+//
+// The fix is to render the same placeholder on both sides and only fill in the real value once the
+// page is hydrated. Effects are the tool for that: they never run on the server, and in the browser
+// they run after hydration.
+
+/// `false` on the server and while hydrating, `true` from right after that.
+pub fn use_mounted(cx: Scope) -> ReadSignal<bool> {
+    let (mounted, set_mounted) = create_signal(cx, false);
+    create_effect(cx, move |_| set_mounted(true));
+    mounted
+}
+
+/// `None` on the server and while hydrating, then `Some(value())`. `value` is called once.
+pub fn use_client_only<T: 'static>(
+    cx: Scope,
+    value: impl Fn() -> T + 'static,
+) -> ReadSignal<Option<T>> {
+    let (client_value, set_client_value) = create_signal(cx, None);
+    // `untrack`, so signals read by `value` don't make the effect run again.
+    create_effect(cx, move |_| set_client_value(Some(cx.untrack(&value))));
+    client_value
+}
+
+/// Renders `fallback` on the server and while hydrating, the children once the page is hydrated.
+#[component]
+pub fn ClientOnly<F, IV>(cx: Scope, fallback: F, children: ChildrenFn) -> impl IntoView
+where
+    F: Fn(Scope) -> IV + 'static,
+    IV: IntoView,
+{
+    let mounted = use_mounted(cx);
+
+    view! { cx,
+        <Show when=move || mounted.get() fallback=fallback>
+            {children(cx)}
+        </Show>
+    }
+}
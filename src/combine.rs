@@ -0,0 +1,27 @@
+// Deriving one value from several signals. By hand that is a closure that reads each of them:
+//
+//     let status = Signal::derive(cx, move || {
+//         let value = value.get();
+//         let step = step.get();
+//         Status { can_increment: value + step <= MAX, ... }
+//     });
+//
+// `combine!` writes that closure for you. List the signals, and the expression after them sees their
+// current values under the same names:
+//
+//     let status = combine!(cx, |value, step| Status { can_increment: value + step <= MAX, ... });
+//
+// Like any derived signal it is recalculated whenever one of the listed signals changes. Each
+// signal is read once per calculation, with `.get()`, so the values must be `Clone`.
+
+/// Derives a `Signal` from several signals, see above.
+#[macro_export]
+macro_rules! combine {
+    ($cx:expr, |$($signal:ident),+ $(,)?| $body:expr) => {
+        ::leptos::Signal::derive($cx, move || {
+            // Shadows each signal with its current value for the body.
+            $(let $signal = ::leptos::SignalGet::get(&$signal);)+
+            $body
+        })
+    };
+}
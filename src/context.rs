@@ -0,0 +1,24 @@
+use leptos::*;
+use std::any::type_name;
+
+// Reading context without a bare `unwrap`. `use_context` returns `None` when nobody above the
+// component provided the value, usually because the provider was forgotten or the component is
+// rendered outside of it. Unwrapping that panics with "called `Option::unwrap()` on a `None`
+// value", which doesn't say what was missing or where it should have come from.
+
+/// Pulls a `T` out of context, panicking with a message that names the type and its `provider`
+/// when it's missing. Like `leptos::expect_context`, plus the hint where the value should come from.
+pub fn expect_context<T: Clone + 'static>(cx: Scope, provider: &str) -> T {
+    use_context::<T>(cx).unwrap_or_else(|| {
+        panic!(
+            "{} is not in context. It is provided by {provider}, is this component rendered outside of it?",
+            type_name::<T>()
+        )
+    })
+}
+
+/// Pulls a `T` out of context, or uses `T::default()` when nobody provided one. For values that
+/// have a sensible fallback, so the component also works on its own.
+pub fn use_context_or_default<T: Clone + Default + 'static>(cx: Scope) -> T {
+    use_context::<T>(cx).unwrap_or_default()
+}
@@ -0,0 +1,73 @@
+use std::panic;
+
+// What the user sees when the WASM panics. A panic in WASM doesn't unwind: the code stops right
+// where it is and every later call into it fails. The page stays up, but nothing reacts anymore,
+// it just looks frozen.
+//
+// So on top of logging the panic to the console (`console_error_panic_hook`) we put an overlay over
+// the page that says so. It is built with plain DOM calls: Leptos, like everything else in the
+// WASM, can't be trusted anymore at this point. For the same reason the Reload button uses an
+// `onclick` attribute (JavaScript) and not a Rust event handler.
+
+const OVERLAY_ID: &str = "crash-overlay";
+
+/// Replaces `console_error_panic_hook::set_once()`. Call once, before mounting the app.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        show_overlay(&info.to_string());
+    }));
+}
+
+fn show_overlay(message: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    // Only the first panic gets an overlay.
+    let Some(body) = document
+        .body()
+        .filter(|_| document.get_element_by_id(OVERLAY_ID).is_none())
+    else {
+        return;
+    };
+    let create = |tag: &str, style: &str, text: &str| {
+        let element = document.create_element(tag).ok()?;
+        element.set_attribute("style", style).ok()?;
+        // `set_text_content`, never HTML: the panic message may contain anything.
+        element.set_text_content(Some(text));
+        Some(element)
+    };
+
+    let overlay = create(
+        "div",
+        "position: fixed; inset: 0; z-index: 2147483647; display: flex; flex-direction: column; \
+         align-items: center; justify-content: center; gap: 1rem; padding: 1rem; \
+         background: rgba(17, 24, 39, 0.95); color: white; font-family: sans-serif; text-align: center",
+        "",
+    );
+    let title = create(
+        "h1",
+        "font-size: 1.5rem; font-weight: bold",
+        "The app crashed",
+    );
+    let details = create(
+        "pre",
+        "max-width: 40rem; white-space: pre-wrap; font-size: 0.875rem; color: #fca5a5",
+        message,
+    );
+    let reload = create(
+        "button",
+        "padding: 0.5rem 1rem; border-radius: 0.25rem; background: #1d4ed8; color: white",
+        "Reload",
+    );
+    let (Some(overlay), Some(title), Some(details), Some(reload)) =
+        (overlay, title, details, reload)
+    else {
+        return;
+    };
+    overlay.set_id(OVERLAY_ID);
+    _ = overlay.set_attribute("role", "alert");
+    _ = reload.set_attribute("onclick", "location.reload()");
+    _ = overlay.append_with_node_3(&title, &details, &reload);
+    _ = body.append_child(&overlay);
+}
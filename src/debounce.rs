@@ -0,0 +1,278 @@
+use crate::context::expect_context;
+use leptos::*;
+use std::time::Duration;
+
+// How long `use_debounced` waits and how often `use_throttled` lets a value through can be left to
+// the app: pass `None` instead of a duration and the hook uses the one in `Timings`, which `App`
+// provides. Those can be changed while the app runs (see the /timings page), every wait that starts
+// after the change uses the new value. A call that passes its own duration keeps it. So the duration
+// of the call wins, then the one in context, then the built-in default when nobody provided `Timings`.
+//
+// Changed timings are saved in localStorage. Like the locale, they are applied after the first frame,
+// the server renders the defaults.
+
+const STORAGE_KEY: &str = "timings";
+
+/// The debounce delay when neither the call nor the context says otherwise.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+/// The throttle interval when neither the call nor the context says otherwise.
+pub const DEFAULT_THROTTLE: Duration = Duration::from_millis(100);
+
+/// The app-wide defaults of `use_debounced` and `use_throttled`.
+#[derive(Copy, Clone)]
+pub struct Timings {
+    pub debounce: RwSignal<Duration>,
+    pub throttle: RwSignal<Duration>,
+}
+
+/// Provides the timings, starting at the defaults, to every component below `cx`.
+pub fn provide_timings(cx: Scope) -> Timings {
+    let timings = Timings {
+        debounce: create_rw_signal(cx, DEFAULT_DEBOUNCE),
+        throttle: create_rw_signal(cx, DEFAULT_THROTTLE),
+    };
+    provide_context(cx, timings);
+
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || {
+            if let Some((debounce, throttle)) = stored_timings() {
+                _ = timings.debounce.try_set(debounce);
+                _ = timings.throttle.try_set(throttle);
+            }
+        });
+    }
+    // `previous` is `None` on the first run, the defaults don't need saving. Saved as milliseconds,
+    // e.g. "300,100".
+    create_effect(cx, move |previous: Option<(Duration, Duration)>| {
+        let current = (timings.debounce.get(), timings.throttle.get());
+        if previous.is_some_and(|previous| previous != current) {
+            if let Ok(Some(storage)) = window().local_storage() {
+                let (debounce, throttle) = current;
+                let millis = format!("{},{}", debounce.as_millis(), throttle.as_millis());
+                _ = storage.set_item(STORAGE_KEY, &millis);
+            }
+        }
+        current
+    });
+
+    timings
+}
+
+/// Pulls the timings out of context, e.g. for a settings panel that changes them.
+pub fn use_timings(cx: Scope) -> Timings {
+    expect_context::<Timings>(cx, "App")
+}
+
+/// Returns a signal that follows `source`, but only after `source` has stopped changing for `delay`.
+/// Every change restarts the wait, so during a burst of changes (typing, dragging the window edge)
+/// only the final value comes through. `None` waits as long as the app-wide `Timings` say.
+pub fn use_debounced<T>(
+    cx: Scope,
+    source: impl Fn() -> T + 'static,
+    delay: impl Into<Option<Duration>>,
+) -> ReadSignal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let delay = resolve(
+        delay.into(),
+        use_context::<Timings>(cx).map(|timings| timings.debounce),
+        DEFAULT_DEBOUNCE,
+    );
+    let (debounced, set_debounced) = create_signal(cx, cx.untrack(&source));
+    let waiting = store_value(cx, Waiting::default());
+    // The timer of the value that is waiting to be applied, if any.
+    let timer = store_value(cx, None::<TimeoutHandle>);
+    let cancel = move || {
+        if let Some(timer) = timer.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+        waiting.update_value(Waiting::cancel);
+    };
+
+    create_effect(cx, move |_| {
+        let value = source();
+        cancel();
+        // Back to the current value (e.g. a letter typed and deleted again), nothing to wait for.
+        if debounced.with_untracked(|debounced| *debounced == value) {
+            return;
+        }
+        let Some(ticket) = waiting.try_update_value(|waiting| waiting.change(value)) else {
+            return;
+        };
+        let handle = set_timeout_with_handle(
+            move || {
+                if let Some(value) = waiting
+                    .try_update_value(|waiting| waiting.elapsed(ticket))
+                    .flatten()
+                {
+                    set_debounced(value);
+                }
+            },
+            delay(),
+        )
+        .ok();
+        timer.set_value(handle);
+    });
+    on_cleanup(cx, cancel);
+
+    debounced
+}
+
+/// The value `use_debounced` holds back, without the timers. Every change gets a ticket, and the timer of a change
+/// hands its ticket back when it runs out. Only the ticket of the latest change gets the value. Clearing the timers
+/// of older changes is only a saving, a timer that fires anyway gets nothing.
+struct Waiting<T> {
+    latest: u64,
+    value: Option<T>,
+}
+
+impl<T> Default for Waiting<T> {
+    fn default() -> Self {
+        Waiting {
+            latest: 0,
+            value: None,
+        }
+    }
+}
+
+impl<T> Waiting<T> {
+    /// `value` waits instead of the one before. Returns the ticket for its timer.
+    fn change(&mut self, value: T) -> u64 {
+        self.latest += 1;
+        self.value = Some(value);
+        self.latest
+    }
+
+    /// Nothing waits anymore.
+    fn cancel(&mut self) {
+        self.value = None;
+    }
+
+    /// The timer with `ticket` ran out. The value, if that was the timer of the latest change.
+    fn elapsed(&mut self, ticket: u64) -> Option<T> {
+        if ticket == self.latest {
+            self.value.take()
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns a signal that follows `source`, but changes at most once per `interval`. The first change
+/// comes through right away. Changes during the wait after it are held back, and the latest of them
+/// comes through when the wait is over. Unlike `use_debounced`, a steady stream of changes (the
+/// pointer moving) keeps coming through, just less often. `None` uses the app-wide `Timings`.
+pub fn use_throttled<T>(
+    cx: Scope,
+    source: impl Fn() -> T + 'static,
+    interval: impl Into<Option<Duration>>,
+) -> ReadSignal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let interval = resolve(
+        interval.into(),
+        use_context::<Timings>(cx).map(|timings| timings.throttle),
+        DEFAULT_THROTTLE,
+    );
+    let (throttled, set_throttled) = create_signal(cx, cx.untrack(&source));
+    // The timer of the current wait, `None` when there is no wait and a change can come through.
+    let timer = store_value(cx, None::<TimeoutHandle>);
+    // The latest change during the wait.
+    let held_back = store_value(cx, None::<T>);
+
+    create_effect(cx, move |_| {
+        let value = source();
+        if timer.with_value(Option::is_some) {
+            held_back.set_value(Some(value));
+        } else if throttled.with_untracked(|throttled| *throttled != value) {
+            set_throttled(value);
+            wait(timer, held_back, set_throttled, interval);
+        }
+    });
+    on_cleanup(cx, move || {
+        if let Some(timer) = timer.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+    });
+
+    throttled
+}
+
+/// One wait of `use_throttled`. A value held back during it comes through at the end, and starts the
+/// next wait.
+fn wait<T: 'static>(
+    timer: StoredValue<Option<TimeoutHandle>>,
+    held_back: StoredValue<Option<T>>,
+    set_throttled: WriteSignal<T>,
+    interval: impl Fn() -> Duration + Copy + 'static,
+) {
+    let handle = set_timeout_with_handle(
+        move || {
+            timer.set_value(None);
+            if let Some(value) = held_back.try_update_value(Option::take).flatten() {
+                set_throttled(value);
+                wait(timer, held_back, set_throttled, interval);
+            }
+        },
+        interval(),
+    )
+    .ok();
+    timer.set_value(handle);
+}
+
+fn stored_timings() -> Option<(Duration, Duration)> {
+    let storage = window().local_storage().ok().flatten()?;
+    let millis = storage.get_item(STORAGE_KEY).ok().flatten()?;
+    let (debounce, throttle) = millis.split_once(',')?;
+    Some((
+        Duration::from_millis(debounce.parse().ok()?),
+        Duration::from_millis(throttle.parse().ok()?),
+    ))
+}
+
+/// The duration of the call if it has one, otherwise the one in context as it is right now, otherwise
+/// `default`.
+fn resolve(
+    own: Option<Duration>,
+    context: Option<RwSignal<Duration>>,
+    default: Duration,
+) -> impl Fn() -> Duration + Copy + 'static {
+    move || {
+        own.or_else(|| context.map(|context| context.get_untracked()))
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_last_of_rapid_changes_comes_through() {
+        let mut waiting = Waiting::default();
+        let l = waiting.change("l");
+        let lu = waiting.change("lu");
+        let lui = waiting.change("lui");
+        assert_eq!(waiting.elapsed(l), None);
+        assert_eq!(waiting.elapsed(lu), None);
+        assert_eq!(waiting.elapsed(lui), Some("lui"));
+    }
+
+    #[test]
+    fn a_value_comes_through_once() {
+        let mut waiting = Waiting::default();
+        let ticket = waiting.change(1);
+        assert_eq!(waiting.elapsed(ticket), Some(1));
+        assert_eq!(waiting.elapsed(ticket), None);
+    }
+
+    #[test]
+    fn a_cancelled_value_never_comes_through() {
+        let mut waiting = Waiting::default();
+        let ticket = waiting.change(1);
+        waiting.cancel();
+        assert_eq!(waiting.elapsed(ticket), None);
+    }
+}
@@ -0,0 +1,233 @@
+use leptos::*;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+// What changed between two versions of a list, e.g. before and after a refetch. Comparing position by
+// position would call everything after an inserted entry "changed". Instead every entry has a key, some
+// field that stays the same while the rest of it may change (an id), and entries are paired by key:
+//
+// - a key only in the new list was added,
+// - a key only in the old list was removed,
+// - a key in both is changed or unchanged, depending on whether the entries are equal (`PartialEq`).
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change<T> {
+    Added(T),
+    Removed(T),
+    Changed { before: T, after: T },
+    Unchanged(T),
+}
+
+/// Pairs the entries of `before` and `after` by `key` and says what happened to each. The result is in
+/// the order of `after`, followed by the removed entries in the order of `before`.
+///
+/// Keys should be unique. If one isn't, its first entry in `before` pairs with its first entry in
+/// `after`, the second with the second, and so on.
+pub fn diff_by_key<T, K>(before: &[T], after: &[T], key: impl Fn(&T) -> K) -> Vec<Change<T>>
+where
+    T: Clone + PartialEq,
+    K: Eq + Hash,
+{
+    // Where each key is in `before`. A queue, so duplicates are paired up in order.
+    let mut positions: HashMap<K, VecDeque<usize>> = HashMap::new();
+    for (index, entry) in before.iter().enumerate() {
+        positions.entry(key(entry)).or_default().push_back(index);
+    }
+    let mut paired = vec![false; before.len()];
+
+    let mut changes: Vec<_> = after
+        .iter()
+        .map(|entry| {
+            let old = positions.get_mut(&key(entry)).and_then(VecDeque::pop_front);
+            match old {
+                None => Change::Added(entry.clone()),
+                Some(index) => {
+                    paired[index] = true;
+                    if before[index] == *entry {
+                        Change::Unchanged(entry.clone())
+                    } else {
+                        Change::Changed {
+                            before: before[index].clone(),
+                            after: entry.clone(),
+                        }
+                    }
+                }
+            }
+        })
+        .collect();
+    changes.extend(
+        before
+            .iter()
+            .zip(paired)
+            .filter(|(_, paired)| !paired)
+            .map(|(entry, _)| Change::Removed(entry.clone())),
+    );
+    changes
+}
+
+/// The differences between `before` and `after`, one line per entry. `key` picks what pairs the
+/// entries up (see `diff_by_key`), `describe` is how an entry is shown. Unchanged entries are left out
+/// unless `show_unchanged` is set.
+#[component]
+pub fn DiffView<T, K>(
+    cx: Scope,
+    #[prop(into)] before: Signal<Vec<T>>,
+    #[prop(into)] after: Signal<Vec<T>>,
+    key: fn(&T) -> K,
+    describe: fn(&T) -> String,
+    #[prop(optional)] show_unchanged: bool,
+) -> impl IntoView
+where
+    T: Clone + PartialEq + 'static,
+    K: Eq + Hash + 'static,
+{
+    let changes = create_memo(cx, move |_| {
+        before.with(|before| after.with(|after| diff_by_key(before, after, key)))
+    });
+
+    let summary = move || {
+        changes.with(|changes| {
+            let count = |wanted: fn(&Change<T>) -> bool| {
+                changes.iter().filter(|change| wanted(change)).count()
+            };
+            let added = count(|change| matches!(change, Change::Added(_)));
+            let removed = count(|change| matches!(change, Change::Removed(_)));
+            let changed = count(|change| matches!(change, Change::Changed { .. }));
+            let unchanged = changes.len() - added - removed - changed;
+            format!("{added} added, {removed} removed, {changed} changed, {unchanged} unchanged")
+        })
+    };
+
+    // A sign in front of every line as well as a color, so the difference doesn't depend on seeing colors.
+    // Screen readers get a word instead of the sign.
+    let line = move |sign: &'static str, word: &'static str, class: &'static str, text: String| {
+        view! { cx,
+            <li class=class>
+                <span class="inline-block w-4" aria-hidden="true">{sign}</span>
+                <span class="sr-only">{word}</span>
+                {text}
+            </li>
+        }
+    };
+    let lines = move || {
+        changes.with(|changes| {
+            changes
+                .iter()
+                .map(|change| match change {
+                    Change::Added(entry) => {
+                        line("+", "Added: ", "text-green-700", describe(entry)).into_view(cx)
+                    }
+                    Change::Removed(entry) => line(
+                        "−",
+                        "Removed: ",
+                        "text-red-700 line-through",
+                        describe(entry),
+                    )
+                    .into_view(cx),
+                    Change::Changed { before, after } => view! { cx,
+                        {line("−", "Changed from: ", "text-red-700 line-through", describe(before))}
+                        {line("+", "to: ", "text-yellow-700", describe(after))}
+                    }
+                    .into_view(cx),
+                    Change::Unchanged(entry) if show_unchanged => {
+                        line("", "", "text-gray-500", describe(entry)).into_view(cx)
+                    }
+                    Change::Unchanged(_) => ().into_view(cx),
+                })
+                .collect_view(cx)
+        })
+    };
+
+    view! { cx,
+        <div>
+            <p>{summary}</p>
+            <ul class="mt-2 font-mono text-sm">{lines}</ul>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Entry {
+        id: u32,
+        name: &'static str,
+    }
+
+    fn entry(id: u32, name: &'static str) -> Entry {
+        Entry { id, name }
+    }
+
+    fn id(entry: &Entry) -> u32 {
+        entry.id
+    }
+
+    #[test]
+    fn entries_are_paired_by_key_not_by_position() {
+        let before = [entry(1, "Mario"), entry(2, "Luigi")];
+        let after = [entry(3, "Peach"), entry(1, "Mario"), entry(2, "Luigi")];
+        assert_eq!(
+            diff_by_key(&before, &after, id),
+            [
+                Change::Added(entry(3, "Peach")),
+                Change::Unchanged(entry(1, "Mario")),
+                Change::Unchanged(entry(2, "Luigi")),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_key_in_both_lists_is_changed_when_the_entries_differ() {
+        let before = [entry(1, "Mario")];
+        let after = [entry(1, "Dr. Mario")];
+        assert_eq!(
+            diff_by_key(&before, &after, id),
+            [Change::Changed {
+                before: entry(1, "Mario"),
+                after: entry(1, "Dr. Mario"),
+            }]
+        );
+    }
+
+    #[test]
+    fn removed_entries_come_last_in_their_old_order() {
+        let before = [entry(1, "Mario"), entry(2, "Luigi"), entry(3, "Peach")];
+        let after = [entry(2, "Luigi")];
+        assert_eq!(
+            diff_by_key(&before, &after, id),
+            [
+                Change::Unchanged(entry(2, "Luigi")),
+                Change::Removed(entry(1, "Mario")),
+                Change::Removed(entry(3, "Peach")),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_are_paired_in_order() {
+        let before = [entry(1, "first"), entry(1, "second")];
+        let after = [entry(1, "first"), entry(1, "changed"), entry(1, "third")];
+        assert_eq!(
+            diff_by_key(&before, &after, id),
+            [
+                Change::Unchanged(entry(1, "first")),
+                Change::Changed {
+                    before: entry(1, "second"),
+                    after: entry(1, "changed"),
+                },
+                Change::Added(entry(1, "third")),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_lists_have_no_changes() {
+        assert_eq!(diff_by_key::<Entry, u32>(&[], &[], id), []);
+        assert_eq!(
+            diff_by_key(&[], &[entry(1, "Mario")], id),
+            [Change::Added(entry(1, "Mario"))]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{Datelike, Local, Timelike};
+use js_sys::{Array, Uint8Array};
+use std::io::{Cursor, Write};
+use std::time::Duration;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use zip::{write::FileOptions, CompressionMethod, DateTime, ZipWriter};
+
+// Files made in the browser, saved without a round trip to the server. The bytes go into a `Blob`, the
+// `Blob` gets a temporary `blob:` url, and clicking a link to that url with a `download` attribute makes
+// the browser save it instead of opening it.
+//
+// Several files at once would be several downloads, and browsers ask (or quietly refuse) after the
+// first one. `zip_files` packs them into one archive first. The whole archive is built in memory,
+// which is fine for the few hundred kilobytes an export usually is.
+
+/// How long a download's `blob:` url stays valid, plenty for the browser to read it.
+const REVOKE_AFTER: Duration = Duration::from_secs(60);
+
+/// Lets the browser save `bytes` as a file called `filename`. `mime` is the type of the content, e.g.
+/// "application/zip". Only works in the browser.
+pub fn download_bytes(bytes: &[u8], mime: &str, filename: &str) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    // A copy of the bytes in JS memory. A view straight into the WASM memory would break as soon as that
+    // memory grows.
+    let parts = Array::of1(&Uint8Array::from(bytes));
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let link: HtmlAnchorElement = leptos::document().create_element("a")?.unchecked_into();
+    link.set_href(&url);
+    link.set_download(filename);
+    // The link doesn't need to be on the page to be clicked.
+    link.click();
+    // The click only starts the download, the browser reads the url afterwards. Revoking it right away can
+    // cancel the download or leave an empty file (Firefox and Safari do), so it is revoked a while later.
+    // That frees the memory behind it, otherwise it would be held until the tab is closed.
+    leptos::set_timeout(move || _ = Url::revoke_object_url(&url), REVOKE_AFTER);
+    Ok(())
+}
+
+/// Packs `files`, pairs of a path in the archive and the content, into a zip archive. Paths may contain
+/// `/` to put files in folders.
+pub fn zip_files<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> zip::result::ZipResult<Vec<u8>> {
+    // The zip crate takes the time from the operating system when asked for "now", and wasm32 doesn't
+    // have one: it would panic. So we hand it the time ourselves, read from the browser by chrono. Zip
+    // times have no time zone, people expect to see their own.
+    let now = Local::now();
+    let modified = DateTime::from_date_and_time(
+        u16::try_from(now.year()).unwrap_or_default(),
+        now.month() as u8,
+        now.day() as u8,
+        now.hour() as u8,
+        now.minute() as u8,
+        now.second() as u8,
+    )
+    .unwrap_or_default();
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modified);
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, content) in files {
+        zip.start_file(path, options)?;
+        zip.write_all(content)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
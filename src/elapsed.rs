@@ -0,0 +1,100 @@
+use crate::locale::{use_locale, Locale};
+use leptos::*;
+use std::time::Duration;
+
+/// Shows how long the page has been open, e.g. "open for 1m 23s", updated every second and in the
+/// language of the picked locale.
+#[component]
+pub fn OpenFor(cx: Scope) -> impl IntoView {
+    let (seconds, set_seconds) = create_signal(cx, 0);
+
+    // Timers only run in the browser. On the server we simply render "open for 0s" (in the default locale).
+    if !leptos_dom::is_server() {
+        // `performance.now()` is a millisecond clock that isn't affected by changes to the system time.
+        let now = || {
+            window()
+                .performance()
+                .map_or(0.0, |performance| performance.now())
+        };
+        let start = now();
+        let interval = set_interval_with_handle(
+            move || set_seconds(((now() - start) / 1000.0) as u64),
+            Duration::from_secs(1),
+        );
+        // Stop the interval when the component is removed, otherwise it would keep ticking forever.
+        on_cleanup(cx, move || {
+            if let Ok(interval) = interval {
+                interval.clear();
+            }
+        });
+    }
+
+    let locale = use_locale(cx);
+    view! { cx, <span>{move || open_for(seconds.get(), locale.get())}</span> }
+}
+
+/// The whole label in `locale`, e.g. "open for 1m 23s" or "seit 1 Min. 23 Sek. geöffnet".
+pub fn open_for(seconds: u64, locale: Locale) -> String {
+    let elapsed = format_elapsed(seconds, locale);
+    match locale {
+        Locale::EnUs | Locale::EnGb => format!("open for {elapsed}"),
+        Locale::De => format!("seit {elapsed} geöffnet"),
+        Locale::Fr => format!("ouvert depuis {elapsed}"),
+        Locale::Ja => format!("開いてから{elapsed}"),
+    }
+}
+
+/// Formats a number of seconds as "45s", "1m 23s" or "2h 5m 0s", with the units `locale` uses.
+pub fn format_elapsed(seconds: u64, locale: Locale) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    // Units for hours, minutes and seconds, and what goes between a number and its unit.
+    let (units, gap) = match locale {
+        Locale::EnUs | Locale::EnGb => (["h", "m", "s"], ""),
+        Locale::De => (["Std.", "Min.", "Sek."], " "),
+        Locale::Fr => (["h", "min", "s"], " "),
+        Locale::Ja => (["時間", "分", "秒"], ""),
+    };
+    let parts = [(hours, units[0]), (minutes, units[1]), (seconds, units[2])];
+    let first = if hours > 0 {
+        0
+    } else if minutes > 0 {
+        1
+    } else {
+        2
+    };
+    let separator = if locale == Locale::Ja { "" } else { " " };
+    parts[first..]
+        .iter()
+        .map(|(amount, unit)| format!("{amount}{gap}{unit}"))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_out_leading_zero_units() {
+        assert_eq!(format_elapsed(0, Locale::EnUs), "0s");
+        assert_eq!(format_elapsed(45, Locale::EnUs), "45s");
+        assert_eq!(format_elapsed(83, Locale::EnUs), "1m 23s");
+        assert_eq!(format_elapsed(7500, Locale::EnUs), "2h 5m 0s");
+    }
+
+    #[test]
+    fn uses_the_units_of_the_locale() {
+        assert_eq!(format_elapsed(7500, Locale::EnGb), "2h 5m 0s");
+        assert_eq!(format_elapsed(7500, Locale::De), "2 Std. 5 Min. 0 Sek.");
+        assert_eq!(format_elapsed(7500, Locale::Fr), "2 h 5 min 0 s");
+        assert_eq!(format_elapsed(7500, Locale::Ja), "2時間5分0秒");
+    }
+
+    #[test]
+    fn puts_the_time_where_the_language_wants_it() {
+        assert_eq!(open_for(83, Locale::EnUs), "open for 1m 23s");
+        assert_eq!(open_for(83, Locale::De), "seit 1 Min. 23 Sek. geöffnet");
+        assert_eq!(open_for(83, Locale::Fr), "ouvert depuis 1 min 23 s");
+        assert_eq!(open_for(83, Locale::Ja), "開いてから1分23秒");
+    }
+}
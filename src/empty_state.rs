@@ -0,0 +1,49 @@
+use leptos::*;
+use std::rc::Rc;
+
+/// A button shown below the message of an `EmptyState`, e.g. "Clear filters".
+#[derive(Clone)]
+pub struct EmptyAction {
+    pub label: String,
+    pub on_click: Rc<dyn Fn()>,
+}
+
+impl EmptyAction {
+    pub fn new(label: impl Into<String>, on_click: impl Fn() + 'static) -> Self {
+        Self {
+            label: label.into(),
+            on_click: Rc::new(on_click),
+        }
+    }
+}
+
+/// What a list shows when it has nothing in it, instead of rendering nothing at all.
+/// The children, if any, are used as the illustration above the message.
+#[component]
+pub fn EmptyState(
+    cx: Scope,
+    /// Tells the user why the list is empty.
+    #[prop(into)]
+    message: String,
+    /// Optional button, usually to get out of the empty state.
+    #[prop(optional)]
+    action: Option<EmptyAction>,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    view! { cx,
+        <div class="flex flex-col items-center p-8 mt-4 text-gray-500 border border-dashed rounded">
+            {children.map(|children| view! { cx, <div class="mb-2 text-4xl">{children(cx)}</div> })}
+            <p>{message}</p>
+            {action.map(|EmptyAction { label, on_click }| {
+                view! { cx,
+                    <button
+                        class="px-3 py-2 mt-4 text-white bg-blue-700 rounded shadow"
+                        on:click=move |_| on_click()
+                    >
+                        {label}
+                    </button>
+                }
+            })}
+        </div>
+    }
+}
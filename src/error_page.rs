@@ -0,0 +1,53 @@
+use crate::report::report_error;
+use leptos::*;
+use std::collections::HashSet;
+
+/// The page shown instead of the app when an error reached the `<ErrorBoundary>` in `App`.
+///
+/// Something in the app is broken at this point, so this only relies on what it is given: no
+/// context (theme, toasts, router, ...), which may be the very thing that failed.
+#[component]
+pub fn ErrorPage(cx: Scope, errors: RwSignal<Errors>) -> impl IntoView {
+    // Report every error once. The effect runs again when another error comes in, `reported`
+    // (what it returned last time) remembers the ones already sent.
+    let report_new = move |mut reported: HashSet<_>| {
+        errors.with(|errors| {
+            for (key, error) in errors.iter() {
+                if reported.insert(key.clone()) {
+                    report_error("ErrorBoundary", error);
+                }
+            }
+        });
+        reported
+    };
+    create_effect(cx, move |reported| report_new(reported.unwrap_or_default()));
+    // Effects don't run on the server, an error during server-side rendering is reported right here.
+    if leptos_dom::is_server() {
+        report_new(HashSet::new());
+    }
+
+    view! { cx,
+        <main class="flex flex-col items-center justify-center min-h-screen gap-4 p-4 text-center">
+            <h1 class="text-2xl font-bold">"Something went wrong"</h1>
+            <p>"Sorry, the page ran into an error. Reloading usually helps."</p>
+            <ul class="text-sm text-red-700">
+                {move || {
+                    errors.with(|errors| {
+                        errors
+                            .iter()
+                            .map(|(_, error)| view! { cx, <li>{error.to_string()}</li> })
+                            .collect_view(cx)
+                    })
+                }}
+            </ul>
+            <button
+                class="px-4 py-2 text-white bg-blue-700 rounded hover:bg-blue-800"
+                on:click=|_| {
+                    _ = window().location().reload();
+                }
+            >
+                "Reload"
+            </button>
+        </main>
+    }
+}
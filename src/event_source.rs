@@ -0,0 +1,71 @@
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// Server-sent events (SSE): the server keeps a plain HTTP response open and writes an event into it
+// whenever it has something new. Unlike a WebSocket it only goes one way, server to browser, but it
+// needs nothing special on the server and the browser's `EventSource` reconnects by itself when the
+// connection drops. `use_event_source` turns it into signals, like `use_websocket` does for WebSockets.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventSourceStatus {
+    /// Connecting, or reconnecting after the connection dropped.
+    Connecting,
+    Open,
+    /// The browser gave up, e.g. because the server answered with an error status.
+    Closed,
+}
+
+#[derive(Copy, Clone)]
+pub struct EventSourceHandle {
+    /// The data of the latest event, `None` until the first one arrives.
+    pub message: ReadSignal<Option<String>>,
+    pub status: ReadSignal<EventSourceStatus>,
+}
+
+/// Listens to the events streamed from `url` while `cx` lives. On the server nothing is connected
+/// and `status` stays `Connecting`.
+pub fn use_event_source(cx: Scope, url: &str) -> EventSourceHandle {
+    let (message, set_message) = create_signal(cx, None);
+    let (status, set_status) = create_signal(cx, EventSourceStatus::Connecting);
+    let handle = EventSourceHandle { message, status };
+    if leptos_dom::is_server() {
+        return handle;
+    }
+
+    let Ok(source) = web_sys::EventSource::new(url) else {
+        set_status(EventSourceStatus::Closed);
+        return handle;
+    };
+    let on_open = Closure::<dyn Fn()>::new(move || set_status(EventSourceStatus::Open));
+    let on_message =
+        Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            if let Some(data) = event.data().as_string() {
+                set_message(Some(data));
+            }
+        });
+    // After an error the browser either tries again (the state goes back to CONNECTING) or gives up (CLOSED).
+    let on_error = Closure::<dyn Fn()>::new({
+        let source = source.clone();
+        move || {
+            set_status(match source.ready_state() {
+                web_sys::EventSource::CLOSED => EventSourceStatus::Closed,
+                _ => EventSourceStatus::Connecting,
+            })
+        }
+    });
+    source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    // Close the connection when the component goes away, otherwise the server would keep streaming to nobody.
+    on_cleanup(cx, move || {
+        source.set_onopen(None);
+        source.set_onmessage(None);
+        source.set_onerror(None);
+        source.close();
+        // Keep the callbacks alive until the source is unhooked.
+        drop((on_open, on_message, on_error));
+    });
+
+    handle
+}
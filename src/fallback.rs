@@ -12,10 +12,17 @@ cfg_if! { if #[cfg(feature = "ssr")] {
     use tower_http::services::ServeDir;
     use leptos::{LeptosOptions, view};
     use crate::app::App;
+    use crate::prerender::STATIC_ROUTES;
 
     pub async fn file_and_error_handler(uri: Uri, State(options): State<LeptosOptions>, req: Request<Body>) -> AxumResponse {
         let root = options.site_root.clone();
-        let res = get_static_file(uri.clone(), &root).await.unwrap();
+        // A pre-rendered page lives in `<page>/index.html`, see prerender.rs.
+        let file_uri = if STATIC_ROUTES.contains(&uri.path()) {
+            format!("{}/index.html", uri.path()).parse().unwrap_or(uri.clone())
+        } else {
+            uri.clone()
+        };
+        let res = get_static_file(file_uri, &root).await.unwrap();
 
         if res.status() == StatusCode::OK {
             res.into_response()
@@ -0,0 +1,106 @@
+use crate::context::expect_context;
+use leptos::*;
+use leptos_router::use_location;
+
+// Favorite pages. `App` provides the list and renders a `<FavoriteToggle/>` inside the `<Router>`:
+// a star that adds the current page to the list or takes it off again. The list is saved in
+// localStorage, so it's still there next time.
+//
+// A saved path may point to a page that isn't compiled in (see the `examples-*` features). It stays
+// in the list, whoever shows the list skips it, and it's back once the page is.
+
+const STORAGE_KEY: &str = "favorites";
+
+#[derive(Copy, Clone)]
+pub struct Favorites(RwSignal<Vec<String>>);
+
+impl Favorites {
+    /// The paths of the favorite pages, in the order they were starred.
+    pub fn paths(&self) -> Vec<String> {
+        self.0.get()
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.0
+            .with(|paths| paths.iter().any(|favorite| favorite == path))
+    }
+
+    /// Adds `path` to the favorites, or removes it if it is one already. Saves the list.
+    pub fn toggle(&self, path: &str) {
+        self.0.update(|paths| {
+            if paths.iter().any(|favorite| favorite == path) {
+                paths.retain(|favorite| favorite != path);
+            } else {
+                paths.push(path.to_string());
+            }
+        });
+        self.save();
+    }
+
+    /// Replaces the favorites with `paths`, e.g. from a backup. Saves the list.
+    pub fn replace(&self, paths: Vec<String>) {
+        self.0.set(paths);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Some(storage) = window().local_storage().ok().flatten() {
+            let json = self
+                .0
+                .with_untracked(serde_json::to_string)
+                .unwrap_or_default();
+            _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+/// Provides the favorites to every component below `cx`.
+pub fn provide_favorites(cx: Scope) -> Favorites {
+    let favorites = Favorites(create_rw_signal(cx, Vec::new()));
+    provide_context(cx, favorites);
+
+    // The server doesn't know the favorites, it renders none. We load them after the first frame,
+    // once the page has hydrated with none just like the server rendered it.
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || favorites.0.set(stored_favorites()));
+    }
+
+    favorites
+}
+
+/// Pulls the favorites out of context.
+pub fn use_favorites(cx: Scope) -> Favorites {
+    expect_context::<Favorites>(cx, "App")
+}
+
+/// A star that adds the current page to the favorites, or removes it. Only shown on pages for
+/// which `track` returns `true`.
+#[component]
+pub fn FavoriteToggle(cx: Scope, track: fn(&str) -> bool) -> impl IntoView {
+    let favorites = use_favorites(cx);
+    let pathname = use_location(cx).pathname;
+    let favorite = move || pathname.with(|path| favorites.contains(path));
+
+    view! { cx,
+        <Show when=move || pathname.with(|path| track(path)) fallback=|_| ()>
+            <button
+                class="text-xl"
+                aria-label="Favorite"
+                aria-pressed=move || favorite().to_string()
+                on:click=move |_| favorites.toggle(&pathname.get_untracked())
+            >
+                {move || if favorite() { "★" } else { "☆" }}
+            </button>
+        </Show>
+    }
+}
+
+fn stored_favorites() -> Vec<String> {
+    window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
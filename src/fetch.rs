@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use leptos::ServerFnError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
+
+// Helpers for talking to JSON APIs, used by the fetch example.
+
+/// What can go wrong while fetching. The messages are meant to be shown to the user. Serializable, so
+/// that a failed fetch on the server reaches the browser as the same error.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, thiserror::Error)]
+pub enum FetchError {
+    #[error("Request failed: {0}")]
+    Request(String),
+    /// The server answered, but with an error status like 404 or 503.
+    #[error("Server responded with status {0}")]
+    Status(u16),
+    /// 429, we sent too many requests. `retry_after` is how long the server asked us to wait, if it said.
+    #[error("Too many requests")]
+    RateLimited { retry_after: Option<Duration> },
+    /// The server answered with something that isn't JSON, typically an HTML error page.
+    #[error("Unexpected content type: {0}")]
+    UnexpectedContentType(String),
+    /// The response isn't the JSON we expected. `field` is the path to the value that didn't fit,
+    /// e.g. `amiibo[3].character`.
+    #[error("Failed to parse field '{field}': {message}")]
+    Parse { field: String, message: String },
+}
+
+impl FetchError {
+    /// Whether trying the same request again might work. Network errors, server errors (5xx) and "too
+    /// many requests" (429) are usually temporary. Any other 4xx means the request itself is wrong, and
+    /// data we can't parse will be just as unparseable the next time, so retrying those is pointless.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FetchError::Request(_) => true,
+            FetchError::Status(status) => *status >= 500,
+            FetchError::RateLimited { .. } => true,
+            FetchError::UnexpectedContentType(_) | FetchError::Parse { .. } => false,
+        }
+    }
+
+    /// How long the server wants us to wait before trying again, if it told us.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FetchError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwasm::Error> for FetchError {
+    fn from(error: reqwasm::Error) -> Self {
+        FetchError::Request(error.to_string())
+    }
+}
+
+/// A server function that didn't answer, e.g. because our own server is down, is a failed request like any other.
+impl From<ServerFnError> for FetchError {
+    fn from(error: ServerFnError) -> Self {
+        FetchError::Request(error.to_string())
+    }
+}
+
+/// GETs `url` and parses the JSON response. We ask for JSON with the `Accept` header, but servers don't
+/// always listen: an error page often comes back as HTML, sometimes even with a 200 status. Checking the
+/// `Content-Type` first turns that into a clear error instead of a confusing parse failure.
+pub async fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, FetchError> {
+    let response = reqwasm::http::Request::get(url)
+        .header("Accept", "application/json")
+        .send()
+        .await?;
+    let headers = response.headers();
+    read_json(
+        response.status(),
+        headers.get("retry-after").as_deref(),
+        headers.get("content-type").as_deref(),
+        &response.text().await?,
+    )
+}
+
+/// The checks of `get_json`, for a response that arrived some other way: its status, its `Retry-After`
+/// and `Content-Type` headers and its body. The server uses this for answers it didn't need a request
+/// for, see `get_amiibo` in app.rs.
+pub fn read_json<T: DeserializeOwned>(
+    status: u16,
+    retry_after: Option<&str>,
+    content_type: Option<&str>,
+    body: &str,
+) -> Result<T, FetchError> {
+    // `fetch` only fails on network errors, a 404 or 500 is a "successful" response we have to check ourselves.
+    if status == 429 {
+        let retry_after = retry_after.and_then(|value| parse_retry_after(value, Utc::now()));
+        return Err(FetchError::RateLimited { retry_after });
+    }
+    if !(200..300).contains(&status) {
+        return Err(FetchError::Status(status));
+    }
+    // e.g. "application/json; charset=utf-8", or "application/problem+json"
+    let mime = content_type
+        .unwrap_or_default()
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if mime != "application/json" && !mime.ends_with("+json") {
+        return Err(FetchError::UnexpectedContentType(if mime.is_empty() {
+            "(none)".to_string()
+        } else {
+            mime.to_string()
+        }));
+    }
+    parse_json(body)
+}
+
+/// Parses a `Retry-After` header. It comes in two forms: a number of seconds ("120") or an HTTP date
+/// ("Wed, 21 Oct 2015 07:28:00 GMT"), which we turn into the time left from `now`. A date in the
+/// past means "now". Returns `None` for anything else.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    // HTTP dates are the same format as email dates (RFC 2822), which chrono knows how to read.
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Deserializes a JSON body, keeping track of where in the document an error happened.
+/// `reqwasm`'s `.json()` only tells you *that* it failed, this tells you which field.
+pub fn parse_json<T: DeserializeOwned>(body: &str) -> Result<T, FetchError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let path = error.path().to_string();
+        FetchError::Parse {
+            // `.` is what serde_path_to_error uses for "the document itself".
+            field: if path == "." {
+                "(root)".to_string()
+            } else {
+                path
+            },
+            message: error.into_inner().to_string(),
+        }
+    })
+}
+
+/// For `#[serde(deserialize_with = "skip_invalid")]` on a `Vec` field: elements that don't parse are
+/// left out (with a warning in the console) instead of failing the whole document.
+pub fn skip_invalid<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    // First read every element as untyped JSON, which can't fail as long as it is valid JSON at all.
+    // Then turn them into `T` one at a time, so one bad element only costs us that element.
+    let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .filter_map(
+            |(index, value)| match serde_path_to_error::deserialize::<_, T>(value) {
+                Ok(element) => Some(element),
+                Err(error) => {
+                    log::warn!(
+                        "Skipping element {index}: {} at '{}'",
+                        error.inner(),
+                        error.path()
+                    );
+                    None
+                }
+            },
+        )
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    #[test]
+    fn read_json_parses_a_successful_json_answer() {
+        let person: Person = read_json(
+            200,
+            None,
+            Some("application/json; charset=utf-8"),
+            r#"{"name":"Ferris","age":8}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            person,
+            Person {
+                name: "Ferris".to_string(),
+                age: 8
+            }
+        );
+    }
+
+    #[test]
+    fn read_json_turns_an_error_status_into_an_error() {
+        let result = read_json::<Person>(502, None, Some("text/plain"), "Amiibo API unreachable");
+        assert_eq!(result, Err(FetchError::Status(502)));
+        let result = read_json::<Person>(404, None, Some("application/json"), "{}");
+        assert_eq!(result, Err(FetchError::Status(404)));
+    }
+
+    #[test]
+    fn read_json_reports_the_field_that_does_not_fit() {
+        let result = read_json::<Person>(
+            200,
+            None,
+            Some("application/json"),
+            r#"{"name":"Ferris","age":"eight"}"#,
+        );
+        assert!(matches!(result, Err(FetchError::Parse { field, .. }) if field == "age"));
+    }
+
+    #[test]
+    fn read_json_rejects_a_body_that_is_not_json() {
+        let result = read_json::<Person>(200, None, Some("text/html"), "<html></html>");
+        assert_eq!(
+            result,
+            Err(FetchError::UnexpectedContentType("text/html".to_string()))
+        );
+        let result = read_json::<Person>(200, None, None, "");
+        assert_eq!(
+            result,
+            Err(FetchError::UnexpectedContentType("(none)".to_string()))
+        );
+    }
+
+    #[test]
+    fn temporary_failures_are_retried() {
+        assert!(FetchError::Request("offline".to_string()).is_retryable());
+        assert!(FetchError::Status(500).is_retryable());
+        assert!(FetchError::Status(503).is_retryable());
+        assert!(FetchError::RateLimited { retry_after: None }.is_retryable());
+    }
+
+    #[test]
+    fn failures_that_would_fail_again_are_not_retried() {
+        assert!(!FetchError::Status(400).is_retryable());
+        assert!(!FetchError::Status(404).is_retryable());
+        assert!(!FetchError::Status(499).is_retryable());
+        assert!(!FetchError::UnexpectedContentType("text/html".to_string()).is_retryable());
+        assert!(!FetchError::Parse {
+            field: "amiibo".to_string(),
+            message: "invalid type".to_string(),
+        }
+        .is_retryable());
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn retry_after_in_seconds() {
+        assert_eq!(
+            parse_retry_after("120", now()),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now()), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_as_a_date_is_the_time_left() {
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:30:30 GMT", now()),
+            Some(Duration::from_secs(150))
+        );
+    }
+
+    #[test]
+    fn retry_after_a_date_in_the_past_is_now() {
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now()),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn retry_after_that_is_neither_is_ignored() {
+        assert_eq!(parse_retry_after("", now()), None);
+        assert_eq!(parse_retry_after("-5", now()), None);
+        assert_eq!(parse_retry_after("soon", now()), None);
+    }
+
+    #[test]
+    fn a_429_carries_its_retry_after() {
+        let result = read_json::<Person>(429, Some("30"), None, "");
+        let error = result.unwrap_err();
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+        let result = read_json::<Person>(429, Some("soon"), None, "");
+        assert_eq!(result, Err(FetchError::RateLimited { retry_after: None }));
+    }
+}
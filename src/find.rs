@@ -0,0 +1,179 @@
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+// Find and replace in plain text. Matches are byte ranges into the text, ready for slicing and for
+// `<Highlight/>` (see highlight.rs).
+//
+// The query is searched for literally, "a.b" finds "a.b" and not "axb". Under the hood it is a regular
+// expression with everything escaped: that gets us case-insensitive matching that knows Unicode
+// ("É" finds "é") and byte ranges into the original text, which lowercasing the text ourselves
+// wouldn't give us ("İ" turns into two characters when lowercased, and every range after it would be
+// off).
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FindOptions {
+    /// "Rust" doesn't find "rust".
+    pub case_sensitive: bool,
+    /// "cat" doesn't find the "cat" in "concatenate".
+    pub whole_word: bool,
+}
+
+/// Where `query` occurs in `text`, in order. Matches don't overlap: "aa" is found once in "aaa", at
+/// the start. An empty query matches nothing.
+pub fn find_matches(text: &str, query: &str, options: FindOptions) -> Vec<Range<usize>> {
+    let Some(regex) = literal_regex(query, options.case_sensitive) else {
+        return Vec::new();
+    };
+
+    let mut matches = Vec::new();
+    let mut position = 0;
+    while let Some(found) = regex.find_at(text, position) {
+        let range = found.range();
+        if !options.whole_word || is_whole_word(text, &range) {
+            position = range.end;
+            matches.push(range);
+        } else {
+            // Not a whole word, but a match starting inside this one might be: "a-a" in "ba-a-a" is no word
+            // at 1, but is one at 3. Search again from the next character instead of after the match.
+            position = range.start + text[range.start..].chars().next().map_or(1, char::len_utf8);
+        }
+        if position > text.len() {
+            break;
+        }
+    }
+    matches
+}
+
+/// `text` with `range` replaced by `replacement`.
+pub fn replace_range(text: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut replaced = String::with_capacity(text.len() + replacement.len());
+    replaced.push_str(&text[..range.start]);
+    replaced.push_str(replacement);
+    replaced.push_str(&text[range.end..]);
+    replaced
+}
+
+/// `text` with every one of `matches` (as returned by `find_matches`) replaced by `replacement`. The
+/// text between them is kept as it is. The replacement is never searched again, so replacing "a" by
+/// "aa" doesn't go on forever.
+pub fn replace_all(text: &str, matches: &[Range<usize>], replacement: &str) -> String {
+    let mut replaced = String::with_capacity(text.len());
+    let mut kept_up_to = 0;
+    for range in matches {
+        replaced.push_str(&text[kept_up_to..range.start]);
+        replaced.push_str(replacement);
+        kept_up_to = range.end;
+    }
+    replaced.push_str(&text[kept_up_to..]);
+    replaced
+}
+
+fn literal_regex(query: &str, case_sensitive: bool) -> Option<Regex> {
+    if query.is_empty() {
+        return None;
+    }
+    RegexBuilder::new(&regex::escape(query))
+        .case_insensitive(!case_sensitive)
+        .build()
+        .ok()
+}
+
+/// Whether `range` of `text` has no letter, digit or `_` right before or right after it.
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+    !before.is_some_and(is_word) && !after.is_some_and(is_word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHOLE_WORD: FindOptions = FindOptions {
+        case_sensitive: false,
+        whole_word: true,
+    };
+
+    /// `find_matches` as `(start, end)` pairs, easier to write down than ranges.
+    fn spans(text: &str, query: &str, options: FindOptions) -> Vec<(usize, usize)> {
+        find_matches(text, query, options)
+            .into_iter()
+            .map(|range| (range.start, range.end))
+            .collect()
+    }
+
+    fn found<'a>(text: &'a str, matches: &[Range<usize>]) -> Vec<&'a str> {
+        matches.iter().map(|range| &text[range.clone()]).collect()
+    }
+
+    #[test]
+    fn matches_do_not_overlap() {
+        assert_eq!(spans("aaa", "aa", FindOptions::default()), [(0, 2)]);
+        assert_eq!(
+            spans("aaaa", "aa", FindOptions::default()),
+            [(0, 2), (2, 4)]
+        );
+    }
+
+    #[test]
+    fn an_empty_query_matches_nothing() {
+        assert!(find_matches("text", "", FindOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn the_query_is_literal() {
+        assert_eq!(spans("a.b axb", "a.b", FindOptions::default()), [(0, 3)]);
+    }
+
+    #[test]
+    fn whole_word_skips_matches_inside_words() {
+        let text = "cat concatenate cat_food cat.";
+        assert_eq!(spans(text, "cat", WHOLE_WORD), [(0, 3), (25, 28)]);
+    }
+
+    #[test]
+    fn whole_word_tries_again_inside_a_rejected_match() {
+        assert_eq!(spans("ba-a-a", "a-a", WHOLE_WORD), [(3, 6)]);
+    }
+
+    #[test]
+    fn case_insensitive_matching_knows_unicode() {
+        let text = "Élan, élan, ÉLAN";
+        let matches = find_matches(text, "élan", FindOptions::default());
+        assert_eq!(found(text, &matches), ["Élan", "élan", "ÉLAN"]);
+        // The ranges are into the original text, also after a character that grows when lowercased.
+        let text = "İstanbul Straße STRASSE";
+        let matches = find_matches(text, "straße", FindOptions::default());
+        assert_eq!(found(text, &matches), ["Straße"]);
+    }
+
+    #[test]
+    fn case_sensitive_matching_tells_cases_apart() {
+        let options = FindOptions {
+            case_sensitive: true,
+            whole_word: false,
+        };
+        assert_eq!(spans("Rust rust", "rust", options), [(5, 9)]);
+    }
+
+    #[test]
+    fn replace_all_keeps_the_text_between_matches() {
+        let text = "one, two, one";
+        let matches = find_matches(text, "one", FindOptions::default());
+        assert_eq!(replace_all(text, &matches, "1"), "1, two, 1");
+        assert_eq!(replace_all(text, &[], "1"), text);
+    }
+
+    #[test]
+    fn replace_all_does_not_search_the_replacement_again() {
+        let text = "a ba";
+        let matches = find_matches(text, "a", FindOptions::default());
+        assert_eq!(replace_all(text, &matches, "aa"), "aa baa");
+    }
+
+    #[test]
+    fn replace_range_replaces_one_match() {
+        assert_eq!(replace_range("one, two, one", 10..13, "1"), "one, two, 1");
+    }
+}
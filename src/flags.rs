@@ -0,0 +1,121 @@
+use crate::context::expect_context;
+use leptos::*;
+use std::collections::HashMap;
+
+// Runtime feature flags. Unlike cargo features (`ssr`, `hydrate`), which decide at compile time what
+// code exists, these decide while the app runs what is shown, so experimental things can be switched
+// on and off during a demo without rebuilding.
+//
+// Flags start from `DEFAULT_FLAGS`, then the choices saved in localStorage are applied, then the URL:
+// `?enable=flag_a,flag_b&disable=flag_c`. Flags changed through `set` are saved to localStorage.
+
+/// Every known flag with its default value and a short description.
+pub const DEFAULT_FLAGS: [(&str, bool, &str); 3] = [
+    (
+        "title_badge",
+        true,
+        "Show the number of pending requests in the page title",
+    ),
+    (
+        "progress_bar",
+        true,
+        "Show the progress bar at the top of the page while loading",
+    ),
+    ("mario_party", false, "Extra Marios on the home page"),
+];
+
+const STORAGE_KEY: &str = "feature_flags";
+
+#[derive(Copy, Clone)]
+pub struct FeatureFlags(RwSignal<HashMap<String, bool>>);
+
+impl FeatureFlags {
+    /// Whether `flag` is on. Unknown flags are off. Reading it in a closure makes that closure reactive.
+    pub fn enabled(&self, flag: &str) -> bool {
+        self.0
+            .with(|flags| flags.get(flag).copied().unwrap_or(false))
+    }
+
+    /// Every flag with its current value.
+    pub fn all(&self) -> HashMap<String, bool> {
+        self.0.get_untracked()
+    }
+
+    /// Turns a flag on or off and remembers the choice in localStorage.
+    pub fn set(&self, flag: &str, enabled: bool) {
+        self.0.update(|flags| {
+            flags.insert(flag.to_string(), enabled);
+        });
+        if let Some(storage) = local_storage() {
+            let json = self
+                .0
+                .with_untracked(serde_json::to_string)
+                .unwrap_or_default();
+            _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+/// Provides the flags to every component below `cx`.
+pub fn provide_feature_flags(cx: Scope) -> FeatureFlags {
+    let defaults = DEFAULT_FLAGS
+        .iter()
+        .map(|(flag, enabled, _)| (flag.to_string(), *enabled))
+        .collect();
+    let flags = FeatureFlags(create_rw_signal(cx, defaults));
+    provide_context(cx, flags);
+
+    // localStorage and the URL are only available in the browser. We apply them after the first frame
+    // so the server rendered HTML (which only knows the defaults) hydrates cleanly first.
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || {
+            flags.0.update(|flags| {
+                flags.extend(stored_flags());
+                flags.extend(url_flags());
+            })
+        });
+    }
+
+    flags
+}
+
+/// Pulls the flags out of context.
+pub fn use_feature_flags(cx: Scope) -> FeatureFlags {
+    expect_context::<FeatureFlags>(cx, "App")
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+fn stored_flags() -> HashMap<String, bool> {
+    local_storage()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn url_flags() -> HashMap<String, bool> {
+    let search = window().location().search().unwrap_or_default();
+    let Ok(params) = web_sys::UrlSearchParams::new_with_str(&search) else {
+        return HashMap::new();
+    };
+    let list = |key: &str| params.get(key).unwrap_or_default();
+    parse_flag_lists(&list("enable"), &list("disable"))
+}
+
+/// Turns the comma separated `enable` and `disable` query values into flag values.
+/// `disable` wins if a flag is in both.
+pub fn parse_flag_lists(enable: &str, disable: &str) -> HashMap<String, bool> {
+    let flags = |list: &str, enabled: bool| {
+        list.split(',')
+            .map(str::trim)
+            .filter(|flag| !flag.is_empty())
+            .map(move |flag| (flag.to_string(), enabled))
+            .collect::<Vec<_>>()
+    };
+    flags(enable, true)
+        .into_iter()
+        .chain(flags(disable, false))
+        .collect()
+}
@@ -0,0 +1,86 @@
+use leptos::html::Div;
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// Keyboard focus trapping for overlays (modals, palettes). While an overlay is open, Tab and
+// Shift+Tab should cycle through the overlay's own controls instead of wandering off to the page
+// behind it, and when the overlay closes focus should go back to where the user was.
+
+/// Everything that can receive focus with the Tab key.
+const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]), \
+    select:not([disabled]), textarea:not([disabled]), iframe, [contenteditable], \
+    [tabindex]:not([tabindex='-1'])";
+
+/// Traps keyboard focus inside `container` for as long as the scope `cx` is alive. Call it from the
+/// component that renders the overlay, so the trap starts when the overlay mounts and ends when it
+/// is removed. On mount the first focusable element is focused, on cleanup focus is restored to
+/// the element that had it before.
+pub fn trap_focus(cx: Scope, container: NodeRef<Div>) {
+    // There is no focus to manage while rendering on the server.
+    if leptos_dom::is_server() {
+        return;
+    }
+
+    let previously_focused = document().active_element();
+
+    container.on_load(cx, |container| {
+        if let Some(first) = focusable_elements(&container).first() {
+            _ = first.focus();
+        }
+    });
+
+    let on_keydown =
+        Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(move |event: web_sys::KeyboardEvent| {
+            if event.key() != "Tab" {
+                return;
+            }
+            let Some(container) = container.get_untracked() else {
+                return;
+            };
+            let focusable = focusable_elements(&container);
+            let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+                // Nothing to focus inside the overlay, so don't let focus leave it either.
+                event.prevent_default();
+                return;
+            };
+
+            let active = document().active_element();
+            let is_active = |element: &web_sys::HtmlElement| {
+                active.as_ref() == Some(element.unchecked_ref::<web_sys::Element>())
+            };
+            let inside = active
+                .as_ref()
+                .is_some_and(|active| container.contains(Some(active)));
+
+            // Wrap around at both ends. If focus somehow escaped the container, pull it back in.
+            if event.shift_key() && (is_active(first) || !inside) {
+                event.prevent_default();
+                _ = last.focus();
+            } else if !event.shift_key() && (is_active(last) || !inside) {
+                event.prevent_default();
+                _ = first.focus();
+            }
+        });
+    _ = document().add_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+
+    on_cleanup(cx, move || {
+        _ = document()
+            .remove_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+        if let Some(element) =
+            previously_focused.and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            _ = element.focus();
+        }
+    });
+}
+
+/// All focusable descendants of `container`, in document (tab) order.
+fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect()
+}
@@ -0,0 +1,54 @@
+use leptos::*;
+use std::ops::Range;
+
+// Text with some parts of it marked, e.g. the matches of a search. The parts are byte ranges into the
+// text, in order and not overlapping, as `find_matches` in find.rs returns them. Each one is wrapped
+// in a `<mark>`, the text in between is shown as it is.
+
+/// `text` with `ranges` highlighted. The range at index `current`, if any, stands out from the
+/// others, e.g. the match "Replace" would replace.
+#[component]
+pub fn Highlight(
+    cx: Scope,
+    #[prop(into)] text: Signal<String>,
+    #[prop(into)] ranges: Signal<Vec<Range<usize>>>,
+    #[prop(optional, into)] current: Option<Signal<Option<usize>>>,
+) -> impl IntoView {
+    let current = move || current.and_then(|current| current.get());
+
+    let parts = move || {
+        let current = current();
+        text.with(|text| {
+            ranges.with(|ranges| {
+                let mut views = Vec::with_capacity(ranges.len() * 2 + 1);
+                let mut shown_up_to = 0;
+                for (index, range) in ranges.iter().enumerate() {
+                    // A range that doesn't fit the text (it changed, and the ranges haven't caught up yet)
+                    // would panic when sliced, it's left out.
+                    if range.start < shown_up_to || text.get(range.clone()).is_none() {
+                        continue;
+                    }
+                    views.push(text[shown_up_to..range.start].to_string().into_view(cx));
+                    let is_current = current == Some(index);
+                    views.push(
+                        view! { cx,
+                            <mark
+                                class=if is_current { "bg-orange-300" } else { "bg-yellow-200" }
+                                aria-current=is_current.then_some("true")
+                            >
+                                {text[range.clone()].to_string()}
+                            </mark>
+                        }
+                        .into_view(cx),
+                    );
+                    shown_up_to = range.end;
+                }
+                views.push(text[shown_up_to..].to_string().into_view(cx));
+                views
+            })
+        })
+    };
+
+    // `whitespace-pre-wrap` keeps line breaks and runs of spaces, like in the textarea.
+    view! { cx, <div class="whitespace-pre-wrap">{parts}</div> }
+}
@@ -0,0 +1,110 @@
+use leptos::*;
+use serde_json::Value;
+
+// Shows any JSON value as a tree: objects and arrays can be opened and closed, everything else is shown
+// as it is. Handy to look at what an API really sent, e.g. in the Fetch example.
+//
+// The tree is built by `node`, which calls itself for every member of an object or array. A component
+// can't do that: `impl IntoView` can't be a type that contains itself. `node` returns the plain `View`
+// instead. Each node has its own open/closed signal, and its children are only built while it is open,
+// so a large response doesn't render thousands of elements nobody looks at.
+
+/// A collapsible tree of `value`. Rebuilt (and closed back to `open_depth`) when `value` changes.
+#[component]
+pub fn JsonTree(
+    cx: Scope,
+    #[prop(into)] value: MaybeSignal<Value>,
+    /// How many levels are open at the start. `1` opens the outermost object or array.
+    #[prop(default = 1)]
+    open_depth: usize,
+) -> impl IntoView {
+    view! { cx,
+        <div class="font-mono text-sm">
+            {move || value.with(|value| node(cx, None, value, 0, open_depth))}
+        </div>
+    }
+}
+
+fn node(cx: Scope, key: Option<String>, value: &Value, depth: usize, open_depth: usize) -> View {
+    // "name: " in objects, "3: " in arrays, nothing for the outermost value.
+    let label = key.map(|key| view! { cx, <span class="text-gray-500">{key}": "</span> });
+
+    let (members, summary, brackets) = match value {
+        Value::Object(object) => (
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>(),
+            count(object.len(), "key"),
+            ("{", "}"),
+        ),
+        Value::Array(array) => (
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index.to_string(), value.clone()))
+                .collect(),
+            count(array.len(), "item"),
+            ("[", "]"),
+        ),
+        scalar => {
+            return view! { cx, <div>{label}{scalar_view(cx, scalar)}</div> }.into_view(cx);
+        }
+    };
+
+    // An empty object or array has nothing to open.
+    if members.is_empty() {
+        return view! { cx, <div>{label}{brackets.0}{brackets.1}</div> }.into_view(cx);
+    }
+
+    let open = create_rw_signal(cx, depth < open_depth);
+    let children = move || {
+        open.get().then(|| {
+            view! { cx,
+                <ul class="pl-4 ml-1 border-l">
+                    {members
+                        .iter()
+                        .map(|(key, value)| {
+                            view! { cx, <li>{node(cx, Some(key.clone()), value, depth + 1, open_depth)}</li> }
+                        })
+                        .collect_view(cx)}
+                </ul>
+            }
+        })
+    };
+
+    view! { cx,
+        <div>
+            <button aria-expanded=move || open.get().to_string() on:click=move |_| open.update(|open| *open = !*open)>
+                <span class="inline-block w-4">{move || if open.get() { "▾" } else { "▸" }}</span>
+                {label}
+                {brackets.0}
+                // Closed, the size says whether it's worth opening.
+                <Show when=move || !open.get() fallback=|_| ()>
+                    <span class="text-gray-500">" "{summary.clone()}" "</span>
+                    {brackets.1}
+                </Show>
+            </button>
+            {children}
+            <Show when=move || open.get() fallback=|_| ()>
+                <div class="pl-4">{brackets.1}</div>
+            </Show>
+        </div>
+    }
+    .into_view(cx)
+}
+
+fn scalar_view(cx: Scope, value: &Value) -> View {
+    let class = match value {
+        Value::String(_) => "text-green-700",
+        Value::Number(_) => "text-blue-700",
+        // `true`, `false` and `null`
+        _ => "text-purple-700",
+    };
+    // `Value`'s `Display` writes JSON, so strings come with their quotes and escapes.
+    view! { cx, <span class=class>{value.to_string()}</span> }.into_view(cx)
+}
+
+fn count(n: usize, what: &str) -> String {
+    format!("{n} {what}{}", if n == 1 { "" } else { "s" })
+}
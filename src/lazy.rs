@@ -0,0 +1,46 @@
+use leptos::*;
+
+// State that is costly to build (a big generated dataset, a parsed file, an index) doesn't have to be
+// built when the component is created. `use_lazy` takes the function that builds it and runs it the
+// first time the value is used. Later uses get the same value, built once. A page that never shows
+// the part that needs it never pays for it.
+//
+// Unlike a memo nothing is tracked: the value doesn't depend on signals and is never built again.
+
+/// A value built on first use, see `use_lazy`.
+pub struct Lazy<T: 'static> {
+    value: StoredValue<Option<T>>,
+    init: StoredValue<Option<Box<dyn FnOnce() -> T>>>,
+}
+
+// Derived `Clone` and `Copy` would require `T: Copy`, but only arena handles are held.
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Lazy<T> {}
+
+impl<T> Lazy<T> {
+    /// Calls `f` with the value, building it first if this is the first use.
+    pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> U {
+        if let Some(init) = self.init.try_update_value(Option::take).flatten() {
+            self.value.set_value(Some(init()));
+        }
+        self.value.with_value(|value| {
+            f(value
+                .as_ref()
+                .expect("a lazy value is built before it is used"))
+        })
+    }
+}
+
+/// A value that `init` builds the first time it is used. It belongs to `cx`: components below can
+/// come and go, the value stays.
+pub fn use_lazy<T>(cx: Scope, init: impl FnOnce() -> T + 'static) -> Lazy<T> {
+    Lazy {
+        value: store_value(cx, None),
+        init: store_value(cx, Some(Box::new(init) as Box<dyn FnOnce() -> T>)),
+    }
+}
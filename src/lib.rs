@@ -1,6 +1,64 @@
 use cfg_if::cfg_if;
+pub mod announce;
+pub mod api;
 pub mod app;
+pub mod backup;
+pub mod characters;
+pub mod circuit_breaker;
+pub mod client_only;
+pub mod combine;
+pub mod context;
+pub mod crash;
+pub mod debounce;
+pub mod diff;
+pub mod download;
+pub mod elapsed;
+pub mod empty_state;
+pub mod error_page;
+pub mod event_source;
 pub mod fallback;
+pub mod favorites;
+pub mod fetch;
+pub mod find;
+pub mod flags;
+pub mod focus;
+pub mod highlight;
+pub mod json_tree;
+pub mod lazy;
+pub mod locale;
+pub mod media_query;
+pub mod middleware;
+pub mod modal;
+pub mod nav;
+pub mod not_found;
+pub mod paginate;
+pub mod pending;
+pub mod permalink;
+pub mod preferences;
+pub mod prerender;
+pub mod reactive_graph;
+pub mod recent;
+pub mod reload_prompt;
+pub mod repeat;
+pub mod repeater;
+pub mod report;
+pub mod rich_text;
+pub mod scroll_progress;
+pub mod scroll_restoration;
+pub mod scroll_spy;
+pub mod settings;
+pub mod state_machine;
+pub mod tabs;
+pub mod tasks;
+pub mod theme;
+pub mod toast;
+pub mod toc;
+pub mod trace;
+pub mod validate;
+pub mod versioned;
+pub mod websocket;
+pub mod window_size;
+pub mod worker;
 
 cfg_if! { if #[cfg(feature = "hydrate")] {
     use leptos::*;
@@ -11,10 +69,13 @@ cfg_if! { if #[cfg(feature = "hydrate")] {
     pub fn hydrate() {
         // initializes logging using the `log` crate
         _ = console_log::init_with_level(log::Level::Debug);
-        console_error_panic_hook::set_once();
+        // Logs panics to the console and shows an "app crashed" overlay, see crash.rs.
+        crate::crash::install_panic_hook();
 
         leptos::mount_to_body(move |cx| {
             view! { cx, <App/> }
         });
+        // From here on the app is running, see reload_prompt.rs.
+        crate::reload_prompt::mark_hydrated();
     }
 }}
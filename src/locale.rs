@@ -0,0 +1,208 @@
+use crate::context::expect_context;
+use chrono::{NaiveDate, NaiveTime};
+use leptos::*;
+
+// How dates and times are written depends on where you are: July 22nd is 07/22/2023 in the US,
+// 22/07/2023 in the UK and 22.07.2023 in Germany. `App` provides the locale, pages format with
+// `format_date_localized` and `format_time_localized` and can offer a `<LocalePicker/>`.
+//
+// The server doesn't know the user's locale, it renders `Locale::default()`. After the page has
+// hydrated we switch to the one the user picked before (saved in localStorage) or, failing that, the
+// browser's language. Switching any earlier would make the page differ from the server's HTML.
+//
+// This is only about how dates and times are shown. `<input type="date">` always hands us
+// "2023-07-22", whatever the locale, and shows the date in the browser's own format.
+
+const STORAGE_KEY: &str = "locale";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    EnUs,
+    EnGb,
+    De,
+    Fr,
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Self; 5] = [Self::EnUs, Self::EnGb, Self::De, Self::Fr, Self::Ja];
+
+    /// The BCP 47 language tag, e.g. "en-US".
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::EnUs => "en-US",
+            Self::EnGb => "en-GB",
+            Self::De => "de-DE",
+            Self::Fr => "fr-FR",
+            Self::Ja => "ja-JP",
+        }
+    }
+
+    /// The name of the locale in its own language, for the picker.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::EnUs => "English (US)",
+            Self::EnGb => "English (UK)",
+            Self::De => "Deutsch",
+            Self::Fr => "Français",
+            Self::Ja => "日本語",
+        }
+    }
+
+    /// The locale for a language tag like the browser's `navigator.language`. An exact match first,
+    /// then any locale of the same language ("de-AT" gets German dates). English outside the US
+    /// mostly writes the day first, so "en-AU" gets the UK format. Plain "en" stays US.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag.to_lowercase();
+        if let Some(locale) = Self::ALL
+            .into_iter()
+            .find(|locale| locale.tag().to_lowercase() == tag)
+        {
+            return Some(locale);
+        }
+        match tag.split('-').next()? {
+            "en" if tag == "en" => Some(Self::EnUs),
+            "en" => Some(Self::EnGb),
+            "de" => Some(Self::De),
+            "fr" => Some(Self::Fr),
+            "ja" => Some(Self::Ja),
+            _ => None,
+        }
+    }
+
+    /// chrono format for a date, see `chrono::format::strftime`.
+    fn date_format(self) -> &'static str {
+        match self {
+            Self::EnUs => "%m/%d/%Y",
+            Self::EnGb | Self::Fr => "%d/%m/%Y",
+            Self::De => "%d.%m.%Y",
+            Self::Ja => "%Y/%m/%d",
+        }
+    }
+
+    /// chrono format for a time of day. The US uses a 12 hour clock, the others 24 hours.
+    fn time_format(self) -> &'static str {
+        match self {
+            Self::EnUs => "%-I:%M:%S %p",
+            _ => "%H:%M:%S",
+        }
+    }
+}
+
+/// `date` the way `locale` writes it, e.g. "07/22/2023" or "22.07.2023".
+pub fn format_date_localized(date: NaiveDate, locale: Locale) -> String {
+    date.format(locale.date_format()).to_string()
+}
+
+/// `time` the way `locale` writes it, e.g. "3:04:05 PM" or "15:04:05".
+pub fn format_time_localized(time: NaiveTime, locale: Locale) -> String {
+    time.format(locale.time_format()).to_string()
+}
+
+#[derive(Copy, Clone)]
+pub struct LocaleContext(RwSignal<Locale>);
+
+impl LocaleContext {
+    pub fn get(&self) -> Locale {
+        self.0.get()
+    }
+
+    /// Switches to `locale` and saves it, it is used again next time.
+    pub fn set(&self, locale: Locale) {
+        self.0.set(locale);
+        if let Ok(Some(storage)) = window().local_storage() {
+            _ = storage.set_item(STORAGE_KEY, locale.tag());
+        }
+    }
+}
+
+/// Provides the locale to every component below `cx`.
+pub fn provide_locale(cx: Scope) -> LocaleContext {
+    let locale = LocaleContext(create_rw_signal(cx, Locale::default()));
+    provide_context(cx, locale);
+
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || {
+            if let Some(detected) = stored_locale().or_else(browser_locale) {
+                _ = locale.0.try_set(detected);
+            }
+        });
+    }
+
+    locale
+}
+
+/// Pulls the locale out of context.
+pub fn use_locale(cx: Scope) -> LocaleContext {
+    expect_context::<LocaleContext>(cx, "App")
+}
+
+/// A drop-down to pick the locale.
+#[component]
+pub fn LocalePicker(cx: Scope) -> impl IntoView {
+    let locale = use_locale(cx);
+
+    view! { cx,
+        <label class="text-sm">
+            "Date and time format: "
+            <select
+                class="p-1 border dark:bg-gray-800"
+                on:change=move |event| {
+                    if let Some(picked) = Locale::from_tag(&event_target_value(&event)) {
+                        locale.set(picked);
+                    }
+                }
+                prop:value=move || locale.get().tag()
+            >
+                {Locale::ALL
+                    .map(|option| view! { cx, <option value=option.tag()>{option.name()}</option> })
+                    .collect_view(cx)}
+            </select>
+        </label>
+    }
+}
+
+fn stored_locale() -> Option<Locale> {
+    let storage = window().local_storage().ok().flatten()?;
+    Locale::from_tag(&storage.get_item(STORAGE_KEY).ok().flatten()?)
+}
+
+fn browser_locale() -> Option<Locale> {
+    Locale::from_tag(&window().navigator().language()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_tags_exactly_ignoring_case() {
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(locale));
+        }
+        assert_eq!(Locale::from_tag("EN-gb"), Some(Locale::EnGb));
+        assert_eq!(Locale::from_tag("de-de"), Some(Locale::De));
+    }
+
+    #[test]
+    fn plain_english_is_us_and_other_english_is_uk() {
+        assert_eq!(Locale::from_tag("en"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_tag("en-AU"), Some(Locale::EnGb));
+        assert_eq!(Locale::from_tag("en-IN"), Some(Locale::EnGb));
+    }
+
+    #[test]
+    fn falls_back_to_the_language() {
+        assert_eq!(Locale::from_tag("de-AT"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("fr-CA"), Some(Locale::Fr));
+        assert_eq!(Locale::from_tag("ja"), Some(Locale::Ja));
+    }
+
+    #[test]
+    fn unknown_languages_have_no_locale() {
+        assert_eq!(Locale::from_tag("es-ES"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        assert_eq!(Locale::from_tag("english"), None);
+    }
+}
@@ -1,11 +1,27 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
-    use axum::{routing::post, Router};
+    use axum::{
+        middleware,
+        routing::{get, post},
+        Router,
+    };
     use leptos::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
-    use leptos_tailwind_axum::{app::*, fallback::file_and_error_handler};
+    use leptos_tailwind_axum::{
+        api::{self, AmiiboCache, AppState, CharacterStore},
+        app::*,
+        fallback::file_and_error_handler,
+        middleware::{cache_control, log_requests},
+        prerender::{prerender, static_file},
+    };
     use log::info;
+    use std::time::Instant;
+    use tokio::sync::watch;
+    use tower_http::compression::{
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+        CompressionLayer,
+    };
 
     simple_logger::init_with_level(log::Level::Info).expect("couldn't initialize logging");
 
@@ -17,23 +33,113 @@ async fn main() {
     let conf = get_configuration(None).await.unwrap();
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
+
+    // `--prerender` writes the static pages to files and exits, see prerender.rs.
+    if std::env::args().any(|arg| arg == "--prerender") {
+        prerender(&leptos_options)
+            .await
+            .expect("couldn't pre-render pages");
+        return;
+    }
+
     // Generate the list of routes in your Leptos App
-    let routes = generate_route_list(|cx| view! { cx, <App/> }).await;
+    let mut routes = generate_route_list(|cx| view! { cx, <App/> }).await;
+    // Pages that have been pre-rendered are left to the fallback, which serves their file.
+    routes.retain(|route| !static_file(&leptos_options.site_root, route.path()).exists());
+    // So is the catch-all route of the "404 — Not Found" page. As an axum route it would answer every request no other
+    // route takes, and the fallback would never get to serve `/pkg/...` and the other files. The fallback renders the
+    // app for paths without a file, and the router in there shows the 404 page.
+    routes.retain(|route| !route.path().contains('*'));
+
+    // `shutdown_signal` sends `true` when the server is asked to stop. Responses that would never end by themselves,
+    // like the server-sent events of `/api/ticks`, wait for it.
+    let (shutting_down_sender, shutting_down) = watch::channel(false);
+
+    let amiibo_cache = AmiiboCache::default();
+    amiibo_cache.purge_periodically();
+    // Shared with all handlers, see api.rs.
+    let state = AppState {
+        leptos_options,
+        started: Instant::now(),
+        http: reqwest::Client::new(),
+        amiibo_cache,
+        characters: CharacterStore::default(),
+        shutting_down,
+    };
 
     // build our application with a route
     let app = Router::new()
-        .route("/api/*fn_name", post(leptos_axum::handle_server_fns))
-        .leptos_routes(&leptos_options, routes, |cx| view! { cx, <App/> })
+        .route("/api/health", get(api::health))
+        .route("/api/version", get(api::version))
+        .route("/api/amiibo", get(api::amiibo))
+        .route("/api/username", get(api::username))
+        .route("/api/ticks", get(api::ticks))
+        // Server functions get the state in context, see api.rs.
+        .route("/api/*fn_name", post(api::server_fns))
+        // So do the pages. The fetch example uses it to ask the Amiibo API while the page is rendered.
+        .leptos_routes_with_context(
+            &state,
+            routes,
+            {
+                let state = state.clone();
+                move |cx| provide_context(cx, state.clone())
+            },
+            |cx| view! { cx, <App/> },
+        )
         .fallback(file_and_error_handler)
-        .with_state(leptos_options);
+        // Layers wrap everything added above, including the fallback.
+        // Compresses responses with brotli or gzip, whichever the browser says it understands (`Accept-Encoding`).
+        // The WASM bundle shrinks to a fraction of its size. Responses that are already compressed (they have a
+        // `Content-Encoding`), images and tiny bodies are left alone. So are server-sent events: the compressor
+        // holds data back until it has enough to work with, and the events would arrive late and in bunches.
+        .layer(CompressionLayer::new().compress_when(
+            DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
+        .layer(middleware::from_fn(cache_control))
+        .layer(middleware::from_fn(log_requests))
+        .with_state(state);
 
     // run our app with hyper
     // `axum::Server` is a re-export of `hyper::Server`
     info!("listening on http://{}", &addr);
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
+        // Stop accepting new connections once we're asked to stop, but let the requests already running finish.
+        .with_graceful_shutdown(shutdown_signal(shutting_down_sender))
         .await
         .unwrap();
+    info!("server stopped");
+}
+
+/// Resolves when the process is asked to stop: Ctrl+C in the terminal (SIGINT), or SIGTERM, which is
+/// what Docker, systemd and friends send on a deploy. Then tells the responses that wait for it through
+/// `shutting_down`.
+#[cfg(feature = "ssr")]
+async fn shutdown_signal(shutting_down: tokio::sync::watch::Sender<bool>) {
+    use log::info;
+    use tokio::signal;
+
+    let ctrl_c = async {
+        signal::ctrl_c().await.expect("couldn't listen for Ctrl+C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("couldn't listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    // There is no SIGTERM on Windows, wait for Ctrl+C only.
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("shutting down, finishing requests in flight");
+    // Nobody listening is fine, there may be no open event streams.
+    _ = shutting_down.send(true);
 }
 
 #[cfg(not(feature = "ssr"))]
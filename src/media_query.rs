@@ -0,0 +1,28 @@
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Whether a CSS media query matches, e.g. `use_media_query(cx, "(min-width: 768px)")`, updated
+/// when that changes (window resized, OS switched to dark mode, ...). Always `false` on the server.
+pub fn use_media_query(cx: Scope, query: &str) -> ReadSignal<bool> {
+    let (matches, set_matches) = create_signal(cx, false);
+    if leptos_dom::is_server() {
+        return matches;
+    }
+    // `None` for a query the browser doesn't understand, it never matches.
+    let Ok(Some(list)) = window().match_media(query) else {
+        return matches;
+    };
+    set_matches(list.matches());
+
+    let on_change = {
+        let list = list.clone();
+        Closure::<dyn Fn()>::new(move || set_matches(list.matches()))
+    };
+    _ = list.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    // Remove the listener when the component goes away, like in `use_window_size`.
+    on_cleanup(cx, move || {
+        _ = list.remove_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    });
+
+    matches
+}
@@ -0,0 +1,113 @@
+use cfg_if::cfg_if;
+
+// Tower/Axum layers wrapped around every request, added in main.rs.
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+    use axum::http::{HeaderValue, Request};
+    use axum::middleware::Next;
+    use axum::response::Response;
+    use log::info;
+    use std::time::Instant;
+
+    /// Logs one line per request once it is answered, e.g. `GET /api/health 200 OK 1.2ms`.
+    pub async fn log_requests<B>(request: Request<B>, next: Next<B>) -> Response {
+        // The request is moved into `next.run`, so grab what we want to log first.
+        let method = request.method().clone();
+        let path = request.uri().path().to_owned();
+        let start = Instant::now();
+        let response = next.run(request).await;
+        // For a streamed response (server-sent events, the streamed HTML) this is the time until the
+        // response started, not until the last byte was sent.
+        info!("{method} {path} {} {:.1?}", response.status(), start.elapsed());
+        response
+    }
+
+    /// Tells browsers how long they may keep a response without asking again (`Cache-Control`).
+    ///
+    /// - HTML pages: always ask (`no-cache`), otherwise a deploy would be invisible until the cache expires.
+    ///   The browser may still keep a copy and get a quick "304 Not Modified" when nothing changed.
+    /// - Fingerprinted files in /pkg (a hash in the name, e.g. `tailwind.3f9a2b1c.wasm`): keep for a year.
+    ///   A new build gets a new name, so a cached copy is never out of date.
+    /// - Other files in /pkg: cargo-leptos doesn't fingerprint by default, so these have the same name
+    ///   after a deploy and have to be checked like the HTML.
+    /// - Anything else (favicon, images): an hour.
+    ///
+    /// Responses that already have a `Cache-Control` (e.g. server-sent events), errors and `/api` are left alone.
+    pub async fn cache_control<B>(request: Request<B>, next: Next<B>) -> Response {
+        let path = request.uri().path().to_owned();
+        let mut response = next.run(request).await;
+        if !response.status().is_success()
+            || response.headers().contains_key(CACHE_CONTROL)
+            || path.starts_with("/api/")
+        {
+            return response;
+        }
+        // Leptos streams its pages without a `Content-Type`, so a path without a file extension counts as a page too.
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("text/html"))
+            || !file_name(&path).contains('.');
+        let value = if is_html {
+            "no-cache"
+        } else if path.starts_with("/pkg/") {
+            if is_fingerprinted(&path) {
+                "public, max-age=31536000, immutable"
+            } else {
+                "no-cache"
+            }
+        } else {
+            "public, max-age=3600"
+        };
+        response.headers_mut().insert(CACHE_CONTROL, HeaderValue::from_static(value));
+        response
+    }
+
+    /// Whether the file name has a content hash between its name and extension, like `app.3f9a2b1c.js`.
+    /// A hash is 8 or more hex digits. It has at least one digit, so a word like `app.minified.js` or
+    /// `jquery.deadbeef.js` doesn't count: caching a file that keeps its name across deploys for a year
+    /// would serve the old one long after it changed.
+    fn is_fingerprinted(path: &str) -> bool {
+        let parts: Vec<&str> = file_name(path).split('.').collect();
+        parts.len() >= 3
+            && parts[1..parts.len() - 1].iter().any(|part| {
+                part.len() >= 8
+                    && part.chars().all(|c| c.is_ascii_hexdigit())
+                    && part.chars().any(|c| c.is_ascii_digit())
+            })
+    }
+
+    fn file_name(path: &str) -> &str {
+        path.rsplit('/').next().unwrap_or_default()
+    }
+}}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_hash_between_name_and_extension_is_a_fingerprint() {
+        assert!(is_fingerprinted("/pkg/tailwind.3f9a2b1c.wasm"));
+        assert!(is_fingerprinted("/pkg/app.3F9A2B1C0D4E5F60.js"));
+        assert!(is_fingerprinted("/pkg/app.12345678.min.js"));
+    }
+
+    #[test]
+    fn words_are_not_fingerprints() {
+        assert!(!is_fingerprinted("/pkg/app.minified.js"));
+        assert!(!is_fingerprinted("/pkg/jquery.combined.js"));
+        // Hex digits only, but no digit among them: a word too.
+        assert!(!is_fingerprinted("/pkg/app.deadbeef.js"));
+    }
+
+    #[test]
+    fn a_fingerprint_needs_a_name_an_extension_and_eight_hex_digits() {
+        assert!(!is_fingerprinted("/pkg/3f9a2b1c.wasm"));
+        assert!(!is_fingerprinted("/pkg/app.3f9a2b1.js"));
+        assert!(!is_fingerprinted("/pkg/app.3f9a2b1g.js"));
+        assert!(!is_fingerprinted("/pkg/leptos_tailwind_axum.js"));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::focus::trap_focus;
+use leptos::html::Div;
+use leptos::*;
+
+/// A dialog rendered on top of the page while `open` is true. Clicking the backdrop or pressing
+/// Escape closes it, and keyboard focus stays inside the dialog while it is open.
+#[component]
+pub fn Modal(
+    cx: Scope,
+    /// Whether the modal is shown. The modal sets it to `false` when the user dismisses it.
+    open: RwSignal<bool>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    // `Show` needs to be able to render the children every time the modal opens, hence `ChildrenFn`
+    // and the `StoredValue` (so the closure below stays `Fn`).
+    let children = store_value(cx, children);
+
+    view! { cx,
+        <Show when=move || open.get() fallback=|_| ()>
+            <ModalPanel open=open>{children.with_value(|children| children(cx))}</ModalPanel>
+        </Show>
+    }
+}
+
+// Split out of `Modal` so that it gets its own scope: `trap_focus` runs when the panel mounts and
+// cleans up (restoring focus) when `Show` removes it.
+#[component]
+fn ModalPanel(cx: Scope, open: RwSignal<bool>, children: Children) -> impl IntoView {
+    let panel = create_node_ref::<Div>(cx);
+    trap_focus(cx, panel);
+
+    view! { cx,
+        <div
+            class="fixed inset-0 flex items-center justify-center bg-black/50"
+            on:click=move |_| open.set(false)
+        >
+            <div
+                node_ref=panel
+                role="dialog"
+                aria-modal="true"
+                class="p-6 bg-white rounded shadow-lg min-w-[20rem]"
+                // Stop the click from reaching the backdrop, otherwise clicking inside would close the modal.
+                on:click=|event| event.stop_propagation()
+                on:keydown=move |event| {
+                    if event.key() == "Escape" {
+                        open.set(false);
+                    }
+                }
+            >
+                {children(cx)}
+            </div>
+        </div>
+    }
+}
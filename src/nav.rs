@@ -0,0 +1,40 @@
+use leptos::*;
+use leptos_router::{use_location, A};
+
+/// A bar with links to Home, to each of `pages` (path and title) and to the settings. The link to
+/// the page you are on is highlighted.
+#[component]
+pub fn NavBar(cx: Scope, pages: Vec<(&'static str, &'static str)>) -> impl IntoView {
+    let pathname = use_location(cx).pathname;
+
+    let link = move |path: &'static str, title: &'static str| {
+        let active = move || pathname.with(|current| current == path);
+        view! { cx,
+            <li class="shrink-0">
+                // `exact`: every path starts with "/", without it Home would count as the current page everywhere.
+                <A
+                    href=path
+                    exact=true
+                    class=move || {
+                        let base = "block px-2 py-1 rounded hover:bg-gray-100 dark:hover:bg-gray-800";
+                        if active() { format!("{base} font-bold text-blue-700") } else { base.to_string() }
+                    }
+                >
+                    {title}
+                </A>
+            </li>
+        }
+    };
+
+    view! { cx,
+        // Many pages and a narrow window: the bar scrolls sideways instead of wrapping onto several lines. The space on
+        // the right is for the star and the theme toggle, which sit on top of it.
+        <nav class="pr-20 overflow-x-auto border-b print:hidden" aria-label="Examples">
+            <ul class="flex gap-1 p-1 text-sm whitespace-nowrap">
+                {link("/", "Home")}
+                {pages.into_iter().map(|(path, title)| link(path, title)).collect_view(cx)}
+                {link("/settings", "Settings")}
+            </ul>
+        </nav>
+    }
+}
@@ -0,0 +1,24 @@
+use leptos::*;
+use leptos_meta::Title;
+use leptos_router::{use_location, A};
+
+/// The page for a URL that none of the routes match, e.g. a typo.
+#[component]
+pub fn NotFound(cx: Scope) -> impl IntoView {
+    // A blank page with a "200 OK" would tell search engines and link checkers that the page exists. The server renders
+    // this page with a 404 status instead. In the browser the page is already loaded, there is no status to set.
+    #[cfg(feature = "ssr")]
+    if let Some(response) = use_context::<leptos_axum::ResponseOptions>(cx) {
+        response.set_status(axum::http::StatusCode::NOT_FOUND);
+    }
+    let path = use_location(cx).pathname;
+
+    view! { cx,
+        <Title text="404 — Not Found"/>
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"404 — Not Found"</h1>
+            <p class="mt-4">"There is no page at " <code>{path}</code> "."</p>
+            <A href="/" class="inline-block mt-4 text-blue-700 underline">"Back to Home"</A>
+        </main>
+    }
+}
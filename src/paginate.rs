@@ -0,0 +1,278 @@
+use crate::fetch::FetchError;
+use leptos::html::Div;
+use leptos::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// Loading a cursor-paginated API one page at a time. Such an API answers each request with a page of
+// items plus a cursor, and you pass that cursor back to get the next page. The last page has no cursor.
+// `use_paginated` keeps track of the cursor and collects all the pages loaded so far into one list,
+// `InfiniteScroll` loads the next page when the user scrolls to the bottom of that list.
+//
+// Every time a page arrives, the one after it is fetched right away in the background (prefetched) and
+// kept until it is asked for, so reaching the bottom shows the next page without waiting. Only one page
+// is fetched ahead, and none after the last page. If the next page is asked for while its prefetch is
+// still on its way, we wait for that request instead of sending a second one.
+
+/// One page of results. `next` is the cursor of the following page, `None` on the last page.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+/// The page after the last one loaded, see `Paginated::prefetch`.
+enum Prefetch<T> {
+    None,
+    /// On its way. `wanted` is set when `load_more` asked for it before it arrived.
+    Loading {
+        cursor: String,
+        wanted: bool,
+    },
+    /// Arrived, waiting for `load_more`.
+    Ready {
+        cursor: String,
+        page: Page<T>,
+    },
+}
+
+type PageFuture<T> = Pin<Box<dyn Future<Output = Result<Page<T>, FetchError>>>>;
+type FetchPage<T> = Rc<dyn Fn(Option<String>) -> PageFuture<T>>;
+
+pub struct Paginated<T: 'static> {
+    items: RwSignal<Vec<T>>,
+    next: StoredValue<Option<String>>,
+    has_more: RwSignal<bool>,
+    loading: RwSignal<bool>,
+    error: RwSignal<Option<FetchError>>,
+    fetch_page: StoredValue<FetchPage<T>>,
+    prefetch: RwSignal<Prefetch<T>>,
+}
+
+// Written out by hand because `#[derive(Copy)]` would require `T: Copy`, which we don't need.
+impl<T> Clone for Paginated<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Paginated<T> {}
+
+impl<T> Paginated<T> {
+    /// Everything loaded so far, all pages in order.
+    pub fn items(&self) -> ReadSignal<Vec<T>> {
+        self.items.read_only()
+    }
+
+    /// `false` once the last page has been loaded.
+    pub fn has_more(&self) -> bool {
+        self.has_more.get()
+    }
+
+    pub fn loading(&self) -> bool {
+        self.loading.get()
+    }
+
+    /// Why the last page failed to load, cleared by the next `load_more`.
+    pub fn error(&self) -> Option<FetchError> {
+        self.error.get()
+    }
+
+    /// Whether the next page has been prefetched, `load_more` will show it right away.
+    pub fn next_prefetched(&self) -> bool {
+        self.prefetch
+            .with(|prefetch| matches!(prefetch, Prefetch::Ready { .. }))
+    }
+
+    /// Fetches the next page and appends it to `items`. Does nothing while a page is already
+    /// loading or when there are no more pages.
+    pub fn load_more(&self) {
+        if self.loading.get_untracked() || !self.has_more.get_untracked() {
+            return;
+        }
+        self.error.set(None);
+
+        let cursor = self.next.get_value();
+        let prefetch = self
+            .prefetch
+            .try_update(|prefetch| std::mem::replace(prefetch, Prefetch::None))
+            .unwrap_or(Prefetch::None);
+        match prefetch {
+            Prefetch::Ready {
+                cursor: fetched,
+                page,
+            } if Some(&fetched) == cursor.as_ref() => {
+                self.show(page);
+                return;
+            }
+            Prefetch::Loading {
+                cursor: fetching, ..
+            } if Some(&fetching) == cursor.as_ref() => {
+                // `prefetch` shows the page when it arrives.
+                self.loading.set(true);
+                self.prefetch.set(Prefetch::Loading {
+                    cursor: fetching,
+                    wanted: true,
+                });
+                return;
+            }
+            // Nothing prefetched, or a page we no longer need: it is dropped.
+            _ => {}
+        }
+
+        self.loading.set(true);
+        let page = self.fetch_page.with_value(|fetch_page| fetch_page(cursor));
+        let this = *self;
+        spawn_local(async move {
+            let result = page.await;
+            // The component may have been removed while we were waiting, then there is nothing to update.
+            if this.loading.try_get_untracked().is_none() {
+                return;
+            }
+            match result {
+                Ok(page) => this.show(page),
+                Err(error) => this.error.set(Some(error)),
+            }
+            this.loading.set(false);
+        });
+    }
+
+    /// Appends `page` to `items` and starts prefetching the one after it.
+    fn show(&self, Page { items, next }: Page<T>) {
+        self.items.update(|all| all.extend(items));
+        self.has_more.set(next.is_some());
+        self.next.set_value(next);
+        self.prefetch();
+    }
+
+    /// Fetches the page after the last one loaded in the background, unless that was the last page.
+    fn prefetch(&self) {
+        let Some(cursor) = self.next.get_value() else {
+            return;
+        };
+        self.prefetch.set(Prefetch::Loading {
+            cursor: cursor.clone(),
+            wanted: false,
+        });
+
+        let page = self
+            .fetch_page
+            .with_value(|fetch_page| fetch_page(Some(cursor.clone())));
+        let this = *self;
+        spawn_local(async move {
+            let result = page.await;
+            // Gone with the component, or no longer the page we are waiting for: forget it.
+            let Some(Some(wanted)) = this.prefetch.try_with_untracked(|prefetch| match prefetch {
+                Prefetch::Loading {
+                    cursor: fetching,
+                    wanted,
+                } if *fetching == cursor => Some(*wanted),
+                _ => None,
+            }) else {
+                return;
+            };
+
+            if wanted {
+                // `load_more` is waiting for this page, show it like a page it fetched itself.
+                this.prefetch.set(Prefetch::None);
+                match result {
+                    Ok(page) => this.show(page),
+                    Err(error) => this.error.set(Some(error)),
+                }
+                this.loading.set(false);
+            } else {
+                // A prefetch that failed is just forgotten: `load_more` will ask again, and show the error if that fails
+                // too. Nobody asked for this page yet, an error now would be for something the user didn't do.
+                this.prefetch.set(match result {
+                    Ok(page) => Prefetch::Ready { cursor, page },
+                    Err(_) => Prefetch::None,
+                });
+            }
+        });
+    }
+}
+
+/// Starts loading `fetch_page(None)` (the first page) right away in the browser. Every
+/// `load_more` after that calls `fetch_page` with the cursor the previous page returned, or gets
+/// that page from the prefetch.
+pub fn use_paginated<T, F, Fut>(cx: Scope, fetch_page: F) -> Paginated<T>
+where
+    F: Fn(Option<String>) -> Fut + 'static,
+    Fut: Future<Output = Result<Page<T>, FetchError>> + 'static,
+{
+    let fetch_page: FetchPage<T> = Rc::new(move |cursor| Box::pin(fetch_page(cursor)));
+    let paginated = Paginated {
+        items: create_rw_signal(cx, Vec::new()),
+        next: store_value(cx, None),
+        has_more: create_rw_signal(cx, true),
+        loading: create_rw_signal(cx, false),
+        error: create_rw_signal(cx, None),
+        fetch_page: store_value(cx, fetch_page),
+        prefetch: create_rw_signal(cx, Prefetch::None),
+    };
+    if !leptos_dom::is_server() {
+        paginated.load_more();
+    }
+    paginated
+}
+
+/// Put this below the list of `paginated.items()`. When it scrolls into view the next page is
+/// loaded. It also shows the loading state and a button to try again if a page failed.
+#[component]
+pub fn InfiniteScroll<T: 'static>(cx: Scope, paginated: Paginated<T>) -> impl IntoView {
+    let sentinel = create_node_ref::<Div>(cx);
+    let (visible, set_visible) = create_signal(cx, false);
+
+    // The observer only tells us when the sentinel comes into view or leaves it. If it is still in
+    // view after a page has loaded (a short page, a tall window), we have to keep loading ourselves.
+    create_effect(cx, move |_| {
+        if visible.get()
+            && paginated.has_more()
+            && !paginated.loading()
+            && paginated.error().is_none()
+        {
+            paginated.load_more();
+        }
+    });
+
+    sentinel.on_load(cx, move |sentinel| {
+        let on_intersect = Closure::<dyn Fn(js_sys::Array)>::new(move |entries: js_sys::Array| {
+            for entry in entries.iter() {
+                let entry: web_sys::IntersectionObserverEntry = entry.unchecked_into();
+                set_visible(entry.is_intersecting());
+            }
+        });
+        let Ok(observer) =
+            web_sys::IntersectionObserver::new(on_intersect.as_ref().unchecked_ref())
+        else {
+            return;
+        };
+        observer.observe(&sentinel);
+        on_cleanup(cx, move || {
+            observer.disconnect();
+            // Keep the callback alive until the observer is gone.
+            drop(on_intersect);
+        });
+    });
+
+    view! { cx,
+        <div node_ref=sentinel class="py-4 text-center text-gray-500">
+            {move || match paginated.error() {
+                Some(error) => view! { cx,
+                    <p class="text-red-700">{error.to_string()}</p>
+                    <button
+                        class="px-3 py-2 mt-2 text-white bg-blue-700 rounded shadow"
+                        on:click=move |_| paginated.load_more()
+                    >
+                        "Try again"
+                    </button>
+                }
+                .into_view(cx),
+                None if paginated.loading() => "Loading...".into_view(cx),
+                None if !paginated.has_more() => "That's everything".into_view(cx),
+                None => ().into_view(cx),
+            }}
+        </div>
+    }
+}
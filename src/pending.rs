@@ -0,0 +1,56 @@
+use crate::context::expect_context;
+use leptos::*;
+
+// App-wide tracking of in-flight async work (fetches, actions). `App` provides the context and every
+// async task holds a `PendingGuard` while it runs. The count is shown as a badge in the document title.
+
+/// Context holding the number of async operations that are currently running.
+#[derive(Copy, Clone)]
+pub struct PendingAsyncContext(RwSignal<usize>);
+
+impl PendingAsyncContext {
+    /// Number of running operations. Reading it inside a closure makes that closure reactive.
+    pub fn count(&self) -> usize {
+        self.0.get()
+    }
+
+    /// Marks the start of an async operation. Keep the returned guard alive until the operation is
+    /// done; dropping it (normally, on an early `?` return, or when the future is cancelled)
+    /// decrements the count again, so we can never "forget" to do it on an error path.
+    pub fn start(&self) -> PendingGuard {
+        self.0.update(|count| *count += 1);
+        PendingGuard(self.0)
+    }
+}
+
+/// Decrements the pending count when dropped. See `PendingAsyncContext::start`.
+#[must_use = "the operation is considered finished as soon as the guard is dropped"]
+pub struct PendingGuard(RwSignal<usize>);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        // `try_update` because the guard may outlive the scope that owns the signal.
+        self.0.try_update(|count| *count = count.saturating_sub(1));
+    }
+}
+
+/// Provides the pending counter to every component below `cx`.
+pub fn provide_pending_async(cx: Scope) -> PendingAsyncContext {
+    let pending = PendingAsyncContext(create_rw_signal(cx, 0));
+    provide_context(cx, pending);
+    pending
+}
+
+/// Pulls the pending counter out of context.
+pub fn use_pending_async(cx: Scope) -> PendingAsyncContext {
+    expect_context::<PendingAsyncContext>(cx, "App")
+}
+
+/// Prefixes a title with the pending count, e.g. "(2) Leptos Examples". Nothing is added when idle.
+pub fn title_with_badge(count: usize, title: String) -> String {
+    if count == 0 {
+        title
+    } else {
+        format!("({count}) {title}")
+    }
+}
@@ -0,0 +1,133 @@
+use leptos::*;
+use leptos_router::{use_location, use_navigate, use_query_map, NavigateOptions, ParamsMap};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Permalinks keep an example's state in the URL query string, so copying the address bar gives
+// you a link that restores exactly what you were looking at (e.g. `/control_flow?date=2023-07-23`).
+
+/// Creates a signal backed by the query parameter `key`. Simple values (numbers, text, dates) are
+/// stored as they are formatted by `Display` and read back with `FromStr`.
+pub fn use_permalink<T>(cx: Scope, key: &'static str, default: T) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Clone + PartialEq + Display + FromStr + 'static,
+{
+    use_permalink_with(
+        cx,
+        key,
+        default,
+        |value| value.to_string(),
+        |raw| raw.parse().ok(),
+    )
+}
+
+/// Composite state kept in several query parameters, e.g. the filters, sort order and page of a
+/// table. See `use_permalink_state`.
+pub trait QueryState: Clone + PartialEq + Default + 'static {
+    /// Reads the state from the query. Missing or malformed parameters fall back to their defaults.
+    fn from_query(query: &ParamsMap) -> Self;
+    /// Writes the state into `query`. Parameters at their default should be removed, which keeps
+    /// links short. Parameters the state doesn't own must be left alone.
+    fn to_query(&self, query: &mut ParamsMap);
+}
+
+/// Creates a signal backed by all the query parameters of `T`. Every change of the signal updates
+/// them in one go, and every URL change (back/forward, a pasted link) updates the signal.
+pub fn use_permalink_state<T: QueryState>(cx: Scope) -> (ReadSignal<T>, WriteSignal<T>) {
+    sync_with_query(
+        cx,
+        |query| Some(T::from_query(query)),
+        |state, query| state.to_query(query),
+        T::default(),
+    )
+}
+
+fn use_permalink_with<T>(
+    cx: Scope,
+    key: &'static str,
+    default: T,
+    encode: fn(&T) -> String,
+    decode: fn(&str) -> Option<T>,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Clone + PartialEq + 'static,
+{
+    let default_value = default.clone();
+    sync_with_query(
+        cx,
+        move |query| query.get(key).and_then(|raw| decode(raw)),
+        // The default value is left out of the URL so untouched examples keep clean links.
+        move |value, query| {
+            if *value == default_value {
+                query.remove(key);
+            } else {
+                query.insert(key.to_string(), encode(value));
+            }
+        },
+        default,
+    )
+}
+
+/// Keeps a signal and the query string in sync. `read` gets the value from the query (`None` means
+/// `default`), `write` puts it back.
+fn sync_with_query<T>(
+    cx: Scope,
+    read: impl Fn(&ParamsMap) -> Option<T> + 'static,
+    write: impl Fn(&T, &mut ParamsMap) + 'static,
+    default: T,
+) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Clone + PartialEq + 'static,
+{
+    let query = use_query_map(cx);
+    let location = use_location(cx);
+    let navigate = use_navigate(cx);
+
+    // Restore on mount: a value in the URL wins over the default.
+    let initial = query
+        .with_untracked(&read)
+        .unwrap_or_else(|| default.clone());
+    let (value, set_value) = create_signal(cx, initial);
+
+    // URL -> signal, for the back/forward buttons. A URL without the value means the default, that's
+    // how the value is left out of it. We only write when the value actually differs, otherwise the
+    // two effects would keep triggering each other.
+    create_effect(cx, move |_| {
+        let from_url = query.with(&read).unwrap_or_else(|| default.clone());
+        if value.with_untracked(|value| *value != from_url) {
+            set_value(from_url);
+        }
+    });
+
+    // Signal -> URL.
+    create_effect(cx, move |previous: Option<()>| {
+        let mut params = query.get_untracked();
+        value.with(|value| write(value, &mut params));
+        // The first run happens while the route is still rendering and the URL already matches
+        // the signal, so there is nothing to do (navigating here would also upset the router).
+        if previous.is_none() {
+            return;
+        }
+        // Same query string as now, e.g. because this change came from the URL in the first place.
+        if query.with_untracked(|query| query.to_query_string()) == params.to_query_string() {
+            return;
+        }
+
+        let url = format!(
+            "{}{}",
+            location.pathname.get_untracked(),
+            params.to_query_string()
+        );
+        // `replace` so that every click on `+` doesn't add a new history entry.
+        let _ = navigate(
+            &url,
+            NavigateOptions {
+                replace: true,
+                scroll: false,
+                ..Default::default()
+            },
+        );
+    });
+
+    (value, set_value)
+}
@@ -0,0 +1,121 @@
+use crate::context::expect_context;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Preferences that don't belong to any one feature, set at /settings: how often pages that show live
+// data fetch it again, and whether to cut animations short. Saved in localStorage as one JSON object.
+//
+// Like the feature flags, the server only knows the defaults. The saved preferences are applied after
+// the first frame, once the page has hydrated with the defaults just like the server rendered them.
+
+const STORAGE_KEY: &str = "preferences";
+
+/// The choices for the auto-refresh interval, in seconds. `None` is off.
+pub const REFRESH_INTERVALS: [(Option<u64>, &str); 4] = [
+    (None, "Off"),
+    (Some(30), "Every 30 seconds"),
+    (Some(60), "Every minute"),
+    (Some(300), "Every 5 minutes"),
+];
+
+/// What is saved in localStorage.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Saved {
+    refresh_seconds: Option<u64>,
+    reduce_motion: bool,
+}
+
+#[derive(Copy, Clone)]
+pub struct Preferences {
+    /// How often pages with live data fetch it again (see `use_auto_refresh`), `None` for never.
+    pub refresh_interval: RwSignal<Option<Duration>>,
+    /// Animations and transitions are cut to 1ms, like with reduced motion turned on in the OS.
+    pub reduce_motion: RwSignal<bool>,
+}
+
+/// Provides the preferences to every component below `cx`.
+pub fn provide_preferences(cx: Scope) -> Preferences {
+    let preferences = Preferences {
+        refresh_interval: create_rw_signal(cx, None),
+        reduce_motion: create_rw_signal(cx, false),
+    };
+    provide_context(cx, preferences);
+
+    if !leptos_dom::is_server() {
+        request_animation_frame(move || {
+            if let Some(saved) = stored_preferences() {
+                _ = preferences
+                    .refresh_interval
+                    .try_set(saved.refresh_seconds.map(Duration::from_secs));
+                _ = preferences.reduce_motion.try_set(saved.reduce_motion);
+            }
+        });
+    }
+
+    // The `reduce-motion` class on `<html>` does the cutting, see input.css.
+    create_effect(cx, move |_| {
+        if let Some(root) = document().document_element() {
+            _ = root
+                .class_list()
+                .toggle_with_force("reduce-motion", preferences.reduce_motion.get());
+        }
+    });
+    // `previous` is `None` on the first run, the defaults don't need saving.
+    create_effect(cx, move |previous: Option<Saved>| {
+        let saved = Saved {
+            refresh_seconds: preferences
+                .refresh_interval
+                .get()
+                .map(|interval| interval.as_secs()),
+            reduce_motion: preferences.reduce_motion.get(),
+        };
+        if previous.is_some_and(|previous| previous != saved) {
+            if let Some((storage, json)) = window()
+                .local_storage()
+                .ok()
+                .flatten()
+                .zip(serde_json::to_string(&saved).ok())
+            {
+                _ = storage.set_item(STORAGE_KEY, &json);
+            }
+        }
+        saved
+    });
+
+    preferences
+}
+
+/// Pulls the preferences out of context.
+pub fn use_preferences(cx: Scope) -> Preferences {
+    expect_context::<Preferences>(cx, "App")
+}
+
+/// Calls `refresh` at the auto-refresh interval picked at /settings, for as long as `cx` lives. Another
+/// interval picked there replaces the timer right away, "Off" stops it.
+pub fn use_auto_refresh(cx: Scope, refresh: impl Fn() + Clone + 'static) {
+    let preferences = use_preferences(cx);
+    let timer = store_value(cx, None::<IntervalHandle>);
+    create_effect(cx, move |_| {
+        if let Some(previous) = timer.get_value() {
+            previous.clear();
+        }
+        let refresh = refresh.clone();
+        let handle = preferences
+            .refresh_interval
+            .get()
+            .and_then(|interval| set_interval_with_handle(refresh, interval).ok());
+        timer.set_value(handle);
+    });
+    on_cleanup(cx, move || {
+        if let Some(timer) = timer.get_value() {
+            timer.clear();
+        }
+    });
+}
+
+fn stored_preferences() -> Option<Saved> {
+    let storage = window().local_storage().ok().flatten()?;
+    serde_json::from_str(&storage.get_item(STORAGE_KEY).ok().flatten()?).ok()
+}
@@ -0,0 +1,62 @@
+use cfg_if::cfg_if;
+
+// Static site generation: render some pages to HTML files once, at build time, instead of on every
+// request. The server hands out the file straight away and the page hydrates as usual once the WASM
+// has loaded.
+//
+//     cargo leptos build --release
+//     LEPTOS_OUTPUT_NAME=tailwind target/release/leptos_tailwind_axum --prerender
+//
+// writes e.g. target/site/pass_children/index.html. Run it again after every build, otherwise the
+// server keeps handing out the old pages.
+
+/// The pages that are pre-rendered. A page qualifies when its first render is the same for every
+/// visitor. The counter, text input and control flow pages keep their state in the URL
+/// (`?value=3`), but the static file is the same whatever the query string says, so they are always
+/// rendered on request.
+#[cfg(feature = "examples-basic")]
+pub const STATIC_ROUTES: [&str; 2] = ["/parent_child_communication", "/pass_children"];
+/// Both pages belong to the `examples-basic` feature, without it there is nothing to pre-render.
+#[cfg(not(feature = "examples-basic"))]
+pub const STATIC_ROUTES: [&str; 0] = [];
+
+cfg_if! { if #[cfg(feature = "ssr")] {
+    use crate::app::App;
+    use axum::body::{Body, HttpBody};
+    use axum::http::Request;
+    use leptos::{view, LeptosOptions};
+    use log::info;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// Where the pre-rendered HTML of `route` goes, e.g. `target/site/pass_children/index.html`.
+    pub fn static_file(site_root: &str, route: &str) -> PathBuf {
+        Path::new(site_root).join(route.trim_start_matches('/')).join("index.html")
+    }
+
+    /// Renders every page in `STATIC_ROUTES` to its `static_file`.
+    pub async fn prerender(options: &LeptosOptions) -> io::Result<()> {
+        // The same handler that renders pages on request, we just call it ourselves.
+        let render = leptos_axum::render_app_to_stream(options.clone(), |cx| view! { cx, <App/> });
+        for route in STATIC_ROUTES {
+            let request = Request::builder()
+                .uri(route)
+                .body(Body::empty())
+                .map_err(io::Error::other)?;
+            let mut body = render(request).await.into_body();
+            // The page comes in chunks as it is rendered, collect them all.
+            let mut html = Vec::new();
+            while let Some(chunk) = body.data().await {
+                html.extend_from_slice(&chunk.map_err(io::Error::other)?);
+            }
+
+            let file = static_file(&options.site_root, route);
+            if let Some(directory) = file.parent() {
+                tokio::fs::create_dir_all(directory).await?;
+            }
+            tokio::fs::write(&file, html).await?;
+            info!("pre-rendered {route} to {}", file.display());
+        }
+        Ok(())
+    }
+}}
@@ -0,0 +1,303 @@
+use leptos::*;
+use std::collections::HashMap;
+
+// Makes reactivity visible: a small graph of signals, memos and effects drawn as an SVG, where the nodes
+// that ran in the last update light up.
+//
+// Leptos doesn't hand out its dependency graph, so the graph is declared next to the code it describes
+// (`GraphNode`s and the edges between them), and the code reports when it runs: every memo and effect
+// calls `tracker.ran("id")` first thing, every click calls `tracker.update("id")` before setting the
+// signal. A node ran in the current update when it reported in since the last `update`.
+//
+// Reporting must not set a signal: that would happen while a memo is running, and the graph itself
+// would be part of the graph. The runs are kept in plain stored values and copied into a signal for
+// the view once per animation frame, after everything that was going to run has run.
+//
+// The tracker also keeps the runs of the latest update in order, for `<UpdateSteps/>` to step through.
+// Leptos runs an update all at once, there is no pausing it halfway, so stepping is a replay: the
+// update has already happened, and each step shows one more of the runs it was made of.
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NodeKind {
+    Signal,
+    Memo,
+    Effect,
+}
+
+/// A box in the graph. Signals go in the first column, what reads them in the columns after it.
+#[derive(Copy, Clone, Debug)]
+pub struct GraphNode {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub kind: NodeKind,
+    pub column: u8,
+    pub row: u8,
+}
+
+/// How often each node ran, and in which update it ran last.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Runs {
+    update: u32,
+    runs: HashMap<&'static str, (u32, u32)>,
+    /// The runs of the latest update in the order they happened, with what each one reported.
+    order: Vec<(&'static str, String)>,
+}
+
+/// Collects the runs of the nodes, see `use_tracker`.
+#[derive(Copy, Clone)]
+pub struct Tracker {
+    pending: StoredValue<Runs>,
+    scheduled: StoredValue<bool>,
+    shown: RwSignal<Runs>,
+}
+
+impl Tracker {
+    /// Starts a new update, caused by writing to the signal `id`. Only what runs from now on lights up.
+    pub fn update(&self, id: &'static str) {
+        self.pending.update_value(|pending| {
+            pending.update += 1;
+            pending.order.clear();
+        });
+        self.ran_with(id, format!("{id} is set"));
+    }
+
+    /// Records that the node `id` ran.
+    pub fn ran(&self, id: &'static str) {
+        self.ran_with(id, id.to_string());
+    }
+
+    /// Records that the node `id` ran, with a line for the list of steps, e.g. what it computed.
+    pub fn ran_with(&self, id: &'static str, step: String) {
+        self.pending.update_value(|pending| {
+            let update = pending.update;
+            let (count, last) = pending.runs.entry(id).or_default();
+            *count += 1;
+            *last = update;
+            pending.order.push((id, step));
+        });
+        self.schedule();
+    }
+
+    /// How often `id` ran so far.
+    fn count(&self, id: &str) -> u32 {
+        self.shown
+            .with(|shown| shown.runs.get(id).map_or(0, |(count, _)| *count))
+    }
+
+    /// Whether `id` ran in the latest update.
+    fn ran_last(&self, id: &str) -> bool {
+        self.shown.with(|shown| {
+            shown
+                .runs
+                .get(id)
+                .is_some_and(|(_, last)| *last == shown.update)
+        })
+    }
+
+    /// Whether `id` is among the first `steps` runs of the latest update.
+    fn ran_within(&self, id: &str, steps: usize) -> bool {
+        self.shown
+            .with(|shown| shown.order.iter().take(steps).any(|(ran, _)| *ran == id))
+    }
+
+    /// How many runs the latest update was made of.
+    fn step_count(&self) -> usize {
+        self.shown.with(|shown| shown.order.len())
+    }
+
+    fn schedule(&self) {
+        // The server has no frames to wait for, it renders what there is.
+        if leptos_dom::is_server() || self.scheduled.get_value() {
+            return;
+        }
+        self.scheduled.set_value(true);
+        let this = *self;
+        request_animation_frame(move || {
+            // The page may be gone by the next frame.
+            if this.scheduled.try_set_value(false).is_none() {
+                this.shown.set(this.pending.get_value());
+            }
+        });
+    }
+}
+
+/// A tracker for the nodes of one graph.
+pub fn use_tracker(cx: Scope) -> Tracker {
+    Tracker {
+        pending: store_value(cx, Runs::default()),
+        scheduled: store_value(cx, false),
+        shown: create_rw_signal(cx, Runs::default()),
+    }
+}
+
+const NODE_WIDTH: f64 = 140.0;
+const NODE_HEIGHT: f64 = 44.0;
+const COLUMN_GAP: f64 = 60.0;
+const ROW_GAP: f64 = 16.0;
+
+/// Top left corner of a node.
+fn position(node: &GraphNode) -> (f64, f64) {
+    (
+        f64::from(node.column) * (NODE_WIDTH + COLUMN_GAP),
+        f64::from(node.row) * (NODE_HEIGHT + ROW_GAP),
+    )
+}
+
+/// `nodes` and the `edges` between them (from the node that is read to the one that reads it), with
+/// the nodes that ran in the latest update of `tracker` highlighted and the number of runs in each.
+#[component]
+pub fn ReactiveGraph(
+    cx: Scope,
+    tracker: Tracker,
+    nodes: Vec<GraphNode>,
+    edges: Vec<(&'static str, &'static str)>,
+    /// When stepping through the update with `<UpdateSteps/>`: how many of its runs are shown. Only
+    /// those light up.
+    #[prop(optional, into)]
+    steps: Option<Signal<usize>>,
+) -> impl IntoView {
+    let ran = move |id: &str| match steps {
+        Some(steps) => tracker.ran_within(id, steps.get()),
+        None => tracker.ran_last(id),
+    };
+    // Arrow heads are referred to by id, each graph on the page needs its own.
+    let arrow = format!(
+        "arrow-{}",
+        nodes
+            .iter()
+            .map(|node| node.id)
+            .collect::<Vec<_>>()
+            .join("-")
+    );
+    let find = |id: &str| nodes.iter().find(|node| node.id == id).copied();
+    let width = nodes
+        .iter()
+        .map(|node| position(node).0 + NODE_WIDTH)
+        .fold(0.0, f64::max);
+    let height = nodes
+        .iter()
+        .map(|node| position(node).1 + NODE_HEIGHT)
+        .fold(0.0, f64::max);
+
+    // From the middle of the right edge of one box to the middle of the left edge of the other.
+    let lines = edges
+        .iter()
+        .filter_map(|(from, to)| Some((find(from)?, find(to)?)))
+        .map(|(from, to)| {
+            let (from_x, from_y) = position(&from);
+            let (to_x, to_y) = position(&to);
+            // The edge lights up along with the node it leads to: that one ran because of it.
+            let active = move || ran(to.id);
+            view! { cx,
+                <line
+                    x1=from_x + NODE_WIDTH
+                    y1=from_y + NODE_HEIGHT / 2.0
+                    x2=to_x
+                    y2=to_y + NODE_HEIGHT / 2.0
+                    stroke-width="2"
+                    class="transition-colors duration-300"
+                    class=("stroke-orange-500", active)
+                    class=("stroke-gray-400", move || !active())
+                    marker-end=format!("url(#{arrow})")
+                />
+            }
+        })
+        .collect_view(cx);
+
+    let boxes = nodes
+        .iter()
+        .map(|node| {
+            let (x, y) = position(node);
+            let id = node.id;
+            let active = move || ran(id);
+            let kind = match node.kind {
+                NodeKind::Signal => "signal",
+                NodeKind::Memo => "memo",
+                NodeKind::Effect => "effect",
+            };
+            // Signals are drawn square, memos rounded and effects as pills.
+            let radius = match node.kind {
+                NodeKind::Signal => 0.0,
+                NodeKind::Memo => 8.0,
+                NodeKind::Effect => NODE_HEIGHT / 2.0,
+            };
+            view! { cx,
+                <g transform=format!("translate({x} {y})")>
+                    <rect
+                        width=NODE_WIDTH
+                        height=NODE_HEIGHT
+                        rx=radius
+                        stroke-width="2"
+                        class="transition-colors duration-300 stroke-gray-500"
+                        class=("fill-orange-200", active)
+                        class=("fill-white", move || !active())
+                    />
+                    <text x=NODE_WIDTH / 2.0 y="18" text-anchor="middle" class="text-sm fill-black">
+                        {node.label}
+                    </text>
+                    <text x=NODE_WIDTH / 2.0 y="36" text-anchor="middle" class="text-xs fill-gray-600">
+                        {move || format!("{kind}, ran {}×", tracker.count(id))}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view(cx);
+
+    view! { cx,
+        <svg
+            viewBox=format!("-4 -4 {} {}", width + 8.0, height + 8.0)
+            class="w-full"
+            role="img"
+            aria-label="Signals, memos and effects, with arrows from what is read to what reads it"
+        >
+            <defs>
+                <marker id=arrow viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto">
+                    <path d="M 0 0 L 10 5 L 0 10 z" class="fill-gray-500"/>
+                </marker>
+            </defs>
+            {lines}
+            {boxes}
+        </svg>
+    }
+}
+
+/// Steps through the latest update of `tracker`, one run at a time. `steps` is how many are shown,
+/// hand the same signal to the `<ReactiveGraph/>` and set it to 1 whenever an update starts.
+#[component]
+pub fn UpdateSteps(cx: Scope, tracker: Tracker, steps: RwSignal<usize>) -> impl IntoView {
+    let total = move || tracker.step_count();
+    let done = move || steps.get() >= total();
+
+    let shown = move || {
+        tracker.shown.with(|shown| {
+            shown
+                .order
+                .iter()
+                .take(steps.get())
+                .map(|(_, step)| view! { cx, <li>{step.clone()}</li> })
+                .collect_view(cx)
+        })
+    };
+
+    view! { cx,
+        <div>
+            <ol class="list-decimal list-inside" aria-live="polite">{shown}</ol>
+            <div class="flex gap-4 mt-2">
+                <button
+                    class="text-blue-700 underline disabled:text-gray-400 disabled:no-underline"
+                    disabled=done
+                    on:click=move |_| steps.update(|steps| *steps += 1)
+                >
+                    "Next step"
+                </button>
+                <button
+                    class="text-blue-700 underline disabled:text-gray-400 disabled:no-underline"
+                    disabled=done
+                    on:click=move |_| steps.set(total())
+                >
+                    "Show all"
+                </button>
+            </div>
+        </div>
+    }
+}
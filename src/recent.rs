@@ -0,0 +1,58 @@
+use crate::context::expect_context;
+use leptos::*;
+use leptos_router::use_location;
+
+// The pages you looked at last, newest first. `App` provides the list and renders a
+// `<RecordVisits/>` inside the `<Router>`, which watches the URL and adds every page you navigate
+// to. A page you come back to moves to the front instead of showing up twice, and only the last
+// `MAX_RECENT` are kept.
+
+/// How many pages the list keeps.
+pub const MAX_RECENT: usize = 5;
+
+#[derive(Copy, Clone)]
+pub struct RecentlyViewed(RwSignal<Vec<String>>);
+
+impl RecentlyViewed {
+    /// The paths of the pages, newest first.
+    pub fn paths(&self) -> Vec<String> {
+        self.0.get()
+    }
+
+    /// Puts `path` at the front, removing it further down and dropping whatever is past `MAX_RECENT`.
+    pub fn visit(&self, path: &str) {
+        self.0.update(|paths| {
+            paths.retain(|visited| visited != path);
+            paths.insert(0, path.to_string());
+            paths.truncate(MAX_RECENT);
+        });
+    }
+}
+
+/// Provides the (empty) list to every component below `cx`. Render a `<RecordVisits/>` inside
+/// the `<Router>` to fill it.
+pub fn provide_recently_viewed(cx: Scope) -> RecentlyViewed {
+    let recent = RecentlyViewed(create_rw_signal(cx, Vec::new()));
+    provide_context(cx, recent);
+    recent
+}
+
+/// Pulls the list out of context.
+pub fn use_recently_viewed(cx: Scope) -> RecentlyViewed {
+    expect_context::<RecentlyViewed>(cx, "App")
+}
+
+/// Adds every page the router navigates to, for which `track` returns `true`, to the list.
+#[component]
+pub fn RecordVisits(cx: Scope, track: fn(&str) -> bool) -> impl IntoView {
+    let recent = use_recently_viewed(cx);
+    let pathname = use_location(cx).pathname;
+    // Runs for the page the app starts on and again whenever the path changes. Query strings (a
+    // counter value, a filter) don't make it another page, so only the path is recorded.
+    create_effect(cx, move |_| {
+        let path = pathname.get();
+        if track(&path) {
+            recent.visit(&path);
+        }
+    });
+}
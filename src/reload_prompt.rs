@@ -0,0 +1,29 @@
+use leptos::*;
+use leptos_meta::Script;
+
+// When the app's code can't be loaded, the page is server-rendered HTML that never becomes
+// interactive: buttons do nothing and nothing says why. The usual cause is a deploy: the HTML came
+// from the old version (or out of a cache), and its /pkg files are gone or no longer fit together.
+// A network hiccup does the same. Either way a reload fixes it, so we offer one.
+//
+// Detecting this can't be done in Rust, it's the Rust (WASM) that failed to load. It's done by a
+// small JavaScript file, public/reload_prompt.js, loaded before anything else. It watches for:
+// - a <script> that fails to load (the JS glue in /pkg),
+// - a failed promise before the app has hydrated (`init()`, loading the WASM),
+// - a failed `import()` of a code chunk at any time. Leptos doesn't split the WASM into chunks
+//   yet, so today there are none, but lazily loaded code would fail in exactly this way.
+//
+// "Hydrated" is the `data-hydrated` attribute on <html>, set by `mark_hydrated` from `hydrate()`.
+
+/// Loads the reload prompt script. Rendered once, in `App`.
+#[component]
+pub fn ReloadPromptScript(cx: Scope) -> impl IntoView {
+    view! { cx, <Script src="/reload_prompt.js"/> }
+}
+
+/// Tells the reload prompt script that the app has loaded and hydrated.
+pub fn mark_hydrated() {
+    if let Some(root) = document().document_element() {
+        _ = root.set_attribute("data-hydrated", "");
+    }
+}
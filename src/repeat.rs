@@ -0,0 +1,64 @@
+use leptos::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Press-and-hold auto repeat for stepper buttons: one step right away, then after a short pause the
+// step repeats, faster and faster, until the button is released.
+
+/// Pause between pressing the button and the first repeat.
+const INITIAL_DELAY: Duration = Duration::from_millis(400);
+/// Interval of the first repeat. Each following one is 15% shorter, down to `MIN_INTERVAL`.
+const FIRST_INTERVAL: Duration = Duration::from_millis(150);
+const MIN_INTERVAL: Duration = Duration::from_millis(30);
+
+/// Delay before repeat number `tick` (0 is the pause after the initial press).
+pub fn repeat_delay(tick: u32) -> Duration {
+    if tick == 0 {
+        return INITIAL_DELAY;
+    }
+    let interval = FIRST_INTERVAL.mul_f64(0.85_f64.powi(tick as i32 - 1));
+    interval.max(MIN_INTERVAL)
+}
+
+#[derive(Copy, Clone)]
+pub struct HoldToRepeat {
+    timer: StoredValue<Option<TimeoutHandle>>,
+}
+
+/// Creates a repeater, wire `start` to `mousedown` and `stop` to `mouseup` and `mouseleave`.
+/// The timer is also stopped when the component is removed.
+pub fn use_hold_to_repeat(cx: Scope) -> HoldToRepeat {
+    let repeat = HoldToRepeat {
+        timer: store_value(cx, None),
+    };
+    on_cleanup(cx, move || repeat.stop());
+    repeat
+}
+
+impl HoldToRepeat {
+    /// Runs `step` once now and keeps repeating it until `stop` is called.
+    pub fn start(&self, step: impl Fn() + 'static) {
+        self.stop();
+        step();
+        self.schedule(Rc::new(step), 0);
+    }
+
+    pub fn stop(&self) {
+        if let Some(timer) = self.timer.try_update_value(Option::take).flatten() {
+            timer.clear();
+        }
+    }
+
+    // Chained timeouts instead of an interval, because the delay changes on every tick.
+    fn schedule(self, step: Rc<dyn Fn()>, tick: u32) {
+        let timer = set_timeout_with_handle(
+            move || {
+                step();
+                self.schedule(step, tick + 1);
+            },
+            repeat_delay(tick),
+        )
+        .ok();
+        self.timer.set_value(timer);
+    }
+}
@@ -0,0 +1,58 @@
+use leptos::*;
+
+// "Add another" sections of a form, e.g. any number of phone numbers. The entries live in a signal
+// holding one signal per entry, `RwSignal<Vec<RwSignal<T>>>`: the list changes when an entry is added
+// or removed, an entry changes when you type in it. Typing doesn't render the list again.
+//
+// The rows are keyed by their entry's signal, which stays the same while it is in the list. Removing
+// the second of three rows removes exactly that row's inputs. Keyed by position, the second row would
+// be kept and the third one removed, and whatever the browser holds in the inputs that we don't
+// (the cursor, an unfinished IME composition, focus) would end up in the wrong row.
+
+/// One row per entry in `items`, each rendered by `row` with a "Remove" button, and an "Add" button
+/// that appends `new_item()`. New entries belong to the repeater's scope.
+#[component]
+pub fn Repeater<T, F, IV>(
+    cx: Scope,
+    items: RwSignal<Vec<RwSignal<T>>>,
+    /// A blank entry, for "Add".
+    new_item: fn() -> T,
+    /// The sub-form for one entry.
+    row: F,
+    #[prop(default = "Add")] add_label: &'static str,
+) -> impl IntoView
+where
+    T: 'static,
+    F: Fn(Scope, RwSignal<T>) -> IV + Copy + 'static,
+    IV: IntoView,
+{
+    let add = move |_| {
+        let item = create_rw_signal(cx, new_item());
+        items.update(|items| items.push(item));
+    };
+    let remove = move |item: RwSignal<T>| {
+        items.update(|items| items.retain(|other| *other != item));
+    };
+
+    view! { cx,
+        <div>
+            <For
+                each=move || items.get()
+                key=|item| *item
+                view=move |cx, item| {
+                    view! { cx,
+                        <div class="flex items-start gap-2 mt-2">
+                            <div class="flex-1">{row(cx, item)}</div>
+                            <button type="button" class="text-red-700 underline" on:click=move |_| remove(item)>
+                                "Remove"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+            <button type="button" class="mt-2 text-blue-700 underline" on:click=add>
+                {add_label}
+            </button>
+        </div>
+    }
+}
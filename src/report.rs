@@ -0,0 +1,18 @@
+use leptos::error::Error;
+use log::error;
+
+// Error reporting: one place every unhandled error goes through. For now a report is a line in the
+// log (the browser console, or the server log during server-side rendering), but this is where an
+// error tracking service would be called.
+
+/// Reports an error nobody handled. `source` says where it was caught, e.g. "ErrorBoundary".
+pub fn report_error(source: &str, error: &Error) {
+    // The error's own message, followed by those of the errors that caused it.
+    let mut message = error.to_string();
+    let mut cause = error.source();
+    while let Some(error) = cause {
+        message.push_str(&format!(": {error}"));
+        cause = error.source();
+    }
+    error!("[{source}] {message}");
+}
@@ -0,0 +1,115 @@
+use leptos::html::Div;
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::HtmlDocument;
+
+// A small rich text editor: a `contenteditable` element with a toolbar for bold and italic, and undo
+// and redo.
+//
+// Leptos normally owns the DOM it renders and updates it from signals. A `contenteditable` element
+// turns that around: the browser changes the DOM as the user types. If we also wrote the signal back
+// into the element, every keystroke would replace the content under the cursor and the cursor would
+// jump to the start. So the flow only goes one way: the element gets its content once, when it is
+// rendered, and after that every change is read from it (`innerHTML`) into the signal.
+//
+// The formatting goes through `document.execCommand`. It is marked as deprecated, but every browser
+// still supports it and there is no replacement yet. Its big advantage is that the browser records
+// each command in the same undo history as the typing, so Ctrl+Z (and our Undo button) undo a "bold"
+// just like a typed word. Wrapping the selection in `<b>` ourselves would leave that history behind.
+
+/// An editable area whose HTML is kept in `html`. It starts with what `html` holds when it's
+/// rendered. Later changes to `html` from elsewhere aren't shown, the editor writes to it only.
+#[component]
+pub fn RichTextEditor(cx: Scope, html: RwSignal<String>) -> impl IntoView {
+    let editor = create_node_ref::<Div>(cx);
+    // Whether the selection (or the cursor) is in bold or italic text, for the pressed state of the buttons.
+    let (bold, set_bold) = create_signal(cx, false);
+    let (italic, set_italic) = create_signal(cx, false);
+
+    let html_document = move || document().unchecked_into::<HtmlDocument>();
+    let sync = move || {
+        if let Some(editor) = editor.get_untracked() {
+            // Leptos' own `inner_html` sets the content, the DOM element's reads it.
+            let element: &web_sys::Element = &editor;
+            html.set(element.inner_html());
+        }
+    };
+    let update_state = move || {
+        let document = html_document();
+        let inside = editor.get_untracked().is_some_and(|editor| {
+            document
+                .active_element()
+                .is_some_and(|active| editor.contains(Some(&active)))
+        });
+        let state = |command| inside && document.query_command_state(command).unwrap_or(false);
+        set_bold(state("bold"));
+        set_italic(state("italic"));
+    };
+    let run = move |command: &str| {
+        _ = html_document().exec_command(command);
+        // The command changed the content, which doesn't fire `input` in every browser.
+        sync();
+        update_state();
+    };
+
+    // Moving the cursor into bold text should press the Bold button. Only the document tells us when the
+    // selection changes.
+    if !leptos_dom::is_server() {
+        let on_selection_change = Closure::<dyn Fn()>::new(update_state);
+        _ = document().add_event_listener_with_callback(
+            "selectionchange",
+            on_selection_change.as_ref().unchecked_ref(),
+        );
+        on_cleanup(cx, move || {
+            _ = document().remove_event_listener_with_callback(
+                "selectionchange",
+                on_selection_change.as_ref().unchecked_ref(),
+            );
+        });
+    }
+
+    let button = move |label: &'static str,
+                       title: &'static str,
+                       command: &'static str,
+                       pressed: Option<ReadSignal<bool>>| {
+        view! { cx,
+            <button
+                type="button"
+                class="w-8 h-8 border rounded"
+                class=("bg-blue-100", move || pressed.is_some_and(|pressed| pressed.get()))
+                class=("dark:bg-blue-900", move || pressed.is_some_and(|pressed| pressed.get()))
+                title=title
+                aria-label=title
+                aria-pressed=move || pressed.map(|pressed| pressed.get().to_string())
+                // A click moves the focus to the button, and with it the selection would be gone before the
+                // command runs. Not letting `mousedown` do its default keeps the focus in the editor.
+                on:mousedown=|event| event.prevent_default()
+                on:click=move |_| run(command)
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! { cx,
+        <div class="border rounded">
+            <div class="flex gap-1 p-1 border-b" role="toolbar" aria-label="Formatting">
+                {button("B", "Bold (Ctrl+B)", "bold", Some(bold))}
+                {button("I", "Italic (Ctrl+I)", "italic", Some(italic))}
+                {button("↶", "Undo (Ctrl+Z)", "undo", None)}
+                {button("↷", "Redo (Ctrl+Y)", "redo", None)}
+            </div>
+            <div
+                node_ref=editor
+                class="min-h-[8rem] p-2 focus:outline-none"
+                contenteditable="true"
+                role="textbox"
+                aria-multiline="true"
+                on:input=move |_| sync()
+                // Set once, when the element is created. Keep it the last attribute and the element not
+                // self-closing (`<div/>`), otherwise Leptos 0.4 leaves it out of the server's HTML.
+                inner_html=html.get_untracked()
+            ></div>
+        </div>
+    }
+}
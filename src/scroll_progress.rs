@@ -0,0 +1,65 @@
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// How far the page has been scrolled, for a reading progress bar. That is the scroll position over
+// the distance the page can be scrolled at all: the height of the page minus the height of the
+// window.
+//
+// `scroll` fires many times per frame while scrolling. Measuring every time would be wasted work, so
+// a scroll only asks for a measurement on the next animation frame, and more scrolls before that
+// frame don't ask again.
+
+/// How far the page is scrolled, in `steps` steps: `0` at the top, `steps` at the bottom. A page
+/// that is no taller than the window can't be scrolled and stays at `0`, so does the server.
+pub fn use_scroll_progress(cx: Scope, steps: i32) -> ReadSignal<i32> {
+    let (progress, set_progress) = create_signal(cx, 0);
+    if leptos_dom::is_server() {
+        return progress;
+    }
+
+    let measure = move || {
+        let Some(root) = document().document_element() else {
+            return;
+        };
+        let scrollable = f64::from(root.scroll_height() - root.client_height());
+        let measured = if scrollable > 0.0 {
+            let y = window().scroll_y().unwrap_or_default();
+            // Clamped, because Safari's rubber band scrolling goes past the top and the bottom.
+            ((y / scrollable).clamp(0.0, 1.0) * f64::from(steps)).round() as i32
+        } else {
+            0
+        };
+        // Most scrolls don't move the bar by a whole step, then nothing needs to update.
+        if progress.get_untracked() != measured {
+            set_progress(measured);
+        }
+    };
+    // Whether a measurement is already waiting for the next frame.
+    let scheduled = store_value(cx, false);
+    let schedule = Closure::<dyn Fn()>::new(move || {
+        if !scheduled.get_value() {
+            scheduled.set_value(true);
+            request_animation_frame(move || {
+                // The page may be gone by the next frame.
+                if scheduled.try_set_value(false).is_none() {
+                    measure();
+                }
+            });
+        }
+    });
+
+    // The page is rendered by now, but its content (and so its height) may still change. The first
+    // measurement happens on the next frame, after that on every scroll and resize.
+    request_animation_frame(measure);
+    for event in ["scroll", "resize"] {
+        _ = window().add_event_listener_with_callback(event, schedule.as_ref().unchecked_ref());
+    }
+    on_cleanup(cx, move || {
+        for event in ["scroll", "resize"] {
+            _ = window()
+                .remove_event_listener_with_callback(event, schedule.as_ref().unchecked_ref());
+        }
+    });
+
+    progress
+}
@@ -0,0 +1,131 @@
+use leptos::*;
+use std::collections::HashMap;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+// Scroll positions on back/forward. Clicking a link already scrolls to the top (leptos_router does
+// that when it pushes the new URL), but going back should bring you to where you were on that page.
+// Browsers do this themselves for ordinary pages. Here they would do it too early: right on
+// `popstate`, while the old page is still on screen (and still animating out), so the position is
+// lost on any page that isn't as long as the one you're leaving.
+//
+// So we turn the browser's restoration off and do it ourselves:
+// - Every history entry gets a key, stored in its history state (`history.state.scroll_key`). The
+//   state belongs to the entry, so the key comes back when you return to it with back/forward.
+// - While you scroll, the position is recorded under the key of the current entry.
+// - On `popstate` (back/forward) the position recorded for the entry we returned to is scrolled to,
+//   retrying every frame until the new page is long enough.
+// - The positions are kept in sessionStorage when the page is unloaded, so a reload restores too.
+
+const STORAGE_KEY: &str = "scroll_positions";
+const STATE_KEY: &str = "scroll_key";
+/// How many frames (about a second) we keep trying to reach the position while the page renders.
+const RESTORE_FRAMES: u32 = 60;
+
+/// Restores the scroll position on back/forward navigation and reload. Call once, in `App`.
+pub fn use_scroll_restoration(cx: Scope) {
+    if leptos_dom::is_server() {
+        return;
+    }
+    let Ok(history) = window().history() else {
+        return;
+    };
+    _ = history.set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+
+    let positions = store_value(cx, load_positions());
+    let restore = move || {
+        let key = entry_key();
+        if let Some(y) = positions.with_value(|positions| positions.get(&key).copied()) {
+            scroll_to(key, y, RESTORE_FRAMES);
+        }
+    };
+    // After a reload.
+    restore();
+
+    let on_scroll = Closure::<dyn Fn()>::new(move || {
+        let y = window().scroll_y().unwrap_or_default();
+        positions.update_value(|positions| {
+            positions.insert(entry_key(), y);
+        });
+    });
+    let on_popstate = Closure::<dyn Fn()>::new(restore);
+    let on_pagehide = Closure::<dyn Fn()>::new(move || positions.with_value(save_positions));
+    let listeners = [
+        ("scroll", on_scroll),
+        ("popstate", on_popstate),
+        ("pagehide", on_pagehide),
+    ];
+    for (event, listener) in &listeners {
+        _ = window().add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+    }
+    on_cleanup(cx, move || {
+        for (event, listener) in &listeners {
+            _ = window()
+                .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+        }
+    });
+}
+
+/// Scrolls to `y`, and again on the next frames until the page is long enough to get there. Stops
+/// early when we navigate away from the entry `key`.
+fn scroll_to(key: String, y: f64, frames_left: u32) {
+    if current_key().as_ref() != Some(&key) {
+        return;
+    }
+    window().scroll_to_with_x_and_y(0.0, y);
+    let reached = window()
+        .scroll_y()
+        .is_ok_and(|scroll_y| (scroll_y - y).abs() < 1.0);
+    if !reached && frames_left > 0 {
+        request_animation_frame(move || scroll_to(key, y, frames_left - 1));
+    }
+}
+
+/// The key of the current history entry, if it has one yet.
+fn current_key() -> Option<String> {
+    let state = window().history().ok()?.state().ok()?;
+    if !state.is_object() {
+        return None;
+    }
+    js_sys::Reflect::get(&state, &STATE_KEY.into())
+        .ok()?
+        .as_string()
+}
+
+/// The key of the current history entry. A new entry (the router pushes them without state) gets
+/// one here.
+fn entry_key() -> String {
+    if let Some(key) = current_key() {
+        return key;
+    }
+    let key = js_sys::Math::random().to_string();
+    let Ok(history) = window().history() else {
+        return key;
+    };
+    // Keep whatever else is in the state, the router hands it to the page as `location.state`.
+    let state = history
+        .state()
+        .ok()
+        .filter(JsValue::is_object)
+        .unwrap_or_else(|| js_sys::Object::new().into());
+    _ = js_sys::Reflect::set(&state, &STATE_KEY.into(), &key.as_str().into());
+    _ = history.replace_state(&state, "");
+    key
+}
+
+fn load_positions() -> HashMap<String, f64> {
+    window()
+        .session_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_positions(positions: &HashMap<String, f64>) {
+    if let (Ok(Some(storage)), Ok(json)) =
+        (window().session_storage(), serde_json::to_string(positions))
+    {
+        _ = storage.set_item(STORAGE_KEY, &json);
+    }
+}
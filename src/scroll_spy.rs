@@ -0,0 +1,70 @@
+use leptos::*;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// Scroll spy: watches a list of sections (by element id) and reports which one is currently being
+// read, so a navigation menu can highlight it. The browser's IntersectionObserver tells us when a
+// section enters or leaves the viewport, we keep the set of visible ones and pick the first.
+
+/// Returns the id of the section currently in view, out of `ids` (given in page order).
+/// A section counts as "in view" while it overlaps the top 40% of the viewport.
+pub fn use_scroll_spy(cx: Scope, ids: Vec<&'static str>) -> ReadSignal<Option<&'static str>> {
+    let (active, set_active) = create_signal(cx, ids.first().copied());
+    // IntersectionObserver only exists in the browser.
+    if leptos_dom::is_server() {
+        return active;
+    }
+
+    let ids: Rc<[&'static str]> = ids.into();
+    let visible = Rc::new(RefCell::new(HashSet::<String>::new()));
+    let on_intersect = Closure::<dyn Fn(js_sys::Array)>::new({
+        let ids = Rc::clone(&ids);
+        move |entries: js_sys::Array| {
+            for entry in entries.iter() {
+                let entry: web_sys::IntersectionObserverEntry = entry.unchecked_into();
+                let id = entry.target().id();
+                if entry.is_intersecting() {
+                    visible.borrow_mut().insert(id);
+                } else {
+                    visible.borrow_mut().remove(&id);
+                }
+            }
+            // When we are in the gap between two sections nothing is visible, keep the last one highlighted then.
+            let visible = visible.borrow();
+            if let Some(id) = ids.iter().find(|id| visible.contains(**id)) {
+                set_active(Some(*id));
+            }
+        }
+    });
+
+    let options = web_sys::IntersectionObserverInit::new();
+    options.set_root_margin("0px 0px -60% 0px");
+    let Ok(observer) = web_sys::IntersectionObserver::new_with_options(
+        on_intersect.as_ref().unchecked_ref(),
+        &options,
+    ) else {
+        return active;
+    };
+
+    // The sections are only in the document once the component has been mounted, which has happened by the next frame.
+    request_animation_frame({
+        let observer = observer.clone();
+        move || {
+            for id in ids.iter() {
+                if let Some(section) = document().get_element_by_id(id) {
+                    observer.observe(&section);
+                }
+            }
+        }
+    });
+
+    on_cleanup(cx, move || {
+        observer.disconnect();
+        // Keep the callback alive until the observer is gone.
+        drop(on_intersect);
+    });
+
+    active
+}
@@ -0,0 +1,166 @@
+use crate::backup::Backup;
+use crate::debounce::use_timings;
+use crate::download::download_bytes;
+use crate::locale::LocalePicker;
+use crate::preferences::{use_preferences, REFRESH_INTERVALS};
+use crate::theme::{use_theme, ThemePreference};
+use crate::toast::use_toasts;
+use leptos::*;
+use leptos_meta::Title;
+use std::time::Duration;
+
+// One page for the app-wide preferences. Nothing is kept here: each control reads and writes the
+// context the preference lives in (the theme, the locale, the timings and the other preferences), so
+// a change shows everywhere right away and is saved the way that context saves it. Changing the theme
+// with the toggle at the top updates the drop-down here too.
+//
+// "Export settings" saves all of them to a file, "Import settings" reads such a file back, see
+// backup.rs.
+
+/// The settings page, at /settings.
+#[component]
+pub fn Settings(cx: Scope) -> impl IntoView {
+    let theme = use_theme(cx);
+    let timings = use_timings(cx);
+    let preferences = use_preferences(cx);
+
+    let theme_value = move || theme.preference.get().tag();
+    let set_theme = move |event| {
+        if let Some(picked) = ThemePreference::from_tag(&event_target_value(&event)) {
+            theme.preference.set(picked);
+        }
+    };
+
+    let refresh_value = move || {
+        preferences
+            .refresh_interval
+            .get()
+            .map(|interval| interval.as_secs().to_string())
+            .unwrap_or_default()
+    };
+    let set_refresh = move |event| {
+        let seconds = event_target_value(&event).parse().ok();
+        preferences
+            .refresh_interval
+            .set(seconds.map(Duration::from_secs));
+    };
+
+    let debounce_millis = move || timings.debounce.get().as_millis().to_string();
+
+    let toasts = use_toasts(cx);
+    let export = move |_| {
+        let json = Backup::collect(cx).to_json();
+        if download_bytes(
+            json.as_bytes(),
+            "application/json",
+            "leptos-examples-settings.json",
+        )
+        .is_err()
+        {
+            toasts.push("The settings couldn't be exported");
+        }
+    };
+    let import = move |event: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&event);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Otherwise picking the same file again, e.g. after fixing it, wouldn't be a change.
+        input.set_value("");
+        spawn_local(async move {
+            let json = wasm_bindgen_futures::JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|text| text.as_string())
+                .ok_or_else(|| "The file couldn't be read".to_string());
+            match json.and_then(|json| Backup::parse(&json)) {
+                Ok(backup) => {
+                    backup.apply(cx);
+                    toasts.push("Settings imported");
+                }
+                Err(error) => toasts.push(format!("Import failed: {error}")),
+            }
+        });
+    };
+
+    view! { cx,
+        <Title text="Settings"/>
+        <main class="max-w-2xl mx-auto mt-12">
+            <h1 class="text-3xl">"Settings"</h1>
+            <p class="mt-2">"They apply right away, all over the app, and are saved in this browser."</p>
+            <div class="grid grid-cols-[auto_1fr] gap-x-4 gap-y-3 items-center mt-6">
+                <label for="theme">"Theme"</label>
+                <select id="theme" class="p-1 border w-fit dark:bg-gray-800" prop:value=theme_value on:change=set_theme>
+                    <option value="system">"Same as the system"</option>
+                    <option value="light">"Light"</option>
+                    <option value="dark">"Dark"</option>
+                </select>
+
+                <span>"Locale"</span>
+                <LocalePicker/>
+
+                <label for="debounce">"Debounce delay"</label>
+                <div class="flex items-center gap-2">
+                    <input
+                        id="debounce"
+                        type="range"
+                        min="0"
+                        max="2000"
+                        step="50"
+                        prop:value=debounce_millis
+                        on:input=move |event| {
+                            if let Ok(millis) = event_target_value(&event).parse() {
+                                timings.debounce.set(Duration::from_millis(millis));
+                            }
+                        }
+                    />
+                    <span>{debounce_millis} "ms"</span>
+                </div>
+
+                <label for="refresh">"Auto-refresh"</label>
+                <select
+                    id="refresh"
+                    class="p-1 border w-fit dark:bg-gray-800"
+                    prop:value=refresh_value
+                    on:change=set_refresh
+                >
+                    {REFRESH_INTERVALS
+                        .map(|(seconds, label)| {
+                            let value = seconds.map(|seconds| seconds.to_string()).unwrap_or_default();
+                            view! { cx, <option value=value>{label}</option> }
+                        })
+                        .collect_view(cx)}
+                </select>
+
+                <label for="reduce-motion">"Reduce motion"</label>
+                <input
+                    id="reduce-motion"
+                    type="checkbox"
+                    class="w-fit"
+                    prop:checked=move || preferences.reduce_motion.get()
+                    on:change=move |event| preferences.reduce_motion.set(event_target_checked(&event))
+                />
+            </div>
+            <p class="mt-6 text-sm text-gray-500">
+                "The debounce delay is how long searches wait for you to stop typing. Auto-refresh fetches the results at "
+                "/fetch and the rows at /table again every so often. Reduce motion cuts animations short, like the setting in your OS."
+            </p>
+            <h2 class="mt-6 text-xl">"Backup"</h2>
+            <p class="mt-2">
+                "All of the above plus the feature flags and your favorites, in one file. Import it here or in another "
+                "browser to get them back."
+            </p>
+            <div class="flex gap-2 mt-2">
+                <button class="px-3 py-2 text-white bg-blue-700 rounded shadow" on:click=export>
+                    "Export settings"
+                </button>
+                // A file input is hard to style, so it is hidden in a label that looks like the button next to it.
+                // Clicking the label opens the file picker.
+                <label class="px-3 py-2 text-white bg-blue-700 rounded shadow cursor-pointer focus-within:ring">
+                    "Import settings"
+                    <input type="file" accept=".json,application/json" class="sr-only" on:change=import/>
+                </label>
+            </div>
+        </main>
+    }
+}
@@ -0,0 +1,157 @@
+use leptos::*;
+use std::fmt::Debug;
+
+// A finite state machine on top of a signal. UI often goes through a handful of states in a fixed
+// order: a fetch is idle, then loading, then done or failed, then loading again. Kept in a few
+// booleans (`loading`, `failed`, ...) nothing stops the code from ending up "loading and failed" at
+// once. Here the states and the moves between them are written down once, as a table of
+// `(from, event, to)`, and `send` only moves along that table.
+//
+// An event that isn't allowed in the current state is ignored and logged as a warning. That is
+// usually a bug (a "done" arriving for a fetch that was never started), and the log says which
+// state and event it was.
+
+/// The current state, and the allowed moves from one state to another.
+pub struct StateMachine<S: 'static, E: 'static> {
+    state: RwSignal<S>,
+    transitions: StoredValue<Vec<(S, E, S)>>,
+}
+
+// Derived `Clone` and `Copy` would require `S` and `E` to be `Copy` too, but the machine only holds
+// arena handles.
+impl<S, E> Clone for StateMachine<S, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, E> Copy for StateMachine<S, E> {}
+
+impl<S, E> StateMachine<S, E>
+where
+    S: Clone + PartialEq + Debug,
+    E: PartialEq + Debug,
+{
+    /// The current state.
+    pub fn get(&self) -> S {
+        self.state.get()
+    }
+
+    /// The current state as a read-only signal, to hand to components.
+    pub fn state(&self) -> ReadSignal<S> {
+        self.state.read_only()
+    }
+
+    /// Moves to the state the table gives for the current state and `event`. Returns `false`, and
+    /// leaves the state as it is, if the table has no such move.
+    pub fn send(&self, event: E) -> bool {
+        let state = self.state.get_untracked();
+        let next = self
+            .transitions
+            .with_value(|transitions| next_state(transitions, &state, &event));
+        match next {
+            Some(next) => {
+                self.state.set(next);
+                true
+            }
+            None => {
+                log::warn!("State machine: {event:?} is not allowed in state {state:?}, ignored");
+                false
+            }
+        }
+    }
+}
+
+/// A state machine that starts in `initial` and can make the moves in `transitions`, each one
+/// `(from, event, to)`. The first move that matches wins.
+pub fn use_state_machine<S, E>(
+    cx: Scope,
+    initial: S,
+    transitions: impl IntoIterator<Item = (S, E, S)>,
+) -> StateMachine<S, E> {
+    StateMachine {
+        state: create_rw_signal(cx, initial),
+        transitions: store_value(cx, transitions.into_iter().collect()),
+    }
+}
+
+/// Where `event` leads from `state`, if anywhere.
+fn next_state<S, E>(transitions: &[(S, E, S)], state: &S, event: &E) -> Option<S>
+where
+    S: Clone + PartialEq,
+    E: PartialEq,
+{
+    transitions
+        .iter()
+        .find(|(from, on, _)| from == state && on == event)
+        .map(|(_, _, to)| to.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Light {
+        Off,
+        On,
+        Broken,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Switch {
+        Flip,
+        Smash,
+    }
+
+    const TABLE: [(Light, Switch, Light); 4] = [
+        (Light::Off, Switch::Flip, Light::On),
+        (Light::On, Switch::Flip, Light::Off),
+        (Light::On, Switch::Smash, Light::Broken),
+        // Never reached, the move above matches first.
+        (Light::On, Switch::Smash, Light::Off),
+    ];
+
+    #[test]
+    fn next_state_follows_the_table() {
+        assert_eq!(
+            next_state(&TABLE, &Light::Off, &Switch::Flip),
+            Some(Light::On)
+        );
+        assert_eq!(
+            next_state(&TABLE, &Light::On, &Switch::Flip),
+            Some(Light::Off)
+        );
+    }
+
+    #[test]
+    fn next_state_has_nothing_for_a_move_that_is_not_in_the_table() {
+        assert_eq!(next_state(&TABLE, &Light::Off, &Switch::Smash), None);
+        assert_eq!(next_state(&TABLE, &Light::Broken, &Switch::Flip), None);
+    }
+
+    #[test]
+    fn next_state_takes_the_first_matching_move() {
+        assert_eq!(
+            next_state(&TABLE, &Light::On, &Switch::Smash),
+            Some(Light::Broken)
+        );
+    }
+
+    #[test]
+    fn send_moves_along_the_table_and_ignores_the_rest() {
+        let runtime = create_runtime();
+        _ = create_scope(runtime, |cx| {
+            let light = use_state_machine(cx, Light::Off, TABLE);
+            assert!(!light.send(Switch::Smash));
+            assert_eq!(light.get(), Light::Off);
+            assert!(light.send(Switch::Flip));
+            assert_eq!(light.get(), Light::On);
+            assert!(light.send(Switch::Smash));
+            assert_eq!(light.get(), Light::Broken);
+            assert!(!light.send(Switch::Flip));
+            assert_eq!(light.state().get(), Light::Broken);
+        });
+        runtime.dispose();
+    }
+}
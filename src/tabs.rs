@@ -0,0 +1,211 @@
+use crate::announce::Announcer;
+use leptos::*;
+use std::collections::HashSet;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// One tab: the label on its button and a function that builds its panel.
+#[derive(Clone)]
+pub struct Tab {
+    pub label: &'static str,
+    pub content: Rc<dyn Fn(Scope) -> View>,
+}
+
+impl Tab {
+    pub fn new<IV: IntoView>(label: &'static str, content: impl Fn(Scope) -> IV + 'static) -> Self {
+        Self {
+            label,
+            content: Rc::new(move |cx| content(cx).into_view(cx)),
+        }
+    }
+}
+
+/// When the panels of `Tabs` are built.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TabMount {
+    /// All panels right away, the inactive ones are hidden. Switching is instant, but every panel
+    /// costs something even if it's never looked at.
+    #[default]
+    Eager,
+    /// A panel the first time its tab is opened, then it stays (hidden) when you switch away. What
+    /// you typed or scrolled in it is still there when you come back.
+    Lazy,
+    /// Only the active panel. It is thrown away when you switch away and built from scratch when
+    /// you come back, so it never holds on to memory but also forgets its state.
+    Recreate,
+}
+
+/// A row of tab buttons above the panel of the selected tab. Left/right arrow keys (and Home/End)
+/// move between the tabs. With `reorderable` the tabs can also be dragged to another place, or
+/// moved with Ctrl + left/right arrow.
+#[component]
+pub fn Tabs(
+    cx: Scope,
+    /// Prefix for the element ids that link the buttons to their panels, unique on the page.
+    id: &'static str,
+    tabs: Vec<Tab>,
+    #[prop(optional)] mount: TabMount,
+    #[prop(optional)] reorderable: bool,
+) -> impl IntoView {
+    let count = tabs.len();
+    let labels = store_value(cx, tabs.iter().map(|tab| tab.label).collect::<Vec<_>>());
+    let label = move |index: usize| labels.with_value(|labels| labels[index]);
+    // Tabs are identified by their index in `tabs` everywhere, `order` lists those indexes in the order
+    // the buttons are shown. Moving a tab only changes `order`, so the selected tab stays selected and
+    // its panel stays what it was.
+    let order = create_rw_signal(cx, (0..count).collect::<Vec<_>>());
+    let position = move |index: usize| {
+        order.with_untracked(|order| order.iter().position(|tab| *tab == index).unwrap_or(0))
+    };
+    let active = create_rw_signal(cx, 0);
+    // The tabs that have been opened at least once, the first one is open from the start.
+    let activated = create_rw_signal(cx, HashSet::from([0]));
+    let focus = move |index: usize| {
+        if let Some(button) = document().get_element_by_id(&format!("{id}-tab-{index}")) {
+            _ = button.unchecked_into::<web_sys::HtmlElement>().focus();
+        }
+    };
+    let select = move |index: usize| {
+        active.set(index);
+        activated.update(|activated| {
+            activated.insert(index);
+        });
+        // Keyboard users move the focus along with the selection.
+        focus(index);
+    };
+    // Screen reader users can't see the tab move, so they are told, if the app has an announcer.
+    let announcer = use_context::<Announcer>(cx);
+    let move_tab = move |index: usize, to: usize| {
+        let from = position(index);
+        if from == to {
+            return;
+        }
+        order.update(|order| {
+            let tab = order.remove(from);
+            order.insert(to, tab);
+        });
+        // The button was moved in the page, which takes the focus away from it in some browsers.
+        focus(index);
+        if let Some(announcer) = announcer {
+            announcer.announce(format!(
+                "{} moved to position {} of {count}",
+                label(index),
+                to + 1
+            ));
+        }
+    };
+    // The tab being dragged, if any.
+    let dragged = store_value(cx, None::<usize>);
+
+    let button = move |cx, index: usize| {
+        let selected = move || active.get() == index;
+        view! { cx,
+            <button
+                id=format!("{id}-tab-{index}")
+                role="tab"
+                aria-selected=move || selected().to_string()
+                aria-controls=format!("{id}-panel-{index}")
+                // Only the selected tab is reached with Tab, the arrow keys do the rest.
+                tabindex=move || if selected() { "0" } else { "-1" }
+                class="px-4 py-2 -mb-px border-b-2"
+                class=("border-blue-700", selected)
+                class=("text-blue-700", selected)
+                class=("border-transparent", move || !selected())
+                draggable=reorderable.then_some("true")
+                on:click=move |_| select(index)
+                on:keydown=move |event| {
+                    let here = position(index);
+                    let moving = reorderable && event.ctrl_key();
+                    let to = match event.key().as_str() {
+                        "ArrowRight" if moving => (here + 1).min(count - 1),
+                        "ArrowLeft" if moving => here.saturating_sub(1),
+                        "ArrowRight" => (here + 1) % count,
+                        "ArrowLeft" => (here + count - 1) % count,
+                        "Home" => 0,
+                        "End" => count - 1,
+                        _ => return,
+                    };
+                    event.prevent_default();
+                    if moving {
+                        move_tab(index, to);
+                    } else {
+                        select(order.with_untracked(|order| order[to]));
+                    }
+                }
+                on:dragstart=move |event| {
+                    dragged.set_value(Some(index));
+                    // Firefox only starts dragging when there is something to drag along.
+                    if let Some(data) = event.data_transfer() {
+                        _ = data.set_data("text/plain", label(index));
+                    }
+                }
+                on:dragend=move |_| dragged.set_value(None)
+                // Dropping is only allowed where `dragover` is cancelled.
+                on:dragover=move |event| {
+                    if dragged.get_value().is_some() {
+                        event.prevent_default();
+                    }
+                }
+                on:drop=move |event| {
+                    if let Some(moved) = dragged.get_value() {
+                        event.prevent_default();
+                        move_tab(moved, position(index));
+                    }
+                }
+            >
+                {label(index)}
+            </button>
+        }
+    };
+
+    let panels = tabs
+        .into_iter()
+        .enumerate()
+        .map(|(index, tab)| {
+            let selected = move || active.get() == index;
+            // Whether the panel's content exists. A memo, so the content is only built when this
+            // flips to `true` and not every time another tab is opened for the first time.
+            let built = create_memo(cx, move |_| match mount {
+                TabMount::Eager => true,
+                TabMount::Lazy => activated.with(|activated| activated.contains(&index)),
+                TabMount::Recreate => selected(),
+            });
+            // The content gets a scope of its own, so whatever it sets up (signals, effects, timers)
+            // goes away with it in `Recreate` mode.
+            let content_scope = store_value(cx, None::<ScopeDisposer>);
+            let content = move || {
+                content_scope.update_value(|scope| {
+                    if let Some(scope) = scope.take() {
+                        scope.dispose();
+                    }
+                });
+                built.get().then(|| {
+                    let (view, scope) = cx.run_child_scope(|cx| (tab.content)(cx));
+                    content_scope.set_value(Some(scope));
+                    view
+                })
+            };
+            view! { cx,
+                <div
+                    id=format!("{id}-panel-{index}")
+                    role="tabpanel"
+                    aria-labelledby=format!("{id}-tab-{index}")
+                    class="py-4"
+                    class:hidden=move || !selected()
+                >
+                    {content}
+                </div>
+            }
+        })
+        .collect_view(cx);
+
+    view! { cx,
+        <div>
+            <div role="tablist" class="flex border-b">
+                // `For` keeps one button per tab and moves it when `order` changes, instead of building them again.
+                <For each=move || order.get() key=|index| *index view=button/>
+            </div>
+            {panels}
+        </div>
+    }
+}
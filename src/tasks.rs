@@ -0,0 +1,258 @@
+use crate::context::expect_context;
+use crate::pending::PendingAsyncContext;
+use leptos::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+// Background tasks. `spawn_local` starts a future and forgets about it: nobody knows what is still
+// running, and there is no way to stop it. `App` provides a `TaskManager` instead, and anything
+// started with `use_tasks(cx).spawn("name", future)` is listed in it with its status until it's done
+// and can be cancelled on the way.
+//
+// Cancelling drops the future. In Rust that really stops it: a future only makes progress when it
+// is polled, and a dropped one is never polled again. Whatever it owned is dropped with it, so a
+// fetch is aborted, a worker terminated, a guard released.
+
+/// How many finished tasks stay in the list after they're done.
+const HISTORY: usize = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaskStatus {
+    Running,
+    Done,
+    /// The task returned an error, this is its message.
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub name: String,
+    pub status: TaskStatus,
+}
+
+/// Identifies a task, e.g. to cancel it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+#[derive(Copy, Clone)]
+pub struct TaskManager {
+    next_id: StoredValue<usize>,
+    /// The running tasks and the last `HISTORY` finished ones, oldest first.
+    tasks: RwSignal<Vec<TaskInfo>>,
+    cancel_flags: StoredValue<HashMap<TaskId, Rc<CancelFlag>>>,
+    pending: Option<PendingAsyncContext>,
+}
+
+impl TaskManager {
+    /// Runs `future` in the background, listed as `name`.
+    pub fn spawn(
+        &self,
+        name: impl Into<String>,
+        future: impl Future<Output = ()> + 'static,
+    ) -> TaskId {
+        self.try_spawn(name, async move {
+            future.await;
+            Ok::<_, String>(())
+        })
+    }
+
+    /// Like `spawn`, for a future that can fail. An `Err` shows up as `TaskStatus::Failed`.
+    pub fn try_spawn<E: Display>(
+        &self,
+        name: impl Into<String>,
+        future: impl Future<Output = Result<(), E>> + 'static,
+    ) -> TaskId {
+        self.next_id.update_value(|next_id| *next_id += 1);
+        let id = TaskId(self.next_id.get_value());
+        let flag = Rc::new(CancelFlag::default());
+        self.cancel_flags.update_value(|flags| {
+            flags.insert(id, flag.clone());
+        });
+        self.tasks.update(|tasks| {
+            tasks.push(TaskInfo {
+                id,
+                name: name.into(),
+                status: TaskStatus::Running,
+            })
+        });
+
+        // The title badge counts background tasks too.
+        let guard = self.pending.map(|pending| pending.start());
+        let this = *self;
+        spawn_local(async move {
+            let status = match (Cancellable {
+                future: Box::pin(future),
+                flag,
+            })
+            .await
+            {
+                Some(Ok(())) => TaskStatus::Done,
+                Some(Err(error)) => TaskStatus::Failed(error.to_string()),
+                None => TaskStatus::Cancelled,
+            };
+            drop(guard);
+            this.finish(id, status);
+        });
+        id
+    }
+
+    /// Stops a running task. Does nothing once it's finished.
+    pub fn cancel(&self, id: TaskId) {
+        if let Some(flag) = self
+            .cancel_flags
+            .with_value(|flags| flags.get(&id).cloned())
+        {
+            flag.cancel();
+        }
+    }
+
+    /// Stops every running task.
+    pub fn cancel_all(&self) {
+        self.cancel_flags
+            .with_value(|flags| flags.values().for_each(|flag| flag.cancel()));
+    }
+
+    /// The running tasks and the last few finished ones, oldest first.
+    pub fn tasks(&self) -> Signal<Vec<TaskInfo>> {
+        self.tasks.into()
+    }
+
+    /// Only the running tasks.
+    pub fn running(&self) -> Vec<TaskInfo> {
+        self.tasks.with(|tasks| {
+            tasks
+                .iter()
+                .filter(|task| task.status == TaskStatus::Running)
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Removes the finished tasks from the list.
+    pub fn clear_finished(&self) {
+        self.tasks
+            .update(|tasks| tasks.retain(|task| task.status == TaskStatus::Running));
+    }
+
+    fn finish(&self, id: TaskId, status: TaskStatus) {
+        // `try_` because the app may be gone by the time a task finishes.
+        self.cancel_flags
+            .try_update_value(|flags| flags.remove(&id));
+        self.tasks.try_update(|tasks| {
+            if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+                task.status = status;
+            }
+            // Forget the oldest finished tasks beyond `HISTORY`.
+            let mut finished = tasks
+                .iter()
+                .filter(|task| task.status != TaskStatus::Running)
+                .count();
+            tasks.retain(|task| {
+                let forget = finished > HISTORY && task.status != TaskStatus::Running;
+                if forget {
+                    finished -= 1;
+                }
+                !forget
+            });
+        });
+    }
+}
+
+/// Provides the task manager to every component below `cx`. Provide the pending counter first, so
+/// running tasks are counted in the title.
+pub fn provide_tasks(cx: Scope) -> TaskManager {
+    let tasks = TaskManager {
+        next_id: store_value(cx, 0),
+        tasks: create_rw_signal(cx, Vec::new()),
+        cancel_flags: store_value(cx, HashMap::new()),
+        pending: use_context::<PendingAsyncContext>(cx),
+    };
+    provide_context(cx, tasks);
+    tasks
+}
+
+/// Pulls the task manager out of context.
+pub fn use_tasks(cx: Scope) -> TaskManager {
+    expect_context::<TaskManager>(cx, "App")
+}
+
+/// Lists the tasks of the `TaskManager` with their status, running ones with a Cancel button. Meant
+/// for debugging and status pages.
+#[component]
+pub fn TaskList(cx: Scope) -> impl IntoView {
+    let manager = use_tasks(cx);
+
+    view! { cx,
+        <ul class="divide-y">
+            <For
+                each=move || manager.tasks().get()
+                // The status is part of the key, so a row is rendered again when its task finishes.
+                key=|task| (task.id, format!("{:?}", task.status))
+                view=move |cx, task: TaskInfo| {
+                    let (status, color) = match &task.status {
+                        TaskStatus::Running => ("Running".to_string(), "text-blue-700"),
+                        TaskStatus::Done => ("Done".to_string(), "text-green-700"),
+                        TaskStatus::Failed(error) => (format!("Failed: {error}"), "text-red-700"),
+                        TaskStatus::Cancelled => ("Cancelled".to_string(), "text-gray-500"),
+                    };
+                    let running = task.status == TaskStatus::Running;
+                    view! { cx,
+                        <li class="flex items-center gap-4 py-2">
+                            <span class="flex-1">{task.name}</span>
+                            <span class=color>{status}</span>
+                            {running.then(|| view! { cx,
+                                <button class="px-2 text-sm border rounded" on:click=move |_| manager.cancel(task.id)>
+                                    "Cancel"
+                                </button>
+                            })}
+                        </li>
+                    }
+                }
+            />
+        </ul>
+    }
+}
+
+/// Shared between a task and the manager. `cancel` sets the flag and wakes the task, which then
+/// sees the flag and finishes without polling its future again.
+#[derive(Default)]
+struct CancelFlag {
+    cancelled: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl CancelFlag {
+    fn cancel(&self) {
+        self.cancelled.set(true);
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Wraps a task's future. Finishes with `Some(output)` when the future does, or with `None` as soon
+/// as the task is cancelled.
+struct Cancellable<F> {
+    future: Pin<Box<F>>,
+    flag: Rc<CancelFlag>,
+}
+
+impl<F: Future> Future for Cancellable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.flag.cancelled.get() {
+            return Poll::Ready(None);
+        }
+        // Whoever wakes the task next, the future or `cancel`, we get polled again.
+        *self.flag.waker.borrow_mut() = Some(context.waker().clone());
+        self.future.as_mut().poll(context).map(Some)
+    }
+}
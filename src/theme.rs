@@ -0,0 +1,174 @@
+use crate::context::expect_context;
+use crate::media_query::use_media_query;
+use leptos::*;
+use leptos_meta::Script;
+
+// Dark mode. Tailwind is set up with `darkMode: 'class'`, so every `dark:` class applies while the
+// `<html>` element has the `dark` class.
+//
+// Until the user picks a theme we follow the operating system (the `prefers-color-scheme` media
+// query), live: switching the OS to dark at sunset switches the page too. Once they pick one it is
+// saved in localStorage and the OS setting no longer matters. Picking the theme the OS has anyway
+// forgets the saved choice, which is the way back to following the OS.
+//
+// The WASM takes a moment to load, and until then nobody would set that class: a dark mode user
+// would see the light page flash up on every reload. `ThemeScript` puts a tiny script in the
+// `<head>` that makes the same decision and sets the class before the page is painted for the first
+// time. The app then picks up from whatever the script did.
+
+const STORAGE_KEY: &str = "theme";
+const PREFERS_DARK: &str = "(prefers-color-scheme: dark)";
+
+/// The pre-paint script. The server escapes the text of a `<script>` tag like any other text, which
+/// would break quotes and slashes, so it uses template literals (backticks) for its strings.
+const PRE_PAINT_SCRIPT: &str = "try { var theme = localStorage.getItem(`theme`); if (theme === `dark` || (theme !== `light` && matchMedia(`(prefers-color-scheme: dark)`).matches)) document.documentElement.classList.add(`dark`) } catch (e) {}";
+
+/// What the user chose.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ThemePreference {
+    Light,
+    Dark,
+    /// No choice made (or it was taken back), use whatever the OS uses.
+    System,
+}
+
+impl ThemePreference {
+    /// A name for the preference, e.g. for a form field or a file. `from_tag` reads it back.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+
+    fn load() -> Self {
+        let saved = window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+        match saved.as_deref() {
+            Some("light") => Self::Light,
+            Some("dark") => Self::Dark,
+            _ => Self::System,
+        }
+    }
+
+    fn save(self) {
+        let Ok(Some(storage)) = window().local_storage() else {
+            return;
+        };
+        _ = match self {
+            Self::Light | Self::Dark => storage.set_item(STORAGE_KEY, self.tag()),
+            Self::System => storage.remove_item(STORAGE_KEY),
+        };
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ThemeContext {
+    pub preference: RwSignal<ThemePreference>,
+    /// `true` while dark mode is on, from the preference and the OS setting.
+    pub dark: Signal<bool>,
+    system_dark: ReadSignal<bool>,
+}
+
+impl ThemeContext {
+    pub fn is_dark(&self) -> bool {
+        self.dark.get()
+    }
+
+    /// Switches to the other theme. When that is the one the OS uses, the page goes back to
+    /// following the OS.
+    pub fn toggle(&self) {
+        let dark = !self.dark.get_untracked();
+        let preference = if dark == self.system_dark.get_untracked() {
+            ThemePreference::System
+        } else if dark {
+            ThemePreference::Dark
+        } else {
+            ThemePreference::Light
+        };
+        self.preference.set(preference);
+    }
+}
+
+/// Provides the theme to every component below `cx`. Render a `<ThemeScript/>` in the same scope.
+pub fn provide_theme(cx: Scope) -> ThemeContext {
+    // The server knows neither, it renders `System` and light. In the browser this makes the same
+    // decision the pre-paint script already made, so nothing changes on screen.
+    let system_dark = use_media_query(cx, PREFERS_DARK);
+    let preference = create_rw_signal(
+        cx,
+        if leptos_dom::is_server() {
+            ThemePreference::System
+        } else {
+            ThemePreference::load()
+        },
+    );
+    let dark = Signal::derive(cx, move || match preference.get() {
+        ThemePreference::Light => false,
+        ThemePreference::Dark => true,
+        ThemePreference::System => system_dark.get(),
+    });
+    let theme = ThemeContext {
+        preference,
+        dark,
+        system_dark,
+    };
+    provide_context(cx, theme);
+
+    // Effects only run in the browser.
+    create_effect(cx, move |_| {
+        if let Some(root) = document().document_element() {
+            _ = root.class_list().toggle_with_force("dark", dark.get());
+        }
+    });
+    // `previous` is `None` on the first run, the preference we just loaded doesn't need saving.
+    create_effect(cx, move |previous: Option<ThemePreference>| {
+        let preference = preference.get();
+        if previous.is_some_and(|previous| previous != preference) {
+            preference.save();
+        }
+        preference
+    });
+
+    theme
+}
+
+/// Pulls the theme out of context.
+pub fn use_theme(cx: Scope) -> ThemeContext {
+    expect_context::<ThemeContext>(cx, "App")
+}
+
+/// Adds the pre-paint script to the `<head>`.
+#[component]
+pub fn ThemeScript(cx: Scope) -> impl IntoView {
+    view! { cx, <Script>{PRE_PAINT_SCRIPT}</Script> }
+}
+
+/// Switches between light and dark.
+#[component]
+pub fn ThemeToggle(cx: Scope) -> impl IntoView {
+    let theme = use_theme(cx);
+
+    // Which icon shows is decided by CSS (`dark:`), not by the signal. The server doesn't know the
+    // theme, so anything rendered from the signal would differ between the server HTML and the
+    // browser and break hydration.
+    view! { cx,
+        <button class="text-xl" aria-label="Toggle dark mode" on:click=move |_| theme.toggle()>
+            <span class="dark:hidden">"🌙"</span>
+            <span class="hidden dark:inline">"☀️"</span>
+        </button>
+    }
+}
@@ -0,0 +1,246 @@
+use crate::context::expect_context;
+use leptos::*;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Toast notifications. `App` provides a `ToastContext` and renders a `<Toaster/>`, any component can
+// then call `use_toasts(cx).push("Saved!")`. At most `MAX_VISIBLE` toasts are on screen at the same
+// time, extra ones wait in a queue and are shown as soon as a slot frees up, so a burst of
+// notifications doesn't cover the whole page.
+
+/// How many toasts can be on screen at once.
+pub const MAX_VISIBLE: usize = 3;
+/// How long a toast stays before it starts leaving.
+const DISPLAY_TIME: Duration = Duration::from_secs(4);
+/// Must match the `duration-300` transition class used by `ToastItem`.
+const EXIT_ANIMATION: Duration = Duration::from_millis(300);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToastMessage {
+    pub id: usize,
+    pub text: String,
+    /// Set while the exit animation plays. The toast still takes up its slot until it is removed.
+    pub leaving: bool,
+    /// Optional button shown next to the text, e.g. "Undo".
+    pub action: Option<ToastAction>,
+}
+
+type OnceCallback = Box<dyn FnOnce()>;
+
+/// A labelled button attached to a toast. The callback runs at most once: clicking the button also
+/// dismisses the toast, and once the toast has started leaving the button can't be clicked anymore.
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: String,
+    // `Cell<Option<..>>` so that an `FnOnce` can be taken out and called through a shared reference.
+    callback: Rc<Cell<Option<OnceCallback>>>,
+}
+
+impl ToastAction {
+    pub fn new(label: impl Into<String>, callback: impl FnOnce() + 'static) -> Self {
+        Self {
+            label: label.into(),
+            callback: Rc::new(Cell::new(Some(Box::new(callback)))),
+        }
+    }
+
+    /// Runs the callback, unless it already ran.
+    fn run(&self) {
+        if let Some(callback) = self.callback.take() {
+            callback();
+        }
+    }
+}
+
+impl fmt::Debug for ToastAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToastAction")
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ToastAction {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.callback, &other.callback)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ToastContext {
+    next_id: StoredValue<usize>,
+    visible: RwSignal<Vec<ToastMessage>>,
+    queued: RwSignal<VecDeque<ToastMessage>>,
+}
+
+impl ToastContext {
+    /// Shows a toast, or queues it if `MAX_VISIBLE` toasts are already on screen.
+    pub fn push(&self, text: impl Into<String>) {
+        self.push_toast(text.into(), None);
+    }
+
+    /// Same as `push`, with an action button (label and callback) on the toast.
+    pub fn push_with_action(
+        &self,
+        text: impl Into<String>,
+        label: impl Into<String>,
+        callback: impl FnOnce() + 'static,
+    ) {
+        self.push_toast(text.into(), Some(ToastAction::new(label, callback)));
+    }
+
+    fn push_toast(&self, text: String, action: Option<ToastAction>) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        let toast = ToastMessage {
+            id,
+            text,
+            leaving: false,
+            action,
+        };
+
+        if self.visible.with_untracked(Vec::len) < MAX_VISIBLE {
+            self.show(toast);
+        } else {
+            self.queued.update(|queued| queued.push_back(toast));
+        }
+    }
+
+    /// Starts the exit animation of a toast, it is removed once the animation is done.
+    pub fn dismiss(&self, id: usize) {
+        let mut found = false;
+        self.visible.update(|visible| {
+            if let Some(toast) = visible
+                .iter_mut()
+                .find(|toast| toast.id == id && !toast.leaving)
+            {
+                toast.leaving = true;
+                found = true;
+            }
+        });
+        // Dismissing twice (the close button and the timer) must not schedule two removals.
+        if found {
+            let toasts = *self;
+            set_timeout(move || toasts.remove(id), EXIT_ANIMATION);
+        }
+    }
+
+    /// Toasts currently on screen, oldest first.
+    pub fn visible(&self) -> Vec<ToastMessage> {
+        self.visible.get()
+    }
+
+    /// Number of toasts waiting for a free slot.
+    pub fn queued_count(&self) -> usize {
+        self.queued.with(VecDeque::len)
+    }
+
+    fn show(&self, toast: ToastMessage) {
+        let id = toast.id;
+        self.visible.update(|visible| visible.push(toast));
+        let toasts = *self;
+        set_timeout(move || toasts.dismiss(id), DISPLAY_TIME);
+    }
+
+    fn remove(&self, id: usize) {
+        self.visible
+            .update(|visible| visible.retain(|toast| toast.id != id));
+        // A slot is free again, drain the queue into it.
+        while self.visible.with_untracked(Vec::len) < MAX_VISIBLE {
+            let mut next = None;
+            self.queued.update(|queued| next = queued.pop_front());
+            match next {
+                Some(toast) => self.show(toast),
+                None => break,
+            }
+        }
+    }
+}
+
+/// Provides the toast queue to every component below `cx`.
+pub fn provide_toasts(cx: Scope) -> ToastContext {
+    let toasts = ToastContext {
+        next_id: store_value(cx, 0),
+        visible: create_rw_signal(cx, Vec::new()),
+        queued: create_rw_signal(cx, VecDeque::new()),
+    };
+    provide_context(cx, toasts);
+    toasts
+}
+
+/// Pulls the toast queue out of context.
+pub fn use_toasts(cx: Scope) -> ToastContext {
+    expect_context::<ToastContext>(cx, "App")
+}
+
+/// Renders the visible toasts stacked in the bottom right corner.
+#[component]
+pub fn Toaster(cx: Scope) -> impl IntoView {
+    let toasts = use_toasts(cx);
+    let has_queued = move || toasts.queued_count() != 0;
+
+    view! { cx,
+        <div class="fixed bottom-4 right-4 flex flex-col gap-2 w-72 print:hidden">
+            // Keyed on the id, so a toast that leaves doesn't re-render (and re-animate) the ones below it.
+            <For
+                each=move || toasts.visible()
+                key=|toast| toast.id
+                view=move |cx, toast| view! { cx, <ToastItem toast=toast/> }
+            />
+            <Show when=has_queued fallback=|_| ()>
+                <p class="text-xs text-right text-gray-500">
+                    {move || format!("+{} more", toasts.queued_count())}
+                </p>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn ToastItem(cx: Scope, toast: ToastMessage) -> impl IntoView {
+    let toasts = use_toasts(cx);
+    let id = toast.id;
+
+    // Start hidden and flip to visible on the next frame, which lets the CSS transition play the enter animation.
+    let (entered, set_entered) = create_signal(cx, false);
+    request_animation_frame(move || set_entered(true));
+    let leaving = move || {
+        toasts
+            .visible
+            .with(|visible| visible.iter().any(|toast| toast.id == id && toast.leaving))
+    };
+    let shown = move || entered.get() && !leaving();
+
+    view! { cx,
+        <div
+            role="status"
+            class="flex items-start justify-between p-3 text-white bg-blue-800 rounded shadow-lg transition-all duration-300"
+            class=("opacity-0", move || !shown())
+            class=("translate-y-2", move || !shown())
+        >
+            <span>{toast.text}</span>
+            {toast.action.map(|action| {
+                let label = action.label.clone();
+                view! { cx,
+                    <button
+                        class="ml-auto mr-2 font-bold underline"
+                        // The toast may already be fading out (timer ran out), too late to act on it then.
+                        disabled=leaving
+                        on:click=move |_| {
+                            action.run();
+                            toasts.dismiss(id);
+                        }
+                    >
+                        {label}
+                    </button>
+                }
+            })}
+            <button class="ml-2" aria-label="Dismiss" on:click=move |_| toasts.dismiss(id)>
+                "×"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,167 @@
+use leptos::html::Div;
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+// A table of contents built from the page itself. Instead of keeping a list of sections next to the
+// content (and forgetting to update it), `<TableOfContents/>` looks at the headings that were actually
+// rendered inside a container and links to each of them.
+//
+// A link needs an id to point to. Headings that don't have one get one made from their text, e.g.
+// "Memo (right)" becomes `memo-right`.
+//
+// Content can change after the first render (a section shows up, a heading is renamed). A
+// `MutationObserver` tells us when anything inside the container changes, and the headings are
+// collected again on the next animation frame, once per frame however many changes there were.
+
+/// A heading found in the content.
+#[derive(Clone, Debug, PartialEq)]
+struct Heading {
+    id: String,
+    text: String,
+    /// 2 for `<h2>`, 3 for `<h3>`, ...
+    level: u8,
+}
+
+/// Links to the headings that match `selector` inside `content`, indented by level. Clicking one
+/// scrolls to it. Empty on the server, the headings are collected once the content is in the page.
+#[component]
+pub fn TableOfContents(
+    cx: Scope,
+    content: NodeRef<Div>,
+    /// Which headings are listed.
+    #[prop(default = "h2, h3")]
+    selector: &'static str,
+) -> impl IntoView {
+    let (headings, set_headings) = create_signal(cx, Vec::<Heading>::new());
+
+    if !leptos_dom::is_server() {
+        let scan = move || {
+            if let Some(content) = content.get_untracked() {
+                let found = collect_headings(&content, selector);
+                // Most changes (a counter ticking, text typed in an input) don't touch a heading.
+                if headings.with_untracked(|headings| *headings != found) {
+                    set_headings(found);
+                }
+            }
+        };
+        // Whether a scan is already waiting for the next frame.
+        let scheduled = store_value(cx, false);
+        let schedule = move || {
+            if !scheduled.get_value() {
+                scheduled.set_value(true);
+                request_animation_frame(move || {
+                    // The page may be gone by the next frame.
+                    if scheduled.try_set_value(false).is_none() {
+                        scan();
+                    }
+                });
+            }
+        };
+
+        let on_mutation = Closure::<dyn Fn()>::new(schedule);
+        if let Ok(observer) = web_sys::MutationObserver::new(on_mutation.as_ref().unchecked_ref()) {
+            content.on_load(cx, {
+                let observer = observer.clone();
+                move |content| {
+                    schedule();
+                    let options = web_sys::MutationObserverInit::new();
+                    options.set_child_list(true);
+                    options.set_subtree(true);
+                    options.set_character_data(true);
+                    // Not attributes: giving a heading its id is a change too, and would start another scan.
+                    _ = observer.observe_with_options(&content, &options);
+                }
+            });
+            on_cleanup(cx, move || {
+                observer.disconnect();
+                // Keep the callback alive until the observer is gone.
+                drop(on_mutation);
+            });
+        }
+    }
+
+    let links = move || {
+        headings
+            .get()
+            .into_iter()
+            .map(|Heading { id, text, level }| {
+                let href = format!("#{id}");
+                view! { cx,
+                    <li style:padding-left=format!("{}rem", level.saturating_sub(2))>
+                        <a
+                            href=href
+                            class="text-blue-700 hover:underline"
+                            // Scroll there ourselves. Left to the browser, the router would see a click on a link and
+                            // treat it as a navigation.
+                            on:click=move |event| {
+                                event.prevent_default();
+                                if let Some(heading) = document().get_element_by_id(&id) {
+                                    heading.scroll_into_view();
+                                }
+                            }
+                        >
+                            {text}
+                        </a>
+                    </li>
+                }
+            })
+            .collect_view(cx)
+    };
+
+    view! { cx,
+        <nav aria-label="On this page">
+            <ul>{links}</ul>
+        </nav>
+    }
+}
+
+/// The headings inside `content` that match `selector`, in page order. Gives the ones without an id
+/// one, unique within the document.
+fn collect_headings(content: &web_sys::Element, selector: &str) -> Vec<Heading> {
+    let Ok(nodes) = content.query_selector_all(selector) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<web_sys::Element>().ok())
+        .map(|element| {
+            let text = element
+                .text_content()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            if element.id().is_empty() {
+                element.set_id(&unique_id(&slug(&text)));
+            }
+            Heading {
+                id: element.id(),
+                text,
+                level: element.tag_name()[1..].parse().unwrap_or(2),
+            }
+        })
+        .collect()
+}
+
+/// Lowercase letters and digits, with a `-` for everything in between: "Memo (right)" is `memo-right`.
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// `base`, or `base-2`, `base-3`, ... if an element in the document already has that id.
+fn unique_id(base: &str) -> String {
+    let base = if base.is_empty() { "section" } else { base };
+    let taken = |id: &str| document().get_element_by_id(id).is_some();
+    (1..)
+        .map(|n| {
+            if n == 1 {
+                base.to_string()
+            } else {
+                format!("{base}-{n}")
+            }
+        })
+        .find(|id| !taken(id))
+        .unwrap_or_default()
+}
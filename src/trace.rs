@@ -0,0 +1,35 @@
+use leptos::*;
+use std::fmt::Debug;
+
+// Watching a signal change. Add `trace_signal(cx, "name", signal)` to a component while you work
+// on it and every change shows up in the browser console:
+//
+//     [trace] value = 0
+//     [trace] value: 0 -> 5
+//
+// It's an effect like any other: it reads the signal, so Leptos runs it again whenever the signal
+// changes. That makes it a handy way to see *when* things update, e.g. that a memo only fires
+// when its result really changed, or that a setter is called more often than you thought.
+
+/// Logs `signal` under `name` now and on every change, with the old and the new value. Only in
+/// debug builds, a release build logs nothing. Works with any signal type (`ReadSignal`,
+/// `RwSignal`, `Memo`, `Signal`).
+pub fn trace_signal<T, S>(cx: Scope, name: &'static str, signal: S)
+where
+    T: Debug,
+    S: SignalWith<T> + 'static,
+{
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    // The effect gets what it returned last time, here the previous value as text. Keeping the text
+    // instead of the value means `T` doesn't have to be `Clone`.
+    create_effect(cx, move |previous: Option<String>| {
+        let value = signal.with(|value| format!("{value:?}"));
+        match previous {
+            None => log::debug!("[trace] {name} = {value}"),
+            Some(previous) => log::debug!("[trace] {name}: {previous} -> {value}"),
+        }
+        value
+    });
+}
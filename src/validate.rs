@@ -0,0 +1,469 @@
+use crate::debounce::use_debounced;
+use leptos::*;
+use regex::Regex;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Form validation. A `Rule` checks a field's text and returns an error message when it doesn't
+// pass. A field gets a list of rules, `use_validation` runs all of them whenever the text changes
+// and hands out the messages of the ones that failed:
+//
+//     let username = use_validation(cx, move || name.get(), vec![required(), min_len(3)]);
+//
+// Rules run inside a memo, so a rule that reads a signal (e.g. "same as the password field") runs
+// again when that signal changes too.
+//
+// Some things only the server knows, e.g. whether a username is still free. Those checks are an
+// `AsyncRule`, see `use_async_validation`. While one is waiting for its answer the field is pending:
+// neither valid nor invalid yet.
+
+type Check = Rc<dyn Fn(&str) -> Option<String>>;
+type AsyncCheck = Rc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Option<String>>>>>;
+
+/// Checks a field's text. `None` means it passes, `Some(message)` says what's wrong.
+#[derive(Clone)]
+pub struct Rule(Check);
+
+impl Rule {
+    /// A rule from a check: the text passes when `check` returns `true`, otherwise the error is
+    /// `message`.
+    pub fn new(message: impl Into<String>, check: impl Fn(&str) -> bool + 'static) -> Self {
+        let message = message.into();
+        Self(Rc::new(move |value| {
+            (!check(value)).then(|| message.clone())
+        }))
+    }
+
+    /// Runs the rule on `value`.
+    pub fn check(&self, value: &str) -> Option<String> {
+        (self.0)(value)
+    }
+
+    /// The same rule with another error message.
+    pub fn with_message(self, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self(Rc::new(move |value| {
+            self.check(value).map(|_| message.clone())
+        }))
+    }
+
+    /// The same rule, except that an empty field passes. For fields that may be left empty, but
+    /// must be right when they're filled in.
+    pub fn optional(self) -> Self {
+        Self(Rc::new(move |value| {
+            if value.trim().is_empty() {
+                None
+            } else {
+                self.check(value)
+            }
+        }))
+    }
+}
+
+/// Something other than spaces must be filled in.
+pub fn required() -> Rule {
+    Rule::new("This field is required", |value| !value.trim().is_empty())
+}
+
+/// At least `length` characters (not bytes, so "é" counts once).
+pub fn min_len(length: usize) -> Rule {
+    Rule::new(format!("Use at least {length} characters"), move |value| {
+        value.chars().count() >= length
+    })
+}
+
+/// At most `length` characters.
+pub fn max_len(length: usize) -> Rule {
+    Rule::new(format!("Use at most {length} characters"), move |value| {
+        value.chars().count() <= length
+    })
+}
+
+/// The text must match the regular expression `pattern`. The pattern is written in the code, so an
+/// invalid one is a bug and panics right away instead of failing every input.
+pub fn matches(pattern: &str, message: impl Into<String>) -> Rule {
+    let regex = Regex::new(pattern).expect("a valid regular expression");
+    Rule::new(message, move |value| regex.is_match(value))
+}
+
+/// Looks like an email address: something, an `@`, and a domain with a dot. Whether the address
+/// really exists only the mail server knows.
+pub fn email() -> Rule {
+    matches(r"^[^@\s]+@[^@\s]+\.[^@\s]+$", "Enter an email address")
+}
+
+/// A check that takes a while, typically because it asks the server. Like a `Rule`, its future
+/// resolves to `None` when the text passes and to `Some(message)` when it doesn't. A check that
+/// can't get an answer (the server is down) should say so in its message, the field can't be known
+/// to be valid then.
+#[derive(Clone)]
+pub struct AsyncRule(AsyncCheck);
+
+impl AsyncRule {
+    pub fn new<F>(check: impl Fn(String) -> F + 'static) -> Self
+    where
+        F: Future<Output = Option<String>> + 'static,
+    {
+        Self(Rc::new(move |value| Box::pin(check(value))))
+    }
+}
+
+/// The answer of an `AsyncRule`, together with the text it is the answer for.
+#[derive(Clone, PartialEq)]
+struct Answer {
+    value: String,
+    error: Option<String>,
+}
+
+/// The validation state of one field, see `use_validation`.
+#[derive(Copy, Clone)]
+pub struct Validation {
+    errors: Memo<Vec<String>>,
+    pending: Signal<bool>,
+    touched: RwSignal<bool>,
+}
+
+impl Validation {
+    /// The messages of every rule that fails, in the order of the rules.
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.get()
+    }
+
+    /// The message of the first rule that fails.
+    pub fn first_error(&self) -> Option<String> {
+        self.errors.with(|errors| errors.first().cloned())
+    }
+
+    /// Whether an `AsyncRule` still has to answer for the current text. A pending field isn't
+    /// valid yet.
+    pub fn is_pending(&self) -> bool {
+        self.pending.get()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(Vec::is_empty) && !self.is_pending()
+    }
+
+    /// The errors to show: none until the user is done with the field (see `touch`), nobody wants
+    /// to be told a field is required before they had a chance to fill it in.
+    pub fn visible_errors(&self) -> Vec<String> {
+        if self.touched.get() {
+            self.errors()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Marks the field as done with, from here on its errors are shown. Call it when the field
+    /// loses focus, and for every field when the form is submitted.
+    pub fn touch(&self) {
+        self.touched.set(true);
+    }
+}
+
+/// Validates the text returned by `value` with `rules`, again whenever it changes.
+pub fn use_validation(
+    cx: Scope,
+    value: impl Fn() -> String + 'static,
+    rules: Vec<Rule>,
+) -> Validation {
+    let errors = create_memo(cx, move |_| {
+        let value = value();
+        rules.iter().filter_map(|rule| rule.check(&value)).collect()
+    });
+    Validation {
+        errors,
+        pending: Signal::derive(cx, || false),
+        touched: create_rw_signal(cx, false),
+    }
+}
+
+/// Validates the text returned by `value` with `rules` and, once those pass, with `check`.
+///
+/// `check` only runs after the text has stopped changing for `delay`, so typing a name doesn't ask
+/// the server about every prefix of it. The field is pending from the moment the text changes until
+/// the answer for exactly that text is in. Answers that arrive for older text (the user kept typing
+/// while the request was on its way) are dropped, they'd say something about another name.
+pub fn use_async_validation(
+    cx: Scope,
+    value: impl Fn() -> String + 'static,
+    rules: Vec<Rule>,
+    check: AsyncRule,
+    delay: Duration,
+) -> Validation {
+    // Read by the rules, the debounce and the answer below. A memo, so `value` runs once per change.
+    let value = create_memo(cx, move |_| value());
+    let settled_rules = rules.clone();
+    let sync_errors = create_memo(cx, move |_| {
+        value.with(|value| {
+            rules
+                .iter()
+                .filter_map(|rule| rule.check(value))
+                .collect::<Vec<_>>()
+        })
+    });
+    let answer = create_rw_signal(cx, None::<Answer>);
+    let settled = use_debounced(cx, move || value.get(), delay);
+    // Effects only run in the browser, the server never starts a check.
+    create_effect(cx, move |_| {
+        let text = settled.get();
+        // The rules run on the settled text, not the current one: that may already be a few letters
+        // further. They may read other signals, then this runs again when those change.
+        let fails_rules = settled_rules.iter().any(|rule| rule.check(&text).is_some());
+        let answered = answer
+            .with_untracked(|answer| answer.as_ref().is_some_and(|answer| answer.value == text));
+        if fails_rules || answered {
+            return;
+        }
+        let future = (check.0)(text.clone());
+        spawn_local(async move {
+            let error = future.await;
+            // Still the text we're waiting for? `try_` because the page may be gone by now.
+            if settled.try_get_untracked().as_ref() == Some(&text) {
+                _ = answer.try_set(Some(Answer { value: text, error }));
+            }
+        });
+    });
+
+    let state = create_memo(cx, move |_| {
+        value.with(|value| {
+            sync_errors.with(|sync_errors| {
+                answer.with(|answer| settle(value, sync_errors, answer.as_ref()))
+            })
+        })
+    });
+    let pending = Signal::derive(cx, move || state.with(|(_, pending)| *pending));
+    let errors = create_memo(cx, move |_| state.with(|(errors, _)| errors.clone()));
+
+    Validation {
+        errors,
+        pending,
+        touched: create_rw_signal(cx, false),
+    }
+}
+
+/// Where a field of `use_async_validation` with the text `value` stands: its errors, and whether it is
+/// pending. `answer` is the latest answer of the `AsyncRule`, for whatever text it was asked about.
+fn settle(value: &str, sync_errors: &[String], answer: Option<&Answer>) -> (Vec<String>, bool) {
+    // No use asking the server about text that already fails the rules, e.g. a name that is too short.
+    if !sync_errors.is_empty() {
+        return (sync_errors.to_vec(), false);
+    }
+    // The answer only counts for the text it was given for.
+    match answer.filter(|answer| answer.value == value) {
+        Some(answer) => (answer.error.iter().cloned().collect(), false),
+        None => (Vec::new(), true),
+    }
+}
+
+/// `true` when every field passes. Touches all of them, so the errors of the ones that don't pass
+/// show up. Call it when the form is submitted. A field that is still pending doesn't pass, the
+/// form should wait for its answer.
+pub fn validate_all(fields: &[Validation]) -> bool {
+    fields.iter().for_each(Validation::touch);
+    fields
+        .iter()
+        .all(|field| field.errors.with_untracked(Vec::is_empty) && !field.pending.get_untracked())
+}
+
+/// The errors of a field, below it. Only the first one unless `all` is set. While the field is
+/// pending it says so instead, whether touched or not: it explains why the form isn't done yet.
+#[component]
+pub fn FieldErrors(
+    cx: Scope,
+    validation: Validation,
+    #[prop(optional)] all: bool,
+) -> impl IntoView {
+    let shown = move || {
+        let mut errors = validation.visible_errors();
+        if !all {
+            errors.truncate(1);
+        }
+        errors
+    };
+
+    view! { cx,
+        <ul class="text-sm text-red-700" aria-live="polite">
+            {move || shown().into_iter().map(|error| view! { cx, <li>{error}</li> }).collect_view(cx)}
+            <Show when=move || validation.is_pending() fallback=|_| ()>
+                <li class="text-gray-500">"Checking…"</li>
+            </Show>
+        </ul>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_scope(test: impl FnOnce(Scope) + 'static) {
+        let runtime = create_runtime();
+        _ = create_scope(runtime, test);
+        runtime.dispose();
+    }
+
+    #[test]
+    fn required_needs_more_than_spaces() {
+        assert!(required().check("").is_some());
+        assert!(required().check("  \t").is_some());
+        assert_eq!(required().check(" x "), None);
+    }
+
+    #[test]
+    fn lengths_count_characters_not_bytes() {
+        assert_eq!(min_len(3).check("éèê"), None);
+        assert!(min_len(3).check("ab").is_some());
+        assert_eq!(max_len(3).check("éèê"), None);
+        assert_eq!(
+            max_len(3).check("abcd"),
+            Some("Use at most 3 characters".to_string())
+        );
+    }
+
+    #[test]
+    fn email_wants_an_at_and_a_domain_with_a_dot() {
+        assert_eq!(email().check("ferris@rust-lang.org"), None);
+        for bad in [
+            "ferris",
+            "ferris@",
+            "ferris@rust",
+            "@rust.org",
+            "fer ris@rust.org",
+            "a@b@c.d",
+        ] {
+            assert!(email().check(bad).is_some(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn matches_uses_the_given_message() {
+        let digits = matches(r"^\d+$", "Digits only");
+        assert_eq!(digits.check("123"), None);
+        assert_eq!(digits.check("12a"), Some("Digits only".to_string()));
+    }
+
+    #[test]
+    fn with_message_replaces_the_message_only_on_failure() {
+        let rule = required().with_message("Tell us your name");
+        assert_eq!(rule.check(""), Some("Tell us your name".to_string()));
+        assert_eq!(rule.check("Ferris"), None);
+    }
+
+    #[test]
+    fn optional_lets_an_empty_field_pass() {
+        let rule = email().optional();
+        assert_eq!(rule.check(""), None);
+        assert_eq!(rule.check("   "), None);
+        assert!(rule.check("ferris").is_some());
+    }
+
+    #[test]
+    fn use_validation_lists_every_failing_rule_in_order() {
+        in_scope(|cx| {
+            let (name, set_name) = create_signal(cx, String::new());
+            let field = use_validation(cx, move || name.get(), vec![required(), min_len(3)]);
+            assert_eq!(
+                field.errors(),
+                ["This field is required", "Use at least 3 characters"]
+            );
+            assert_eq!(
+                field.first_error().as_deref(),
+                Some("This field is required")
+            );
+            set_name("ab".to_string());
+            assert_eq!(field.errors(), ["Use at least 3 characters"]);
+            set_name("abc".to_string());
+            assert!(field.is_valid());
+        });
+    }
+
+    #[test]
+    fn rules_that_read_signals_run_again_when_those_change() {
+        in_scope(|cx| {
+            let (password, set_password) = create_signal(cx, "secret".to_string());
+            let (repeated, _) = create_signal(cx, "secret".to_string());
+            let same = Rule::new("The passwords differ", move |value| {
+                password.with(|password| password == value)
+            });
+            let field = use_validation(cx, move || repeated.get(), vec![same]);
+            assert!(field.is_valid());
+            set_password("another".to_string());
+            assert_eq!(field.errors(), ["The passwords differ"]);
+        });
+    }
+
+    #[test]
+    fn errors_are_shown_once_the_field_is_touched() {
+        in_scope(|cx| {
+            let field = use_validation(cx, String::new, vec![required()]);
+            assert!(field.visible_errors().is_empty());
+            field.touch();
+            assert_eq!(field.visible_errors(), ["This field is required"]);
+        });
+    }
+
+    fn answer(value: &str, error: Option<&str>) -> Answer {
+        Answer {
+            value: value.to_string(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn a_field_waits_for_the_answer_for_its_text() {
+        assert_eq!(settle("ferris", &[], None), (Vec::new(), true));
+        let taken = answer("ferris", Some("That name is taken"));
+        assert_eq!(
+            settle("ferris", &[], Some(&taken)),
+            (vec!["That name is taken".to_string()], false)
+        );
+        let free = answer("ferris", None);
+        assert_eq!(settle("ferris", &[], Some(&free)), (Vec::new(), false));
+    }
+
+    #[test]
+    fn an_answer_for_older_text_does_not_count() {
+        let taken = answer("ferr", Some("That name is taken"));
+        assert_eq!(settle("ferris", &[], Some(&taken)), (Vec::new(), true));
+    }
+
+    #[test]
+    fn text_that_fails_the_rules_is_not_checked() {
+        let too_short = vec!["Use at least 3 characters".to_string()];
+        assert_eq!(settle("fe", &too_short, None), (too_short.clone(), false));
+        // Not even an answer that happens to be there for it.
+        let taken = answer("fe", Some("That name is taken"));
+        assert_eq!(settle("fe", &too_short, Some(&taken)), (too_short, false));
+    }
+
+    #[test]
+    fn a_pending_field_does_not_pass() {
+        in_scope(|cx| {
+            // The check never answers, effects don't run outside the browser anyway.
+            let check = AsyncRule::new(|_| std::future::pending());
+            let field = use_async_validation(
+                cx,
+                || "ferris".to_string(),
+                vec![required()],
+                check,
+                Duration::ZERO,
+            );
+            assert!(field.is_pending());
+            assert!(!field.is_valid());
+            assert!(!validate_all(&[field]));
+        });
+    }
+
+    #[test]
+    fn validate_all_touches_every_field() {
+        in_scope(|cx| {
+            let valid = use_validation(cx, || "Ferris".to_string(), vec![required()]);
+            let invalid = use_validation(cx, String::new, vec![required()]);
+            assert!(!validate_all(&[valid, invalid]));
+            assert_eq!(invalid.visible_errors(), ["This field is required"]);
+            assert!(validate_all(&[valid]));
+        });
+    }
+}
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+// Optimistic concurrency for rows that several people can edit. Every row carries a version, which goes up
+// by one with every change. An edit remembers the version it started from, and saving it only goes
+// through while the row still has that version. Otherwise someone else saved in between, and writing over
+// their change without asking would lose it. The caller gets the row as it is now instead and can merge
+// (see `merge_field`) or give up.
+//
+// Nothing is locked while an edit is open. Conflicts are rare, and the one who runs into one finds out
+// when saving, which is the right moment to decide what to do about it.
+
+/// A row with its id and version.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub id: usize,
+    pub version: u64,
+    pub value: T,
+}
+
+/// What became of a save.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Saved<T> {
+    /// The row had the expected version, this is it with the new value and the next version.
+    Saved(Versioned<T>),
+    /// The row changed since the edit started. Nothing was saved, this is the row as it is now.
+    Conflict(Versioned<T>),
+    /// There is no row with that id.
+    Missing,
+}
+
+/// Saves `value` as row `id`, if the row still has `version`.
+pub fn save<T: Clone>(rows: &mut [Versioned<T>], id: usize, version: u64, value: T) -> Saved<T> {
+    let Some(row) = rows.iter_mut().find(|row| row.id == id) else {
+        return Saved::Missing;
+    };
+    if row.version != version {
+        return Saved::Conflict(row.clone());
+    }
+    row.version += 1;
+    row.value = value;
+    Saved::Saved(row.clone())
+}
+
+/// One field after a three-way merge.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Merged<F> {
+    /// Only one side changed it (or both the same way), this is the value to keep.
+    Agreed(F),
+    /// Both changed it, each their own way. Somebody has to pick.
+    Clash { mine: F, theirs: F },
+}
+
+/// Merges a field that was `base` when the edit started, is `mine` in the edit and `theirs` in the row
+/// that was saved in the meantime. A change on one side wins over no change on the other.
+pub fn merge_field<F: Clone + PartialEq>(base: &F, mine: &F, theirs: &F) -> Merged<F> {
+    if mine == theirs || theirs == base {
+        Merged::Agreed(mine.clone())
+    } else if mine == base {
+        Merged::Agreed(theirs.clone())
+    } else {
+        Merged::Clash {
+            mine: mine.clone(),
+            theirs: theirs.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_rows() -> Vec<Versioned<&'static str>> {
+        vec![
+            Versioned {
+                id: 0,
+                version: 1,
+                value: "Mario",
+            },
+            Versioned {
+                id: 1,
+                version: 4,
+                value: "Luigi",
+            },
+        ]
+    }
+
+    #[test]
+    fn saves_on_the_expected_version() {
+        let mut rows = two_rows();
+        let saved = Versioned {
+            id: 1,
+            version: 5,
+            value: "Weegee",
+        };
+        assert_eq!(save(&mut rows, 1, 4, "Weegee"), Saved::Saved(saved.clone()));
+        assert_eq!(rows[1], saved);
+        // The other rows are left alone.
+        assert_eq!(rows[0].value, "Mario");
+    }
+
+    #[test]
+    fn rejects_an_edit_of_an_older_version() {
+        let mut rows = two_rows();
+        assert_eq!(
+            save(&mut rows, 1, 3, "Weegee"),
+            Saved::Conflict(two_rows()[1].clone())
+        );
+        assert_eq!(rows, two_rows());
+    }
+
+    #[test]
+    fn the_second_of_two_edits_of_the_same_version_conflicts() {
+        let mut rows = two_rows();
+        assert!(matches!(
+            save(&mut rows, 0, 1, "Dr. Mario"),
+            Saved::Saved(_)
+        ));
+        assert_eq!(
+            save(&mut rows, 0, 1, "Super Mario"),
+            Saved::Conflict(Versioned {
+                id: 0,
+                version: 2,
+                value: "Dr. Mario",
+            })
+        );
+    }
+
+    #[test]
+    fn an_unknown_row_is_missing() {
+        assert_eq!(save(&mut two_rows(), 7, 1, "Wario"), Saved::Missing);
+    }
+
+    #[test]
+    fn merges_a_change_on_one_side() {
+        assert_eq!(merge_field(&1985, &1986, &1985), Merged::Agreed(1986));
+        assert_eq!(merge_field(&1985, &1985, &1986), Merged::Agreed(1986));
+        assert_eq!(merge_field(&1985, &1985, &1985), Merged::Agreed(1985));
+    }
+
+    #[test]
+    fn the_same_change_on_both_sides_is_no_clash() {
+        assert_eq!(merge_field(&1985, &1986, &1986), Merged::Agreed(1986));
+    }
+
+    #[test]
+    fn different_changes_on_both_sides_clash() {
+        assert_eq!(
+            merge_field(&1985, &1986, &1990),
+            Merged::Clash {
+                mine: 1986,
+                theirs: 1990
+            }
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use futures::{
+    channel::mpsc::{unbounded, UnboundedSender},
+    future::{abortable, poll_fn, AbortHandle},
+    SinkExt, StreamExt,
+};
+use leptos::*;
+use reqwasm::websocket::{futures::WebSocket, Message, State};
+use std::{task::Poll, time::Duration};
+
+// A WebSocket as a couple of signals, built on reqwasm's `WebSocket`. That one is a `Stream` of the messages
+// received and a `Sink` for the ones to send. `use_websocket` runs each connection as a task that feeds the
+// stream into signals a component can read, and closes the connection when the component goes away.
+//
+// When the connection drops without us asking for it (server restart, flaky network), we reconnect.
+// The wait between attempts doubles every time (1s, 2s, 4s, ... up to `MAX_RECONNECT_DELAY`), so a server
+// that is down isn't flooded with attempts. Once a connection opens the wait starts at 1s again.
+
+/// The wait before the first reconnect attempt.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// The wait never gets longer than this.
+pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WebSocketStatus {
+    Connecting,
+    Open,
+    /// The connection dropped, we are waiting to try again.
+    Reconnecting,
+    Closed,
+}
+
+#[derive(Copy, Clone)]
+pub struct WebSocketHandle {
+    /// The latest text message received, `None` until the first one arrives.
+    pub message: ReadSignal<Option<String>>,
+    pub status: ReadSignal<WebSocketStatus>,
+    set_message: WriteSignal<Option<String>>,
+    set_status: WriteSignal<WebSocketStatus>,
+    url: StoredValue<String>,
+    connection: StoredValue<Option<Connection>>,
+    // Reconnect attempts since the connection was last open, decides how long the next wait is.
+    attempts: StoredValue<u32>,
+    reconnect_timer: StoredValue<Option<TimeoutHandle>>,
+    // Set by `close` (and when the component goes away). A connection we closed ourselves stays closed.
+    closed: StoredValue<bool>,
+}
+
+impl WebSocketHandle {
+    /// Sends a text message. Returns `false` if it couldn't be sent, e.g. because the connection isn't open.
+    pub fn send(&self, message: &str) -> bool {
+        self.status.try_get_untracked() == Some(WebSocketStatus::Open)
+            && self
+                .connection
+                .try_with_value(|connection| {
+                    connection.as_ref().is_some_and(|connection| {
+                        connection
+                            .outgoing
+                            .unbounded_send(message.to_string())
+                            .is_ok()
+                    })
+                })
+                .unwrap_or(false)
+    }
+
+    /// Closes the connection for good, it won't be reconnected.
+    pub fn close(&self) {
+        self.closed.set_value(true);
+        if let Some(timer) = self
+            .reconnect_timer
+            .try_update_value(Option::take)
+            .flatten()
+        {
+            timer.clear();
+        }
+        // Dropping the connection closes it, see `Drop` below.
+        self.connection.set_value(None);
+        self.set_status.set(WebSocketStatus::Closed);
+    }
+
+    fn connect(self) {
+        let url = self.url.get_value();
+        let Ok(mut socket) = WebSocket::open(&url) else {
+            // Not a valid WebSocket url, trying again won't help.
+            self.set_status.set(WebSocketStatus::Closed);
+            return;
+        };
+        // `send` queues messages here, the task below hands them to the socket one at a time.
+        let (outgoing, mut queued) = unbounded::<String>();
+
+        let (task, abort) = abortable(async move {
+            // reqwasm doesn't tell us when the socket opens, but the sink is ready from then on. Until then
+            // nothing arrives on the stream, except the error and the close of a connection that failed.
+            let opened = poll_fn(|cx| {
+                if socket.poll_ready_unpin(cx).is_ready() {
+                    return Poll::Ready(matches!(socket.state(), State::Open));
+                }
+                socket.poll_next_unpin(cx).map(|_| false)
+            })
+            .await;
+            if !opened {
+                return;
+            }
+            self.attempts.set_value(0);
+            self.set_status.set(WebSocketStatus::Open);
+
+            let (mut sink, mut stream) = socket.split();
+            let receive = async {
+                while let Some(message) = stream.next().await {
+                    // Binary messages are ignored, we only deal in text here. Errors are always followed
+                    // by the close, which ends the stream.
+                    if let Ok(Message::Text(text)) = message {
+                        self.set_message.set(Some(text));
+                    }
+                }
+            };
+            let send = async {
+                while let Some(text) = queued.next().await {
+                    if sink.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // The connection is over once the stream ends, not before.
+                futures::future::pending::<()>().await;
+            };
+            futures::pin_mut!(receive, send);
+            futures::future::select(receive, send).await;
+        });
+        spawn_local(async move {
+            // An aborted task belongs to a connection we closed ourselves, only a dropped one is reconnected.
+            if task.await.is_ok() {
+                self.schedule_reconnect();
+            }
+        });
+        // Replacing the old connection (if any) drops it, see `Drop` below.
+        self.connection
+            .set_value(Some(Connection { outgoing, abort }));
+    }
+
+    fn schedule_reconnect(self) {
+        if self.closed.try_get_value() != Some(false) {
+            return;
+        }
+        let attempt = self.attempts.get_value();
+        self.attempts.set_value(attempt + 1);
+        self.set_status.set(WebSocketStatus::Reconnecting);
+        let timer = set_timeout_with_handle(
+            move || {
+                // `close` clears the timer, but better safe than reconnecting a socket nobody wants.
+                if self.closed.try_get_value() == Some(false) {
+                    self.set_status.set(WebSocketStatus::Connecting);
+                    self.connect();
+                }
+            },
+            reconnect_delay(attempt),
+        );
+        self.reconnect_timer.set_value(timer.ok());
+    }
+}
+
+/// How long to wait before reconnect attempt number `attempt` (starting at 0): 1s, 2s, 4s, ...,
+/// capped at `MAX_RECONNECT_DELAY`.
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    INITIAL_RECONNECT_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_RECONNECT_DELAY)
+}
+
+/// The task running a connection, and the way to send messages through it.
+struct Connection {
+    outgoing: UnboundedSender<String>,
+    abort: AbortHandle,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // Aborting drops the task, and with it the socket, which closes it.
+        self.abort.abort();
+    }
+}
+
+/// Connects to `url` (e.g. "wss://echo.websocket.org") and keeps the connection open while `cx` lives,
+/// reconnecting when it drops. On the server nothing is connected and `status` stays `Connecting`.
+pub fn use_websocket(cx: Scope, url: &str) -> WebSocketHandle {
+    let (message, set_message) = create_signal(cx, None);
+    let (status, set_status) = create_signal(cx, WebSocketStatus::Connecting);
+    let handle = WebSocketHandle {
+        message,
+        status,
+        set_message,
+        set_status,
+        url: store_value(cx, url.to_string()),
+        connection: store_value(cx, None),
+        attempts: store_value(cx, 0),
+        reconnect_timer: store_value(cx, None),
+        closed: store_value(cx, false),
+    };
+    if leptos_dom::is_server() {
+        return handle;
+    }
+
+    handle.connect();
+    on_cleanup(cx, move || handle.close());
+
+    handle
+}
@@ -0,0 +1,45 @@
+use leptos::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WindowSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// The size of the browser window, updated when it is resized. On the server, where there is no
+/// window, it is `0 x 0`.
+pub fn use_window_size(cx: Scope) -> ReadSignal<WindowSize> {
+    let (size, set_size) = create_signal(
+        cx,
+        WindowSize {
+            width: 0.0,
+            height: 0.0,
+        },
+    );
+    if leptos_dom::is_server() {
+        return size;
+    }
+
+    let measure = || {
+        let window = window();
+        let dimension = |value: Result<wasm_bindgen::JsValue, _>| {
+            value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0)
+        };
+        WindowSize {
+            width: dimension(window.inner_width()),
+            height: dimension(window.inner_height()),
+        }
+    };
+    set_size(measure());
+
+    let on_resize = Closure::<dyn Fn()>::new(move || set_size(measure()));
+    _ = window().add_event_listener_with_callback("resize", on_resize.as_ref().unchecked_ref());
+    // Remove the listener when the component goes away, otherwise every visit of the page would add another one.
+    on_cleanup(cx, move || {
+        _ = window()
+            .remove_event_listener_with_callback("resize", on_resize.as_ref().unchecked_ref());
+    });
+
+    size
+}
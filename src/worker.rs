@@ -0,0 +1,141 @@
+use js_sys::{Array, Promise, Reflect};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Blob, BlobPropertyBag, ErrorEvent, MessageEvent, Url, Worker, WorkerOptions, WorkerType,
+};
+
+// Runs a function of our own WASM in a Web Worker, a background thread of the browser. Everything
+// else (event handlers, rendering, timers) runs on the main thread, one thing at a time: while a
+// long computation runs there, the page can't react to anything. In a worker it runs next to the
+// page instead.
+//
+// A worker can't share memory with the page, it is a separate copy of the app. It loads the same
+// JS and WASM files the page loaded (cached by now), calls one of the functions the WASM exports
+// (`#[wasm_bindgen] pub fn ...`) and posts the result back. Input and result are copied between
+// the two, so they are plain JS values: numbers, strings, arrays, objects.
+
+/// Where cargo-leptos puts the JS and WASM, see `output-name` and `site-pkg-dir` in Cargo.toml. Without
+/// cargo-leptos (wasm-pack) the WASM file gets a `_bg` suffix, like in leptos_integration_utils.
+const SCRIPT: &str = match option_env!("LEPTOS_OUTPUT_NAME") {
+    Some(name) => name,
+    None => env!("CARGO_PKG_NAME"),
+};
+const WASM_SUFFIX: &str = match option_env!("LEPTOS_OUTPUT_NAME") {
+    Some(_) => "",
+    None => "_bg",
+};
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum WorkerError {
+    /// The browser didn't let us start a worker.
+    #[error("Could not start the worker: {0}")]
+    Start(String),
+    /// The worker started, but loading the app or running the function failed.
+    #[error("The worker failed: {0}")]
+    Failed(String),
+}
+
+/// Calls the exported WASM function `function` with `input` in a new worker and returns what it
+/// returned. The worker is stopped when the result is in, or when the returned future is dropped
+/// before that (e.g. the component that started it went away).
+pub async fn run_in_worker(function: &str, input: JsValue) -> Result<JsValue, WorkerError> {
+    let worker = start()?;
+
+    // A promise that settles with the first message (or error) from the worker.
+    let mut on_message = None;
+    let mut on_error = None;
+    let answer = Promise::new(&mut |resolve, reject| {
+        let message = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            _ = resolve.call1(&JsValue::NULL, &event.data());
+        });
+        let error = Closure::<dyn Fn(ErrorEvent)>::new(move |event: ErrorEvent| {
+            _ = reject.call1(&JsValue::NULL, &event.message().into());
+        });
+        worker
+            .0
+            .set_onmessage(Some(message.as_ref().unchecked_ref()));
+        worker.0.set_onerror(Some(error.as_ref().unchecked_ref()));
+        // Kept until the answer is in, the worker calls them.
+        on_message = Some(message);
+        on_error = Some(error);
+    });
+
+    let request = js_sys::Object::new();
+    _ = Reflect::set(&request, &"function".into(), &function.into());
+    _ = Reflect::set(&request, &"input".into(), &input);
+    worker
+        .0
+        .post_message(&request)
+        .map_err(|error| WorkerError::Start(describe(&error)))?;
+
+    let answer = JsFuture::from(answer)
+        .await
+        .map_err(|error| WorkerError::Failed(describe(&error)))?;
+    drop((on_message, on_error));
+    // The worker answers `{ ok: result }` or `{ error: "message" }`, see `worker_script`.
+    match Reflect::get(&answer, &"error".into())
+        .ok()
+        .and_then(|error| error.as_string())
+    {
+        Some(error) => Err(WorkerError::Failed(error)),
+        None => Ok(Reflect::get(&answer, &"ok".into()).unwrap_or(JsValue::UNDEFINED)),
+    }
+}
+
+/// The worker's code. It imports the app as a module, waits for the WASM to load, then runs the
+/// requested function for every message.
+fn worker_script(origin: &str) -> String {
+    format!(
+        r#"import * as app from "{origin}/pkg/{SCRIPT}.js";
+const ready = app.default("{origin}/pkg/{SCRIPT}{WASM_SUFFIX}.wasm");
+self.onmessage = async (event) => {{
+  await ready;
+  try {{
+    self.postMessage({{ ok: app[event.data.function](event.data.input) }});
+  }} catch (error) {{
+    self.postMessage({{ error: String(error) }});
+  }}
+}};
+"#
+    )
+}
+
+/// A running worker. Dropping it stops the worker.
+struct RunningWorker(Worker, String);
+
+impl Drop for RunningWorker {
+    fn drop(&mut self) {
+        self.0.terminate();
+        _ = Url::revoke_object_url(&self.1);
+    }
+}
+
+fn start() -> Result<RunningWorker, WorkerError> {
+    let start_error = |error: JsValue| WorkerError::Start(describe(&error));
+    let origin = leptos::window().location().origin().map_err(start_error)?;
+    // The script is handed to the worker as an in-memory file (a blob URL), so it can't go out of
+    // sync with the app the way a separate .js file in public/ could.
+    let parts = Array::of1(&worker_script(&origin).into());
+    let options = BlobPropertyBag::new();
+    options.set_type("text/javascript");
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options).map_err(start_error)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(start_error)?;
+    // A module worker, so it can `import` the app.
+    let options = WorkerOptions::new();
+    options.set_type(WorkerType::Module);
+    let worker = Worker::new_with_options(&url, &options).map_err(start_error)?;
+    Ok(RunningWorker(worker, url))
+}
+
+// JS errors are often `Error` objects, which don't turn into a string by themselves.
+fn describe(error: &JsValue) -> String {
+    error
+        .as_string()
+        .or_else(|| {
+            error
+                .dyn_ref::<js_sys::Error>()
+                .map(|error| error.message().into())
+        })
+        .unwrap_or_else(|| format!("{error:?}"))
+}
@@ -1,9 +1,23 @@
 /** @type {import('tailwindcss').Config} */
 module.exports = {
   content: ["*.html", "./src/**/*.rs",],
+  darkMode: "class",
   theme: {
-    extend: {},
+    extend: {
+      // Page transitions, used by <AnimatedRoutes> in src/app.rs. Going back slides the other way.
+      keyframes: {
+        "route-out": { to: { opacity: "0", transform: "translateX(-1rem)" } },
+        "route-in": { from: { opacity: "0", transform: "translateX(1rem)" } },
+        "route-out-back": { to: { opacity: "0", transform: "translateX(1rem)" } },
+        "route-in-back": { from: { opacity: "0", transform: "translateX(-1rem)" } },
+      },
+      animation: {
+        "route-out": "route-out 150ms ease-in forwards",
+        "route-in": "route-in 150ms ease-out",
+        "route-out-back": "route-out-back 150ms ease-in forwards",
+        "route-in-back": "route-in-back 150ms ease-out",
+      },
+    },
   },
   plugins: [],
 }
-